@@ -0,0 +1,72 @@
+//! Compare walking imports and formatting a stream which registers the same
+//! import many times, before and after [Tokens::dedup_registered].
+//!
+//! Run with `cargo bench --bench dedup_registered`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use genco::prelude::*;
+
+const REGISTRATIONS: usize = 10_000;
+const ITERATIONS: u32 = 20;
+
+fn tokens() -> rust::Tokens {
+    let map = rust::import("std::collections", "HashMap");
+    let mut tokens = rust::Tokens::new();
+
+    for _ in 0..REGISTRATIONS {
+        tokens.register(&map);
+    }
+
+    quote_in!(tokens => let map = $(&map)::new(););
+    tokens
+}
+
+fn measure<F>(mut f: F) -> Duration
+where
+    F: FnMut(),
+{
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        f();
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let duplicated = tokens();
+    let mut deduped = duplicated.clone();
+    deduped.dedup_registered();
+
+    println!(
+        "registrations: {} duplicated, {} deduped",
+        duplicated.walk_imports().count(),
+        deduped.walk_imports().count()
+    );
+
+    for (name, tokens) in [("duplicated", &duplicated), ("deduped", &deduped)] {
+        let walk = measure(|| {
+            black_box(tokens.walk_imports().count());
+        });
+
+        let format = measure(|| {
+            black_box(tokens.to_file_string().unwrap());
+        });
+
+        println!(
+            "{}: walk_imports {:?}, to_file_string {:?}",
+            name, walk, format
+        );
+    }
+
+    let dedup = measure(|| {
+        let mut tokens = duplicated.clone();
+        tokens.dedup_registered();
+        black_box(tokens);
+    });
+
+    println!("dedup_registered: {:?}", dedup);
+}
@@ -0,0 +1,62 @@
+//! Compare interpolating a large fragment into many files by cloning it
+//! against sharing it through [Tokens::append_shared].
+//!
+//! Run with `cargo bench --bench shared`.
+
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use genco::prelude::*;
+
+const FILES: usize = 500;
+const ITERATIONS: u32 = 20;
+
+fn fragment() -> rust::Tokens {
+    let map = rust::import("std::collections", "HashMap");
+    let mut tokens = rust::Tokens::new();
+
+    for n in 0..22 {
+        quote_in!(tokens => let $(format!("v{}", n)) = $(&map)::new(););
+        tokens.push();
+    }
+
+    tokens
+}
+
+fn measure<F>(mut f: F) -> Duration
+where
+    F: FnMut(),
+{
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        f();
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let fragment = fragment();
+    let shared = Arc::new(fragment.clone());
+
+    println!("fragment: {} items", fragment.len());
+
+    let cloned = measure(|| {
+        for _ in 0..FILES {
+            let tokens: rust::Tokens = quote!(fn foo() { $(&fragment) });
+            black_box(tokens);
+        }
+    });
+
+    let shared = measure(|| {
+        for _ in 0..FILES {
+            let tokens: rust::Tokens = quote!(fn foo() { $(&shared) });
+            black_box(tokens);
+        }
+    });
+
+    println!("cloned: {:?} per {} files", cloned, FILES);
+    println!("shared: {:?} per {} files", shared, FILES);
+}
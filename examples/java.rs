@@ -24,7 +24,7 @@ fn main() -> anyhow::Result<()> {
     let stdout = std::io::stdout();
     let mut w = fmt::IoWriter::new(stdout.lock());
 
-    let fmt = fmt::Config::from_lang::<Java>().with_newline("\n\r");
+    let fmt = fmt::Config::from_lang::<Java>().with_newline("\r\n");
     let config = java::Config::default().with_package("se.tedro");
 
     tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
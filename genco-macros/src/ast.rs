@@ -95,6 +95,7 @@ pub(crate) enum ControlKind {
     Space,
     Push,
     Line,
+    SoftBreak,
 }
 
 #[derive(Debug)]
@@ -174,6 +175,13 @@ pub(crate) enum Ast {
         /// The inner stream processed.
         stream: TokenStream,
     },
+    /// A loop which repeats for as long as a condition holds.
+    While {
+        /// Expression being used as a condition.
+        condition: syn::Expr,
+        /// The inner stream processed.
+        stream: TokenStream,
+    },
     Condition {
         /// Expression being use as a condition.
         condition: syn::Expr,
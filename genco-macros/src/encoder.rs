@@ -59,6 +59,7 @@ impl<'a> Encoder<'a> {
     /// Encode a single item into the encoder.
     pub(crate) fn encode(&mut self, cursor: Cursor, ast: Ast) -> Result<()> {
         self.step(cursor)?;
+        self.item_buffer.mark(cursor.start);
 
         match ast {
             Ast::Tree { tt, .. } => {
@@ -103,6 +104,9 @@ impl<'a> Encoder<'a> {
             Ast::DelimiterClose { delimiter, .. } => {
                 self.encode_close_delimiter(delimiter);
             }
+            Ast::While { condition, stream } => {
+                self.encode_while(condition, stream);
+            }
             Ast::Condition {
                 condition,
                 then_branch,
@@ -196,6 +200,10 @@ impl<'a> Encoder<'a> {
                 self.output
                     .extend(q::quote_spanned!(control.span => #receiver.line();));
             }
+            ControlKind::SoftBreak => {
+                self.output
+                    .extend(q::quote_spanned!(control.span => #receiver.soft_break();));
+            }
         }
     }
 
@@ -246,9 +254,9 @@ impl<'a> Encoder<'a> {
         if let Some(join) = join {
             self.output.extend(q::quote! {
                 {
-                    let mut __it = IntoIterator::into_iter(#expr).peekable();
+                    let mut __it = ::core::iter::IntoIterator::into_iter(#expr).peekable();
 
-                    while let Some(#pattern) = __it.next() {
+                    while let ::core::option::Option::Some(#pattern) = __it.next() {
                         #stream
 
                         if __it.peek().is_some() {
@@ -266,6 +274,15 @@ impl<'a> Encoder<'a> {
         }
     }
 
+    /// Encode a while loop with an inner stream.
+    pub(crate) fn encode_while(&mut self, condition: syn::Expr, stream: TokenStream) {
+        self.item_buffer.flush(&mut self.output);
+
+        self.output.extend(q::quote! {
+            while #condition { #stream }
+        });
+    }
+
     /// Encode an if statement with an inner stream.
     pub(crate) fn encode_condition(
         &mut self,
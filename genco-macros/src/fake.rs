@@ -18,32 +18,60 @@ pub(crate) struct LineColumn {
 }
 
 impl LineColumn {
-    #[cfg(proc_macro_span)]
-    pub(crate) fn start(span: Span) -> Option<Self> {
-        let span = span.unwrap().start();
+    #[cfg(feature = "locations")]
+    fn new(line_column: proc_macro2::LineColumn) -> Option<Self> {
+        // Line numbers are 1-based, so a zero line indicates that the
+        // compiler does not expose span locations.
+        if line_column.line == 0 {
+            return None;
+        }
 
         Some(Self {
-            line: span.line(),
-            column: span.column(),
+            line: line_column.line,
+            column: line_column.column,
         })
     }
 
-    #[cfg(proc_macro_span)]
+    #[cfg(feature = "locations")]
+    pub(crate) fn start(span: Span) -> Option<Self> {
+        Self::new(span.start())
+    }
+
+    #[cfg(feature = "locations")]
     pub(crate) fn end(span: Span) -> Option<Self> {
-        let span = span.unwrap().end();
+        Self::new(span.end())
+    }
 
-        Some(Self {
+    // NB: only compiled if the build script detected a compiler where the
+    // location of spans is stable.
+    #[cfg(all(not(feature = "locations"), proc_macro_span))]
+    #[allow(clippy::incompatible_msrv)]
+    pub(crate) fn start(span: Span) -> Option<Self> {
+        Some(Self::from_span(span.unwrap().start()))
+    }
+
+    #[cfg(all(not(feature = "locations"), proc_macro_span))]
+    #[allow(clippy::incompatible_msrv)]
+    pub(crate) fn end(span: Span) -> Option<Self> {
+        Some(Self::from_span(span.unwrap().end()))
+    }
+
+    #[cfg(all(not(feature = "locations"), proc_macro_span))]
+    #[allow(clippy::incompatible_msrv)]
+    fn from_span(span: proc_macro::Span) -> Self {
+        // NB: columns reported by the compiler are 1-based.
+        Self {
             line: span.line(),
-            column: span.column(),
-        })
+            column: span.column().saturating_sub(1),
+        }
     }
 
-    #[cfg(not(proc_macro_span))]
+    #[cfg(all(not(feature = "locations"), not(proc_macro_span)))]
     pub(crate) fn start(_: Span) -> Option<Self> {
         None
     }
 
-    #[cfg(not(proc_macro_span))]
+    #[cfg(all(not(feature = "locations"), not(proc_macro_span)))]
     pub(crate) fn end(_: Span) -> Option<Self> {
         None
     }
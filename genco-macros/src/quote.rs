@@ -3,7 +3,7 @@ use syn::parse::{ParseBuffer, ParseStream};
 use syn::spanned::Spanned;
 use syn::{token, Result, Token};
 
-use crate::ast::{Ast, Control, Delimiter, LiteralName, MatchArm, Name};
+use crate::ast::{Ast, Control, ControlKind, Delimiter, LiteralName, MatchArm, Name};
 use crate::encoder::Encoder;
 use crate::fake::Buf;
 use crate::fake::LineColumn;
@@ -185,6 +185,25 @@ impl<'a> Quote<'a> {
         Ok((req, ast))
     }
 
+    /// Parse `while <condition> { <quoted> }` or `while <condition> => <quoted>`.
+    fn parse_while(&self, input: ParseStream) -> Result<(Requirements, Ast)> {
+        input.parse::<Token![while]>()?;
+        let condition = syn::Expr::parse_without_eager_brace(input)?;
+
+        let content;
+
+        let input = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            input
+        } else {
+            syn::braced!(content in input);
+            &content
+        };
+
+        let (req, stream) = Quote::new(self.cx).parse(input)?;
+        Ok((req, Ast::While { condition, stream }))
+    }
+
     fn parse_match(&self, input: ParseStream) -> Result<(Requirements, Ast)> {
         input.parse::<Token![match]>()?;
         let condition = syn::Expr::parse_without_eager_brace(input)?;
@@ -310,6 +329,10 @@ impl<'a> Quote<'a> {
             let (req, ast) = self.parse_loop(&scope)?;
             encoder.requirements.merge_with(req);
             ast
+        } else if scope.peek(Token![while]) {
+            let (req, ast) = self.parse_while(&scope)?;
+            encoder.requirements.merge_with(req);
+            ast
         } else if scope.peek(Token![match]) {
             let (req, ast) = self.parse_match(&scope)?;
             encoder.requirements.merge_with(req);
@@ -380,6 +403,22 @@ impl<'a> Quote<'a> {
                             },
                         )?;
                     }
+                    (LiteralName::Ident("soft"), content) => {
+                        if let Some(content) = content {
+                            return Err(syn::Error::new(
+                                content.span(),
+                                "Control `soft` does not expect an argument",
+                            ));
+                        }
+
+                        let control = Control {
+                            kind: ControlKind::SoftBreak,
+                            span: name.span(),
+                        };
+
+                        let cursor = self.buf.join(start.span(), end.span())?;
+                        encoder.encode(cursor, Ast::Control { control })?;
+                    }
                     (LiteralName::Char(c), content) => {
                         let control = match Control::from_char(name.span(), c) {
                             Some(control) => control,
@@ -401,7 +440,7 @@ impl<'a> Quote<'a> {
                     (LiteralName::Ident(string), _) => {
                         return Err(syn::Error::new(
                             name.span(),
-                            format!("Unsupported function `{string}`, expected one of: str"),
+                            format!("Unsupported function `{string}`, expected one of: str, soft"),
                         ));
                     }
                 }
@@ -1,4 +1,4 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned as _;
 use syn::{Result, Token};
@@ -24,9 +24,12 @@ impl Parse for QuoteIn {
 
         let Ctxt { receiver, module } = &cx;
 
+        let target = syn::Ident::new("__genco_macros_target", Span::call_site());
+
         // Give the assignment its own span to improve diagnostics.
         let assign_mut = q::quote_spanned! { expr.span() =>
-            let #receiver: &mut #module::tokens::Tokens<_> = &mut #expr;
+            let mut #target = #module::tokens::QuoteTarget::quote_target(&mut #expr);
+            let #receiver: &mut #module::tokens::Tokens<_> = &mut *#target;
         };
 
         let stream = q::quote! {{
@@ -1,11 +1,14 @@
 use proc_macro2::{Span, TokenStream};
 
+use crate::fake::LineColumn;
 use crate::Ctxt;
 
 /// Buffer used to resolve static items.
 pub(crate) struct StaticBuffer<'a> {
     cx: &'a Ctxt,
     buffer: String,
+    /// Where the buffered content starts in the source.
+    start: Option<LineColumn>,
 }
 
 impl<'a> StaticBuffer<'a> {
@@ -14,6 +17,15 @@ impl<'a> StaticBuffer<'a> {
         Self {
             cx,
             buffer: String::new(),
+            start: None,
+        }
+    }
+
+    /// Mark the location in the source at which the buffer starts, unless
+    /// content has already been buffered.
+    pub(crate) fn mark(&mut self, start: LineColumn) {
+        if self.buffer.is_empty() && self.start.is_none() {
+            self.start = Some(start);
         }
     }
 
@@ -34,7 +46,15 @@ impl<'a> StaticBuffer<'a> {
 
             let s = syn::LitStr::new(&self.buffer, Span::call_site());
             tokens.extend(q::quote!(#receiver.append(#module::tokens::ItemStr::Static(#s));));
+
+            #[cfg(feature = "locations")]
+            if let Some(LineColumn { line, column }) = self.start {
+                tokens.extend(q::quote!(#receiver.__location(file!(), #line, #column);));
+            }
+
             self.buffer.clear();
         }
+
+        self.start = None;
     }
 }
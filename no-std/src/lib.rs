@@ -0,0 +1,101 @@
+//! Checks that genco can be used with only `alloc` available.
+//!
+//! This should be built and tested on its own so that the `std` feature of
+//! genco isn't enabled by other members of the workspace:
+//!
+//! ```bash
+//! cargo test -p genco-no-std
+//! ```
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use genco::fmt;
+use genco::prelude::*;
+
+/// Format the given tokens as a file using a [fmt::FmtWriter], which is how
+/// output is collected without access to `std::io`.
+pub fn to_file_string(tokens: &rust::Tokens) -> fmt::Result<String> {
+    let mut w = fmt::FmtWriter::new(String::new());
+    let fmt = fmt::Config::from_lang::<Rust>();
+    tokens.format_file(&mut w.as_formatter(&fmt), &rust::Config::default())?;
+    Ok(w.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use genco::prelude::*;
+
+    use super::to_file_string;
+
+    #[test]
+    fn test_quote() -> genco::fmt::Result {
+        let vec = rust::import("alloc::vec", "Vec");
+        let fields = vec!["a", "b", "c"];
+
+        let tokens: rust::Tokens = quote! {
+            struct Foo {
+                $(for f in &fields join (,$['\r']) => $(*f): u32)
+            }
+
+            fn build() -> $(&vec)<u32> {
+                $(if fields.is_empty() {
+                    $(&vec)::new()
+                } else {
+                    $vec::with_capacity($(fields.len()))
+                })
+            }
+        };
+
+        let expected = "use alloc::vec::Vec;\n\nstruct Foo {\n    a: u32,\n    b: u32,\n    c: u32\n}\n\nfn build() -> Vec<u32> {\n    Vec::with_capacity(3)\n}\n";
+
+        assert_eq!(expected, to_file_string(&tokens)?);
+        assert_eq!(expected, tokens.to_file_string()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_quote_in() -> genco::fmt::Result {
+        let mut tokens = rust::Tokens::new();
+        let count = 2usize;
+
+        let greet = quote_fn! {
+            fn greet() -> &'static str {
+                $(quoted("hello"))
+            }
+        };
+
+        quote_in! { tokens =>
+            $greet
+
+            $(match count {
+                0 => fn empty() {},
+                n => fn count() -> usize { $n },
+            })
+        };
+
+        assert_eq!(
+            "fn greet() -> &'static str {\n    \"hello\"\n}\n\nfn count() -> usize { 2 }\n",
+            to_file_string(&tokens)?
+        );
+
+        Ok(())
+    }
+
+    /// Compilers before 1.88 don't expose where spans are located, so only
+    /// whitespace within a single line is detected unless the `locations`
+    /// feature is enabled.
+    #[test]
+    fn test_single_line() -> genco::fmt::Result {
+        let count = 2usize;
+        let tokens: rust::Tokens = quote!(fn count() -> usize { $count });
+
+        assert_eq!("fn count() -> usize { 2 }\n", to_file_string(&tokens)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+use crate::fmt;
+use std::collections::VecDeque;
+use std::io;
+use std::mem;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::vec::Vec;
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// The default size of the chunks buffered output is split into at line
+/// boundaries.
+const DEFAULT_THRESHOLD: usize = 8 * 1024;
+
+/// Helper struct to format a token stream to an underlying writer implementing
+/// [AsyncWrite][tokio::io::AsyncWrite].
+///
+/// Since formatting is synchronous, output is collected in an internal buffer,
+/// which is split into chunks at line boundaries once they grow past a
+/// [threshold][Self::with_threshold]. Completed chunks are written to the
+/// underlying writer for as long as it accepts output without waiting.
+/// Whatever remains is written when [flush][Self::flush] is awaited.
+///
+/// Formatting never waits for the underlying writer, so if it's slower than
+/// formatting, the output of a whole token stream is buffered in memory until
+/// it's flushed. To bound the memory used for large outputs, format them as
+/// many smaller token streams and flush in between them, like below.
+///
+/// See also [Tokens::format_file_async][crate::Tokens::format_file_async].
+///
+/// This requires the `tokio` feature to be enabled.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::fmt;
+///
+/// async fn run() -> Result<(), genco::fmt::WriteFileError> {
+///     let mut w = fmt::AsyncIoWriter::new(Vec::<u8>::new());
+///
+///     let fmt = fmt::Config::from_lang::<Rust>();
+///     let config = rust::Config::default();
+///
+///     for n in 0..3 {
+///         let tokens: rust::Tokens = quote!(fn $(format!("f{n}"))() {});
+///         tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+///         w.flush().await?;
+///     }
+///
+///     let vector = w.into_inner();
+///
+///     assert_eq!("fn f0() {}\nfn f1() {}\nfn f2() {}\n", std::str::from_utf8(&vector).unwrap());
+///     Ok(())
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build()?.block_on(run())?;
+/// # Ok::<_, genco::fmt::WriteFileError>(())
+/// ```
+pub struct AsyncIoWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    writer: W,
+    /// Completed chunks of output, waiting to be written.
+    chunks: VecDeque<Vec<u8>>,
+    /// The number of bytes of the first chunk which have been written.
+    written: usize,
+    /// Output which hasn't been completed into a chunk yet.
+    buffer: Vec<u8>,
+    threshold: usize,
+    waker: Waker,
+    error: Option<io::Error>,
+}
+
+impl<W> AsyncIoWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Construct a new writer which writes formatted output to the given
+    /// asynchronous writer.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            chunks: VecDeque::new(),
+            written: 0,
+            buffer: Vec::new(),
+            threshold: DEFAULT_THRESHOLD,
+            waker: Waker::from(Arc::new(NoopWake)),
+            error: None,
+        }
+    }
+
+    /// Set the number of buffered bytes after which output is completed into
+    /// a chunk at the next line boundary, and written to the underlying writer
+    /// without waiting for [flush][Self::flush].
+    ///
+    /// This defaults to 8 KiB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn foo() {}
+    ///     fn bar() {}
+    /// };
+    ///
+    /// let mut w = fmt::AsyncIoWriter::new(Vec::<u8>::new()).with_threshold(0);
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    /// tokens.format_file(&mut w.as_formatter(&fmt), &rust::Config::default())?;
+    ///
+    /// // Every completed line has been written without flushing, since a
+    /// // vector never has to wait.
+    /// assert_eq!(b"fn foo() {}\nfn bar() {}\n", &w.into_inner()[..]);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_threshold(self, threshold: usize) -> Self {
+        Self { threshold, ..self }
+    }
+
+    /// Convert into a formatter, which formats into the internal buffer.
+    pub fn as_formatter<'a>(&'a mut self, config: &'a fmt::Config) -> fmt::Formatter<'a> {
+        fmt::Formatter::new(self, config)
+    }
+
+    /// Write all buffered output to the underlying writer and flush it.
+    ///
+    /// # Errors
+    ///
+    /// Errors if writing to the underlying writer fails, including while
+    /// output was written past the threshold during formatting. In that case
+    /// any remaining buffered output is discarded.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        if let Some(error) = self.error.take() {
+            self.chunks.clear();
+            self.written = 0;
+            self.buffer.clear();
+            return Err(error);
+        }
+
+        if !self.buffer.is_empty() {
+            self.chunks.push_back(mem::take(&mut self.buffer));
+        }
+
+        while let Some(chunk) = self.chunks.front() {
+            self.writer.write_all(&chunk[self.written..]).await?;
+            self.chunks.pop_front();
+            self.written = 0;
+        }
+
+        self.writer.flush().await
+    }
+
+    /// Convert into underlying writer.
+    ///
+    /// Output which hasn't been [flushed][Self::flush] is discarded.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Write as many completed chunks as the underlying writer accepts
+    /// without waiting.
+    ///
+    /// NB: this polls with a waker that does nothing. That's fine, since the
+    /// writer is polled again with a real waker when flushing.
+    fn write_ready(&mut self) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let mut cx = Context::from_waker(&self.waker);
+
+        while let Some(chunk) = self.chunks.front() {
+            let buf = &chunk[self.written..];
+
+            match Pin::new(&mut self.writer).poll_write(&mut cx, buf) {
+                Poll::Ready(Ok(0)) => {
+                    self.error = Some(io::ErrorKind::WriteZero.into());
+                    break;
+                }
+                Poll::Ready(Ok(n)) => {
+                    self.written += n;
+
+                    if self.written == chunk.len() {
+                        self.chunks.pop_front();
+                        self.written = 0;
+                    }
+                }
+                Poll::Ready(Err(error)) => {
+                    self.error = Some(error);
+                    break;
+                }
+                Poll::Pending => break,
+            }
+        }
+    }
+}
+
+impl<W> core::fmt::Write for AsyncIoWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.buffer.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl<W> fmt::Write for AsyncIoWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    #[inline(always)]
+    fn write_line(&mut self, config: &fmt::Config) -> fmt::Result {
+        self.buffer.extend_from_slice(config.newline.as_bytes());
+
+        if self.buffer.len() >= self.threshold {
+            self.chunks.push_back(mem::take(&mut self.buffer));
+            self.write_ready();
+        }
+
+        Ok(())
+    }
+}
+
+struct NoopWake;
+
+impl Wake for NoopWake {
+    fn wake(self: Arc<Self>) {}
+}
@@ -0,0 +1,124 @@
+use crate::fmt;
+
+/// Writer which compares the output of the formatter to existing content
+/// instead of storing it.
+///
+/// This can be used to determine whether regenerating a file would change
+/// it, without rewriting it. Comparison stops at the first difference, after
+/// which the rest of the output is ignored.
+///
+/// See also [Tokens::differs_from][crate::Tokens::differs_from].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::fmt;
+///
+/// let tokens: rust::Tokens = quote! {
+///     fn foo() {
+///         bar();
+///     }
+/// };
+///
+/// let fmt = fmt::Config::from_lang::<Rust>();
+/// let config = rust::Config::default();
+///
+/// let mut w = fmt::ComparingWriter::new("fn foo() {\n    bar();\n}\n");
+/// tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+/// assert!(!w.changed());
+/// assert_eq!(None, w.first_difference());
+///
+/// let mut w = fmt::ComparingWriter::new("fn foo() {\n    baz();\n}\n");
+/// tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+/// assert!(w.changed());
+/// assert_eq!(Some(1), w.first_difference());
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ComparingWriter<'a> {
+    existing: &'a str,
+    /// The number of bytes of the existing content which have been matched.
+    offset: usize,
+    /// The line of the existing content being compared.
+    line: usize,
+    /// The line at which the output diverged, if it has.
+    difference: Option<usize>,
+}
+
+impl<'a> ComparingWriter<'a> {
+    /// Construct a new writer comparing output to the given existing
+    /// content.
+    pub fn new(existing: &'a str) -> Self {
+        Self {
+            existing,
+            offset: 0,
+            line: 0,
+            difference: None,
+        }
+    }
+
+    /// Convert into a formatter.
+    pub fn as_formatter<'b>(&'b mut self, config: &'b fmt::Config) -> fmt::Formatter<'b> {
+        fmt::Formatter::new(self, config)
+    }
+
+    /// Test if the output written so far differs from the existing content.
+    ///
+    /// Once formatting is done, this also reports a difference if the
+    /// existing content is longer than the output.
+    pub fn changed(&self) -> bool {
+        self.first_difference().is_some()
+    }
+
+    /// The first line, starting at 0, at which the output differs from the
+    /// existing content, if it does.
+    pub fn first_difference(&self) -> Option<usize> {
+        if self.difference.is_some() {
+            return self.difference;
+        }
+
+        if self.offset < self.existing.len() {
+            return Some(self.line);
+        }
+
+        None
+    }
+
+    fn compare(&mut self, s: &str) {
+        if self.difference.is_some() {
+            return;
+        }
+
+        let rest = &self.existing.as_bytes()[self.offset..];
+
+        let matched = s.bytes().zip(rest).take_while(|(a, b)| a == *b).count();
+
+        self.line += s.as_bytes()[..matched]
+            .iter()
+            .filter(|b| **b == b'\n')
+            .count();
+
+        if matched < s.len() {
+            self.difference = Some(self.line);
+        } else {
+            self.offset += matched;
+        }
+    }
+}
+
+impl core::fmt::Write for ComparingWriter<'_> {
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.compare(s);
+        Ok(())
+    }
+}
+
+impl fmt::Write for ComparingWriter<'_> {
+    #[inline(always)]
+    fn write_line(&mut self, config: &fmt::Config) -> fmt::Result {
+        self.compare(config.newline);
+        Ok(())
+    }
+}
@@ -1,4 +1,11 @@
+use crate::fmt::span::{Span, SpanListener};
+use crate::fmt::{ParseConfigError, Profile};
 use crate::lang::Lang;
+use crate::tokens::ItemStr;
+use alloc::string::ToString;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::str::FromStr;
 
 /// Indentation configuration.
 ///
@@ -30,12 +37,149 @@ use crate::lang::Lang;
 /// };
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Indentation {
     /// Each indentation is the given number of spaces.
     Space(usize),
     /// Each indentation is a tab.
     Tab,
+    /// Each indentation is the given string, like three spaces or a tab
+    /// followed by a space.
+    ///
+    /// When tracking columns, each indentation is as wide as the number of
+    /// characters in the string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn foo() -> u32 {
+    ///         42u32
+    ///     }
+    /// };
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>()
+    ///     .with_indentation(fmt::Indentation::Custom("\t "));
+    ///
+    /// assert_eq!(
+    ///     "fn foo() -> u32 {\n\t 42u32\n}\n",
+    ///     tokens.to_file_string_with(&rust::Config::default(), &fmt)?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    Custom(&'static str),
+}
+
+/// How the width of written text is measured when tracking columns.
+///
+/// This determines what [Formatter::column][crate::fmt::Formatter::column]
+/// reports for lines containing non-ASCII characters.
+///
+/// ```
+/// use genco::fmt;
+/// use genco::prelude::*;
+///
+/// let fmt = fmt::Config::from_lang::<Rust>()
+///     .with_width_measure(fmt::WidthMode::Chars);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WidthMode {
+    /// Each character counts as one column.
+    #[default]
+    Chars,
+    /// Characters count as their displayed width in a terminal, so that wide
+    /// characters like CJK ideographs and emoji occupy two columns and
+    /// combining characters occupy none.
+    ///
+    /// This requires the `unicode-width` feature to be enabled.
+    #[cfg(feature = "unicode-width")]
+    Unicode,
+}
+
+impl WidthMode {
+    /// Measure the width of the given string.
+    pub(super) fn measure(self, s: &str) -> usize {
+        match self {
+            WidthMode::Chars => s.chars().count(),
+            #[cfg(feature = "unicode-width")]
+            WidthMode::Unicode => unicode_width::UnicodeWidthStr::width(s),
+        }
+    }
+}
+
+/// Where the [prelude][Config::with_prelude] and [header][Config::with_header]
+/// of a file are written, relative to the lines which a language requires to
+/// come first, like build constraints in Go or the coding declaration in
+/// Python.
+///
+/// A [shebang][Config::with_shebang] is always written first, regardless of
+/// the placement.
+///
+/// # Examples
+///
+/// ```
+/// use genco::fmt;
+/// use genco::prelude::*;
+///
+/// let tokens: go::Tokens = quote!(func Example() {});
+///
+/// let config = go::Config::default()
+///     .with_package("example")
+///     .with_build_constraint("linux");
+///
+/// let fmt = fmt::Config::from_lang::<Go>().with_header(["Code generated. DO NOT EDIT."]);
+///
+/// assert_eq!(
+///     vec![
+///         "//go:build linux",
+///         "",
+///         "// Code generated. DO NOT EDIT.",
+///         "",
+///         "package example",
+///         "",
+///         "func Example() {}",
+///     ],
+///     tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+/// );
+///
+/// let fmt = fmt.with_header_placement(fmt::HeaderPlacement::BeforeDirectives);
+///
+/// assert_eq!(
+///     vec![
+///         "// Code generated. DO NOT EDIT.",
+///         "",
+///         "//go:build linux",
+///         "",
+///         "package example",
+///         "",
+///         "func Example() {}",
+///     ],
+///     tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeaderPlacement {
+    /// Write the prelude and header before the lines which a language
+    /// requires to come first.
+    BeforeDirectives,
+    /// Write the prelude and header after the lines which a language requires
+    /// to come first. This is the default.
+    #[default]
+    AfterDirectives,
+}
+
+/// The header of a file.
+#[derive(Debug, Clone)]
+pub(super) enum Header {
+    /// Lines which are written as line comments.
+    Lines(Vec<ItemStr>),
+    /// Text which is written as-is.
+    Raw(ItemStr),
 }
 
 /// Configuration to use for formatting output.
@@ -45,6 +189,32 @@ pub struct Config {
     pub(super) indentation: Indentation,
     /// What to use as a newline.
     pub(super) newline: &'static str,
+    /// How to measure the width of written text, if set.
+    pub(super) width_measure: Option<WidthMode>,
+    /// Whether trailing commas should be written.
+    pub(super) trailing_comma: bool,
+    /// Whether operators should be surrounded by spaces.
+    pub(super) operator_spacing: bool,
+    /// The width at which lines are broken at soft breaks.
+    pub(super) max_width: Option<usize>,
+    /// Interpreter directive written as the first line of files.
+    pub(super) shebang: Option<ItemStr>,
+    /// Text written at the top of files.
+    pub(super) prelude: Option<ItemStr>,
+    /// Header written after the prelude of files.
+    pub(super) header: Option<Header>,
+    /// Where the header is written.
+    pub(super) header_placement: HeaderPlacement,
+    /// Whether a byte order mark should be written at the start of files.
+    pub(super) bom: bool,
+    /// Whether files should be terminated with a line ending.
+    pub(super) trailing_newline: bool,
+    /// The maximum depth of nested token streams being formatted.
+    pub(super) max_depth: Option<usize>,
+    /// Whether line endings and indentation should be left out.
+    pub(super) minify: bool,
+    /// Callback receiving the span of every item written.
+    pub(super) span_listener: Option<SpanListener>,
 }
 
 impl Config {
@@ -54,9 +224,76 @@ impl Config {
     where
         L: Lang,
     {
+        Self::new(L::default_indentation())
+    }
+
+    /// Construct a formatter configuration from a preset [Profile], which
+    /// bundles indentation, newline, and trailing newline settings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: java::Tokens = quote! {
+    ///     int foo() {
+    ///         return 42;
+    ///     }
+    /// };
+    ///
+    /// let config = java::Config::default();
+    ///
+    /// let fmt = fmt::Config::profile(fmt::Profile::Google);
+    /// assert_eq!(
+    ///     "int foo() {\n  return 42;\n}\n",
+    ///     tokens.to_file_string_with(&config, &fmt)?
+    /// );
+    ///
+    /// let fmt = fmt::Config::profile(fmt::Profile::GoFmt);
+    /// assert_eq!(
+    ///     "int foo() {\n\treturn 42;\n}\n",
+    ///     tokens.to_file_string_with(&config, &fmt)?
+    /// );
+    ///
+    /// let fmt = fmt::Config::profile(fmt::Profile::Compact);
+    /// assert_eq!(
+    ///     "int foo() {\n return 42;\n}",
+    ///     tokens.to_file_string_with(&config, &fmt)?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn profile(profile: Profile) -> Self {
+        let (indentation, trailing_newline) = match profile {
+            Profile::Google => (Indentation::Space(2), true),
+            Profile::Rustfmt => (Indentation::Space(4), true),
+            Profile::GoFmt => (Indentation::Tab, true),
+            Profile::Compact => (Indentation::Space(1), false),
+        };
+
+        Self {
+            trailing_newline,
+            ..Self::new(indentation)
+        }
+    }
+
+    fn new(indentation: Indentation) -> Self {
         Self {
-            indentation: L::default_indentation(),
+            indentation,
             newline: "\n",
+            width_measure: None,
+            trailing_comma: false,
+            operator_spacing: true,
+            max_width: None,
+            shebang: None,
+            prelude: None,
+            header: None,
+            header_placement: HeaderPlacement::AfterDirectives,
+            bom: false,
+            trailing_newline: true,
+            max_depth: None,
+            minify: false,
+            span_listener: None,
         }
     }
 
@@ -68,8 +305,721 @@ impl Config {
         }
     }
 
-    /// Set what to use as newline.
+    /// Set what to use as newline, which is `"\n"` by default.
+    ///
+    /// This is used for all line breaks produced by the formatter, including
+    /// the ones in preludes and imports. Newline characters which are part of
+    /// literals are written as-is.
+    ///
+    /// The newline must be either `"\n"` or `"\r\n"`. Formatting with any
+    /// other newline fails, and the failure is recorded as
+    /// [ValidationError::InvalidNewline] in
+    /// [Formatter::error][crate::fmt::Formatter::error].
+    ///
+    /// [ValidationError::InvalidNewline]: crate::tokens::ValidationError::InvalidNewline
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: csharp::Tokens = quote! {
+    ///     class Foo {
+    ///         void Bar() {}
+    ///     }
+    /// };
+    ///
+    /// let fmt = fmt::Config::from_lang::<Csharp>().with_newline("\r\n");
+    ///
+    /// assert_eq!(
+    ///     "class Foo {\r\n    void Bar() {}\r\n}\r\n",
+    ///     tokens.to_file_string_with(&csharp::Config::default(), &fmt)?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
     pub fn with_newline(self, newline: &'static str) -> Self {
         Self { newline, ..self }
     }
+
+    /// Set how the width of written text is measured when tracking columns.
+    ///
+    /// Columns are only tracked if something consumes them, which is the case
+    /// if a width measure is set, or if a [span
+    /// listener][Self::with_span_listener] or [maximum
+    /// width][Self::with_max_width] is configured, in which case
+    /// [WidthMode::Chars] is used unless another measure is set. Set a width
+    /// measure to use [Formatter::column][crate::fmt::Formatter::column] when
+    /// formatting language items.
+    pub fn with_width_measure(self, width_measure: WidthMode) -> Self {
+        Self {
+            width_measure: Some(width_measure),
+            ..self
+        }
+    }
+
+    /// Set whether trailing commas should be written after the last item of
+    /// multi-line lists.
+    ///
+    /// This is consulted by [tokens::delimited_list], and is disabled by
+    /// default.
+    ///
+    /// [tokens::delimited_list]: crate::tokens::delimited_list()
+    pub fn with_trailing_comma(self, trailing_comma: bool) -> Self {
+        Self {
+            trailing_comma,
+            ..self
+        }
+    }
+
+    /// Set whether operators formatted through [tokens::binop] should be
+    /// surrounded by spaces, like `a + b` instead of `a+b`.
+    ///
+    /// This is enabled by default.
+    ///
+    /// [tokens::binop]: crate::tokens::binop()
+    pub fn with_operator_spacing(self, operator_spacing: bool) -> Self {
+        Self {
+            operator_spacing,
+            ..self
+        }
+    }
+
+    /// Set the width which lines should not exceed, as measured by the
+    /// configured [WidthMode].
+    ///
+    /// Lines which would exceed it are broken at the last [soft
+    /// break][crate::Tokens::soft_break] which lets the text fit, and are
+    /// continued on the next line with one extra level of indentation. Lines
+    /// without soft breaks are left as they are.
+    ///
+    /// This is not set by default, in which case soft breaks are always
+    /// written as spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn main() {
+    ///         let value = compute(first_argument,$[soft]second_argument,$[soft]third_argument);
+    ///     }
+    /// };
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>().with_max_width(60);
+    /// let output = tokens.to_file_string_with(&rust::Config::default(), &fmt)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "fn main() {",
+    ///         "    let value = compute(first_argument, second_argument,",
+    ///         "        third_argument);",
+    ///         "}",
+    ///     ],
+    ///     output.lines().collect::<Vec<_>>()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_max_width(self, max_width: usize) -> Self {
+        Self {
+            max_width: Some(max_width),
+            ..self
+        }
+    }
+
+    /// Set text to write at the top of files, before anything else including
+    /// imports, like a license header.
+    ///
+    /// The prelude is written verbatim and is separated from the rest of the
+    /// file by an empty line. Like the [header][Self::with_header], it's
+    /// written after the lines which a language requires to come first, unless
+    /// the [header placement][Self::with_header_placement] says otherwise.
+    /// It's only written when formatting files, like with
+    /// [Tokens::format_file][crate::Tokens::format_file].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    /// let tokens: rust::Tokens = quote!(let m = $map::<u32, u32>::new(););
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>()
+    ///     .with_prelude("// Copyright 2026 Example Authors\n// SPDX-License-Identifier: MIT");
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// tokens.format_file(&mut w.as_formatter(&fmt), &rust::Config::default())?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "// Copyright 2026 Example Authors",
+    ///         "// SPDX-License-Identifier: MIT",
+    ///         "",
+    ///         "use std::collections::HashMap;",
+    ///         "",
+    ///         "let m = HashMap::<u32, u32>::new();",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_prelude<P>(self, prelude: P) -> Self
+    where
+        P: Into<ItemStr>,
+    {
+        Self {
+            prelude: Some(prelude.into()),
+            ..self
+        }
+    }
+
+    /// Set a header to write at the top of files, where each line is written
+    /// as a line comment using the [comment syntax][Lang::line_comment] of
+    /// the language being formatted.
+    ///
+    /// The header is written after the [prelude][Self::with_prelude] and is
+    /// separated from the rest of the file by an empty line. Where it's
+    /// written relative to lines which a language requires to come first is
+    /// determined by the [header placement][Self::with_header_placement].
+    /// Like the prelude, this only applies when formatting files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let header = ["Code generated by example. DO NOT EDIT.", "", "Source: example.proto"];
+    /// let fmt = fmt::Config::from_lang::<Go>().with_header(header);
+    ///
+    /// let tokens: go::Tokens = quote!(func Example() {});
+    /// let config = go::Config::default()
+    ///     .with_package("example")
+    ///     .with_build_constraint("linux");
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "//go:build linux",
+    ///         "",
+    ///         "// Code generated by example. DO NOT EDIT.",
+    ///         "//",
+    ///         "// Source: example.proto",
+    ///         "",
+    ///         "package example",
+    ///         "",
+    ///         "func Example() {}",
+    ///     ],
+    ///     tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+    /// );
+    ///
+    /// let tokens: python::Tokens = quote!(print("hello"));
+    /// let fmt = fmt::Config::from_lang::<Python>().with_header(header);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "# Code generated by example. DO NOT EDIT.",
+    ///         "#",
+    ///         "# Source: example.proto",
+    ///         "",
+    ///         "print(\"hello\")",
+    ///     ],
+    ///     tokens.to_file_vec_with(&python::Config::default(), &fmt, fmt::VecWriter::new())?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_header<I>(self, lines: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<ItemStr>,
+    {
+        Self {
+            header: Some(Header::Lines(lines.into_iter().map(Into::into).collect())),
+            ..self
+        }
+    }
+
+    /// Set a header to write at the top of files, which has already been
+    /// formatted as comments and is written as-is.
+    ///
+    /// This is placed like a header set through
+    /// [with_header][Self::with_header], which it replaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: java::Tokens = quote!(class Foo {});
+    ///
+    /// let fmt = fmt::Config::from_lang::<Java>()
+    ///     .with_raw_header("/*\n * Code generated. DO NOT EDIT.\n */");
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "/*",
+    ///         " * Code generated. DO NOT EDIT.",
+    ///         " */",
+    ///         "",
+    ///         "class Foo {}",
+    ///     ],
+    ///     tokens.to_file_vec_with(&java::Config::default(), &fmt, fmt::VecWriter::new())?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_raw_header<H>(self, header: H) -> Self
+    where
+        H: Into<ItemStr>,
+    {
+        Self {
+            header: Some(Header::Raw(header.into())),
+            ..self
+        }
+    }
+
+    /// Set where the [prelude][Self::with_prelude] and
+    /// [header][Self::with_header] are written relative to the lines which a
+    /// language requires to come first, like a shebang in Python or build
+    /// constraints in Go.
+    ///
+    /// This is [HeaderPlacement::AfterDirectives] by default.
+    pub fn with_header_placement(self, header_placement: HeaderPlacement) -> Self {
+        Self {
+            header_placement,
+            ..self
+        }
+    }
+
+    /// Set an interpreter directive to write as the first line of files, like
+    /// `#!/usr/bin/env node`.
+    ///
+    /// The shebang is written verbatim before anything else, including the
+    /// [prelude][Self::with_prelude]. It takes precedence over a shebang
+    /// configured for the language, like
+    /// [python::Config::with_shebang][crate::lang::python::Config::with_shebang],
+    /// which is then not written. Like the prelude, this only applies when
+    /// formatting files.
+    ///
+    /// Since the shebang has to be the very first thing in the file,
+    /// formatting a file fails if a [byte order mark][Self::with_bom] is also
+    /// enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let read_to_string = rust::import("std::fs", "read_to_string");
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn main() {
+    ///         print!("{}", $read_to_string("input.txt").unwrap());
+    ///     }
+    /// };
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>()
+    ///     .with_shebang("#!/usr/bin/env -S cargo +nightly -Zscript");
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// tokens.format_file(&mut w.as_formatter(&fmt), &rust::Config::default())?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "#!/usr/bin/env -S cargo +nightly -Zscript",
+    ///         "use std::fs::read_to_string;",
+    ///         "",
+    ///         "fn main() {",
+    ///         "    print!(\"{}\", read_to_string(\"input.txt\").unwrap());",
+    ///         "}",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_shebang<S>(self, shebang: S) -> Self
+    where
+        S: Into<ItemStr>,
+    {
+        Self {
+            shebang: Some(shebang.into()),
+            ..self
+        }
+    }
+
+    /// Set whether a UTF-8 byte order mark (`U+FEFF`) should be written at the
+    /// start of files.
+    ///
+    /// Like [with_prelude][Self::with_prelude], this only applies when
+    /// formatting files.
+    ///
+    /// Formatting a file fails if it has a shebang, either through
+    /// [with_shebang][Self::with_shebang] or the language configuration like
+    /// [python::Config::with_shebang][crate::lang::python::Config::with_shebang],
+    /// since the shebang has to be the very first thing in the file. The
+    /// failure is recorded as [ValidationError::ShebangWithBom] in
+    /// [Formatter::error][crate::fmt::Formatter::error].
+    ///
+    /// [ValidationError::ShebangWithBom]: crate::tokens::ValidationError::ShebangWithBom
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    /// use genco::tokens::ValidationError;
+    ///
+    /// let tokens: python::Tokens = quote!(print("hello"));
+    ///
+    /// let fmt = fmt::Config::from_lang::<Python>().with_bom(true);
+    /// let config = python::Config::default().with_shebang("#!/usr/bin/env python3");
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// let mut formatter = w.as_formatter(&fmt);
+    /// assert!(tokens.format_file(&mut formatter, &config).is_err());
+    ///
+    /// assert_eq!(Some(&ValidationError::ShebangWithBom), formatter.error());
+    /// ```
+    pub fn with_bom(self, bom: bool) -> Self {
+        Self { bom, ..self }
+    }
+
+    /// Set whether files should be terminated with exactly one line ending.
+    ///
+    /// Trailing whitespace in the token stream, like a final
+    /// [line][crate::Tokens::line] or [push][crate::Tokens::push], never
+    /// results in more than one line ending. If this is disabled, files are
+    /// written without a terminating line ending instead. This is enabled by
+    /// default and only applies when formatting files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens: rust::Tokens = quote!(fn foo() {});
+    /// tokens.line();
+    ///
+    /// let config = rust::Config::default();
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    /// assert_eq!("fn foo() {}\n", tokens.to_file_string_with(&config, &fmt)?);
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>().with_trailing_newline(false);
+    /// assert_eq!("fn foo() {}", tokens.to_file_string_with(&config, &fmt)?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_trailing_newline(self, trailing_newline: bool) -> Self {
+        Self {
+            trailing_newline,
+            ..self
+        }
+    }
+
+    /// Set the maximum depth of token streams which are formatted inside of
+    /// other token streams, like the body of a [rust::module].
+    ///
+    /// Formatting a stream nested deeper than this results in an error
+    /// instead of overflowing the stack, which is useful when formatting
+    /// untrusted input. The error is diagnosed as
+    /// [ValidationError::MaxDepthExceeded] by
+    /// [Formatter::error][crate::fmt::Formatter::error]. By default there is
+    /// no limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     $(rust::module("a", quote!($(rust::module("b", quote!(fn b() {}))))))
+    /// };
+    ///
+    /// let config = rust::Config::default();
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>().with_max_depth(2);
+    /// assert!(tokens.to_file_string_with(&config, &fmt).is_err());
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>().with_max_depth(3);
+    /// assert!(tokens.to_file_string_with(&config, &fmt).is_ok());
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    ///
+    /// [rust::module]: crate::lang::rust::module()
+    /// [ValidationError::MaxDepthExceeded]: crate::tokens::ValidationError::MaxDepthExceeded
+    pub fn with_max_depth(self, max_depth: usize) -> Self {
+        Self {
+            max_depth: Some(max_depth),
+            ..self
+        }
+    }
+
+    /// Set whether output should be minified, which is disabled by default.
+    ///
+    /// When minifying, line breaks and indentation are left out. A single
+    /// space is written in place of a line break only where it's needed to
+    /// keep the surrounding tokens apart, like between two identifiers.
+    /// Spaces and the content of quoted strings are written as usual.
+    ///
+    /// The [prelude][Self::with_prelude] and [shebang][Self::with_shebang]
+    /// are still written on lines of their own. Note that languages where
+    /// line breaks are significant, like Python, or line comments in the
+    /// token stream can't be minified.
+    ///
+    /// Minifying requires every statement to be explicitly terminated, like
+    /// with a `;` in JavaScript. A line break which ends a statement through
+    /// automatic semicolon insertion is not preserved, so `a` and `b` on
+    /// separate lines are written as `a b`, and `return` followed by a value
+    /// on the next line is written as `return value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: java::Tokens = quote! {
+    ///     int foo(int a, int b) {
+    ///         int c = a - b;
+    ///         return -c;
+    ///     }
+    /// };
+    ///
+    /// let fmt = fmt::Config::from_lang::<Java>()
+    ///     .with_minify(true)
+    ///     .with_trailing_newline(false);
+    ///
+    /// assert_eq!(
+    ///     "int foo(int a, int b) {int c = a - b;return -c;}",
+    ///     tokens.to_file_string_with(&java::Config::default(), &fmt)?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_minify(self, minify: bool) -> Self {
+        Self { minify, ..self }
+    }
+
+    /// Set a callback which is called with the [Span] of every item written,
+    /// which can be used to build a source map or an index of
+    /// generated symbols.
+    ///
+    /// Items which don't write anything, like whitespace, are not reported.
+    /// Items in the imports and other preambles written by
+    /// [Tokens::format_file][crate::Tokens::format_file] are reported as
+    /// well, and are told apart through [Span::in_preamble].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: java::Tokens = quote! {
+    ///     int foo() {
+    ///         return 42;
+    ///     }
+    /// };
+    ///
+    /// let spans = Arc::new(Mutex::new(Vec::new()));
+    /// let listener = spans.clone();
+    ///
+    /// let fmt = fmt::Config::from_lang::<Java>().with_span_listener(move |span| {
+    ///     listener.lock().unwrap().push((span.start(), span.end()));
+    /// });
+    ///
+    /// tokens.to_file_string_with(&java::Config::default(), &fmt)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         ((0, 0), (0, 3)),
+    ///         ((0, 4), (0, 9)),
+    ///         ((0, 10), (0, 11)),
+    ///         ((1, 4), (1, 10)),
+    ///         ((1, 11), (1, 14)),
+    ///         ((2, 0), (2, 1)),
+    ///     ],
+    ///     *spans.lock().unwrap()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_span_listener<F>(self, listener: F) -> Self
+    where
+        F: Fn(Span) + Send + Sync + 'static,
+    {
+        Self {
+            span_listener: Some(SpanListener(Arc::new(listener))),
+            ..self
+        }
+    }
+
+    /// Apply a comma-separated list of `key=value` options, like
+    /// `"indent=tab,newline=crlf"`, on top of this configuration.
+    ///
+    /// See the [FromStr] implementation for the supported options. Options are applied
+    /// in order, so a `profile` should come before the options overriding
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// Errors if an option is malformed, has an unknown key, or has an
+    /// invalid value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "std")] {
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: go::Tokens = quote! {
+    ///     func foo() {
+    ///         bar()
+    ///     }
+    /// };
+    ///
+    /// let fmt = fmt::Config::from_lang::<Go>().with_options("newline=crlf")?;
+    ///
+    /// assert_eq!(
+    ///     "func foo() {\r\n\tbar()\r\n}\r\n",
+    ///     tokens.to_file_string_with(&go::Config::default(), &fmt)?
+    /// );
+    /// # }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_options(mut self, options: &str) -> Result<Self, ParseConfigError> {
+        for option in options.split(',').map(str::trim) {
+            if option.is_empty() {
+                continue;
+            }
+
+            let Some((key, value)) = option.split_once('=') else {
+                return Err(ParseConfigError::MissingValue {
+                    option: option.to_string(),
+                });
+            };
+
+            let (key, value) = (key.trim(), value.trim());
+
+            let invalid = || ParseConfigError::InvalidValue {
+                key: key.to_string(),
+                value: value.to_string(),
+            };
+
+            let parse_bool = || match value {
+                "true" => Ok(true),
+                "false" => Ok(false),
+                _ => Err(invalid()),
+            };
+
+            let parse_usize = || value.parse::<usize>().map_err(|_| invalid());
+
+            self = match key {
+                "profile" => {
+                    let profile = Profile::parse(value).ok_or_else(invalid)?;
+                    let profile = Self::profile(profile);
+
+                    Self {
+                        indentation: profile.indentation,
+                        newline: profile.newline,
+                        trailing_newline: profile.trailing_newline,
+                        ..self
+                    }
+                }
+                "indent" => {
+                    let indentation = match value {
+                        "tab" => Indentation::Tab,
+                        _ => Indentation::Space(parse_usize()?),
+                    };
+
+                    self.with_indentation(indentation)
+                }
+                "newline" => {
+                    let newline = match value {
+                        "lf" => "\n",
+                        "crlf" => "\r\n",
+                        _ => return Err(invalid()),
+                    };
+
+                    self.with_newline(newline)
+                }
+                "trailing_newline" => self.with_trailing_newline(parse_bool()?),
+                "trailing_comma" => self.with_trailing_comma(parse_bool()?),
+                "operator_spacing" => self.with_operator_spacing(parse_bool()?),
+                "bom" => self.with_bom(parse_bool()?),
+                "max_width" => self.with_max_width(parse_usize()?),
+                "max_depth" => self.with_max_depth(parse_usize()?),
+                "minify" => self.with_minify(parse_bool()?),
+                _ => {
+                    return Err(ParseConfigError::UnknownKey {
+                        key: key.to_string(),
+                    })
+                }
+            };
+        }
+
+        Ok(self)
+    }
+}
+
+/// Parse a configuration from a comma-separated list of `key=value` options,
+/// like `"indent=tab,newline=crlf"`, which is useful when loading it from an
+/// environment variable or a command line flag.
+///
+/// Options which are not specified are the same as for
+/// [Profile::Rustfmt]. The following options are supported:
+///
+/// * `profile` - one of `google`, `rustfmt`, `gofmt`, or `compact`, see
+///   [Config::profile].
+/// * `indent` - `tab`, or the number of spaces to indent with.
+/// * `newline` - `lf` or `crlf`.
+/// * `trailing_newline`, `trailing_comma`, `operator_spacing`, `bom`, and
+///   `minify` - `true` or `false`.
+/// * `max_width` and `max_depth` - a number.
+///
+/// # Errors
+///
+/// Errors with a [ParseConfigError] if an option is malformed, has an unknown
+/// key, or has an invalid value.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use genco::fmt;
+/// use genco::prelude::*;
+///
+/// let tokens: csharp::Tokens = quote! {
+///     class Foo {
+///         void Bar() {}
+///     }
+/// };
+///
+/// let fmt = "indent=tab,newline=crlf".parse::<fmt::Config>()?;
+///
+/// assert_eq!(
+///     "class Foo {\r\n\tvoid Bar() {}\r\n}\r\n",
+///     tokens.to_file_string_with(&csharp::Config::default(), &fmt)?
+/// );
+///
+/// assert_eq!(
+///     Err(fmt::ParseConfigError::UnknownKey { key: "indentation".into() }),
+///     "indentation=tab".parse::<fmt::Config>().map(|_| ())
+/// );
+/// # }
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+impl FromStr for Config {
+    type Err = ParseConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::profile(Profile::Rustfmt).with_options(s)
+    }
 }
@@ -1,6 +1,8 @@
 use crate::fmt;
 use crate::lang::Lang;
 use crate::tokens::Item;
+#[cfg(feature = "locations")]
+use crate::tokens::Location;
 
 /// Trait for peeking items.
 pub(super) trait Parse<L>
@@ -34,7 +36,7 @@ where
     fn parse(item: &Item<L>) -> fmt::Result<&Self::Output> {
         match item {
             Item::Literal(s) => Ok(s),
-            _ => Err(std::fmt::Error),
+            _ => Err(core::fmt::Error),
         }
     }
 }
@@ -57,7 +59,7 @@ where
     fn parse(item: &Item<L>) -> fmt::Result<&Self::Output> {
         match item {
             Item::CloseEval => Ok(&()),
-            _ => Err(std::fmt::Error),
+            _ => Err(core::fmt::Error),
         }
     }
 }
@@ -68,23 +70,63 @@ where
     L: Lang,
 {
     items: &'a [Item<L>],
+    /// Index of the next item.
+    index: usize,
+    /// Locations of the remaining items, sorted by index.
+    #[cfg(feature = "locations")]
+    locations: &'a [(usize, Location)],
 }
 
 impl<'a, L> Cursor<'a, L>
 where
     L: Lang,
 {
-    pub(super) fn new(items: &'a [Item<L>]) -> Self {
-        Self { items }
+    pub(super) fn new(
+        items: &'a [Item<L>],
+        #[cfg(feature = "locations")] locations: &'a [(usize, Location)],
+    ) -> Self {
+        Self {
+            items,
+            index: 0,
+            #[cfg(feature = "locations")]
+            locations,
+        }
     }
 
     /// Get the next item.
-    pub(super) fn next(&mut self) -> Option<&Item<L>> {
+    pub(super) fn next(&mut self) -> Option<&'a Item<L>> {
         let (first, rest) = self.items.split_first()?;
         self.items = rest;
+        self.index += 1;
         Some(first)
     }
 
+    /// Get the index of the last item returned by [next()][Self::next].
+    pub(super) fn index(&self) -> usize {
+        self.index.saturating_sub(1)
+    }
+
+    /// Get the location recorded for the last item returned by
+    /// [next()][Self::next], if any.
+    #[cfg(feature = "locations")]
+    pub(super) fn location(&mut self) -> Option<Location> {
+        let index = self.index.checked_sub(1)?;
+
+        while let Some(((i, location), rest)) = self.locations.split_first() {
+            if *i > index {
+                break;
+            }
+
+            self.locations = rest;
+
+            if *i == index {
+                return Some(*location);
+            }
+        }
+
+        None
+    }
+
     #[inline]
     pub(super) fn peek<P>(&self) -> bool
     where
@@ -114,7 +156,7 @@ where
     where
         P: Parse<L>,
     {
-        let item = self.next().ok_or(std::fmt::Error)?;
+        let item = self.next().ok_or(core::fmt::Error)?;
         P::parse(item)
     }
 }
@@ -0,0 +1,413 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::format;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::string::String;
+use std::vec::Vec;
+
+use relative_path::{Component, RelativePath, RelativePathBuf};
+
+use crate::fmt;
+use crate::lang::Lang;
+use crate::Tokens;
+
+/// A collection of files to generate, each identified by a path relative to
+/// the directory they are written to.
+///
+/// Files are formatted through [Tokens::format_file] when they are written
+/// with [write_to][FileSet::write_to], which also creates any missing
+/// directories.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::fmt;
+///
+/// let dir = std::env::temp_dir().join(format!("genco-file-set-{}", std::process::id()));
+/// # let _ = std::fs::remove_dir_all(&dir);
+///
+/// let mut files = fmt::FileSet::<Rust>::new()
+///     .with_skip_unchanged(true)
+///     .with_manifest(".generated");
+///
+/// files.insert("lib.rs", quote!(pub mod foo;));
+/// files.insert("foo/mod.rs", quote!(pub fn foo() {}));
+///
+/// let summary = files.write_to(&dir)?;
+/// assert_eq!(2, summary.written().len());
+///
+/// assert_eq!(
+///     "pub fn foo() {}\n",
+///     std::fs::read_to_string(dir.join("foo").join("mod.rs"))?
+/// );
+///
+/// // Writing the same files again leaves them untouched.
+/// let summary = files.write_to(&dir)?;
+/// assert!(summary.written().is_empty());
+/// assert_eq!(2, summary.skipped().len());
+///
+/// // Files which were previously generated but are no longer part of the
+/// // set are removed.
+/// let mut files = fmt::FileSet::<Rust>::new().with_manifest(".generated");
+/// files.insert("lib.rs", quote!());
+///
+/// let summary = files.write_to(&dir)?;
+/// assert_eq!(vec!["foo/mod.rs"], summary.removed());
+/// assert!(!dir.join("foo").join("mod.rs").exists());
+///
+/// std::fs::remove_dir_all(&dir)?;
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+pub struct FileSet<L>
+where
+    L: Lang,
+{
+    files: BTreeMap<RelativePathBuf, (Tokens<L>, L::Config)>,
+    fmt: fmt::Config,
+    skip_unchanged: bool,
+    manifest: Option<RelativePathBuf>,
+}
+
+impl<L> FileSet<L>
+where
+    L: Lang,
+{
+    /// Construct a new empty set of files, which are formatted using the
+    /// default configuration for the language.
+    pub fn new() -> Self {
+        Self {
+            files: BTreeMap::new(),
+            fmt: fmt::Config::from_lang::<L>(),
+            skip_unchanged: false,
+            manifest: None,
+        }
+    }
+
+    /// Set the formatting configuration to use for all files.
+    pub fn with_config(self, fmt: fmt::Config) -> Self {
+        Self { fmt, ..self }
+    }
+
+    /// Set whether files whose content is unchanged should be left untouched,
+    /// which preserves their modification times for incremental builds.
+    ///
+    /// Existing files are compared while formatting through
+    /// [Tokens::differs_from_with], so the output of unchanged files is never
+    /// stored. This is disabled by default.
+    pub fn with_skip_unchanged(self, skip_unchanged: bool) -> Self {
+        Self {
+            skip_unchanged,
+            ..self
+        }
+    }
+
+    /// Record the generated files in a manifest at the given path, relative
+    /// to the output directory.
+    ///
+    /// When a manifest is used, files which were listed in the previous
+    /// manifest but are no longer part of the set are removed when writing.
+    ///
+    /// Like the paths of files, the path of the manifest must be contained in
+    /// the output directory, and it must not be the path of a file in the
+    /// set. Otherwise writing fails, see [write_to][FileSet::write_to].
+    pub fn with_manifest<P>(self, manifest: P) -> Self
+    where
+        P: Into<RelativePathBuf>,
+    {
+        Self {
+            manifest: Some(manifest.into()),
+            ..self
+        }
+    }
+
+    /// Add a file with the given language configuration, replacing any
+    /// existing file at the same path.
+    ///
+    /// The path must be contained in the output directory, which is not the
+    /// case if it is empty, absolute, or has `.` or `..` components.
+    /// Otherwise writing fails, see [write_to][FileSet::write_to].
+    pub fn insert_with<P>(&mut self, path: P, tokens: Tokens<L>, config: L::Config)
+    where
+        P: Into<RelativePathBuf>,
+    {
+        self.files.insert(path.into(), (tokens, config));
+    }
+
+    /// Get the number of files in the set.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Test if the set contains no files.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Write all files to the given directory.
+    ///
+    /// # Errors
+    ///
+    /// Errors are reported through [WriteFileError][fmt::WriteFileError],
+    /// which distinguishes I/O failures from formatting errors. All files are
+    /// formatted before anything is written, so a formatting error leaves the
+    /// directory untouched.
+    ///
+    /// Paths are checked before anything is written as well. If the path of a
+    /// file or the manifest is not contained in the output directory, the
+    /// manifest has the same path as a file, or a path is nested under the
+    /// path of another file or the manifest, this fails with an I/O error of
+    /// the kind [InvalidInput][io::ErrorKind::InvalidInput].
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    /// use std::io;
+    ///
+    /// let dir = std::env::temp_dir().join(format!("genco-file-set-invalid-{}", std::process::id()));
+    ///
+    /// let mut files = fmt::FileSet::<Rust>::new();
+    /// files.insert("../lib.rs", quote!(pub mod foo;));
+    ///
+    /// let error = files.write_to(&dir).unwrap_err();
+    /// assert!(matches!(error, fmt::WriteFileError::Io(e) if e.kind() == io::ErrorKind::InvalidInput));
+    /// assert!(!dir.exists());
+    /// ```
+    pub fn write_to<D>(&self, dir: D) -> Result<FileSetSummary, fmt::WriteFileError>
+    where
+        D: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        let mut summary = FileSetSummary::default();
+
+        self.check_paths()?;
+
+        let mut outputs = Vec::with_capacity(self.files.len());
+
+        for (path, (tokens, config)) in &self.files {
+            let target = path.to_path(dir);
+
+            if self.skip_unchanged {
+                if let Some(existing) = read_existing(&target)? {
+                    if !tokens.differs_from_with(&existing, config, &self.fmt)? {
+                        summary.skipped.push(path.clone());
+                        continue;
+                    }
+                }
+            }
+
+            let mut w = fmt::FmtWriter::new(String::new());
+            tokens.format_file(&mut w.as_formatter(&self.fmt), config)?;
+            outputs.push((path, target, w.into_inner()));
+        }
+
+        for (path, target, output) in outputs {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::write(&target, output)?;
+            summary.written.push(path.clone());
+        }
+
+        if let Some(manifest) = &self.manifest {
+            self.update_manifest(dir, manifest, &mut summary)?;
+        }
+
+        Ok(summary)
+    }
+
+    /// Check that all paths are contained in the output directory, and that
+    /// none of them replaces or is nested under another generated file.
+    fn check_paths(&self) -> io::Result<()> {
+        let paths = self.files.keys().chain(self.manifest.as_ref());
+
+        for path in paths.clone() {
+            check_contained(path)?;
+        }
+
+        if let Some(manifest) = &self.manifest {
+            if self.files.contains_key(manifest) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("manifest has the same path as a generated file: {manifest}"),
+                ));
+            }
+        }
+
+        for path in paths {
+            let mut parent = path.parent();
+
+            while let Some(p) = parent {
+                if self.files.contains_key(p) || self.manifest.as_deref() == Some(p) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("path is nested under a generated file: {path}"),
+                    ));
+                }
+
+                parent = p.parent();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove files which are listed in the existing manifest but not in the
+    /// current set, and write the new manifest.
+    fn update_manifest(
+        &self,
+        dir: &Path,
+        manifest: &RelativePath,
+        summary: &mut FileSetSummary,
+    ) -> io::Result<()> {
+        let manifest = manifest.to_path(dir);
+
+        let previous = match fs::read_to_string(&manifest) {
+            Ok(previous) => previous,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let mut paths = BTreeSet::new();
+
+        for line in previous.lines().filter(|line| !line.is_empty()) {
+            let path = RelativePath::new(line);
+
+            if !is_contained(path) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("manifest lists path outside of the output directory: {line}"),
+                ));
+            }
+
+            paths.insert(path.normalize());
+        }
+
+        for path in paths {
+            if self.files.contains_key(&path) {
+                continue;
+            }
+
+            match fs::remove_file(path.to_path(dir)) {
+                Ok(()) => summary.removed.push(path),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut output = String::new();
+
+        for path in self.files.keys() {
+            output.push_str(path.as_str());
+            output.push('\n');
+        }
+
+        if let Some(parent) = manifest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(manifest, output)
+    }
+}
+
+impl<L> FileSet<L>
+where
+    L: Lang,
+    L::Config: Default,
+{
+    /// Add a file using the default language configuration, replacing any
+    /// existing file at the same path.
+    pub fn insert<P>(&mut self, path: P, tokens: Tokens<L>)
+    where
+        P: Into<RelativePathBuf>,
+    {
+        self.insert_with(path, tokens, L::Config::default());
+    }
+}
+
+impl<L> Default for FileSet<L>
+where
+    L: Lang,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check that the given path is contained in the output directory.
+fn check_contained(path: &RelativePath) -> io::Result<()> {
+    if is_contained(path) {
+        return Ok(());
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("path is not contained in the output directory: {path}"),
+    ))
+}
+
+/// Test if the given path is non-empty and only consists of normal
+/// components, so that it can't refer to anything outside of the directory
+/// it's joined with.
+fn is_contained(path: &RelativePath) -> bool {
+    if path.as_str().starts_with('/') {
+        return false;
+    }
+
+    let mut components = path.components().peekable();
+
+    if components.peek().is_none() {
+        return false;
+    }
+
+    components.all(|c| match c {
+        // Also guard against components which have a special meaning to
+        // the platform, like drive prefixes or backslash separators.
+        Component::Normal(c) => matches!(
+            Path::new(c).components().collect::<Vec<_>>().as_slice(),
+            [std::path::Component::Normal(..)]
+        ),
+        _ => false,
+    })
+}
+
+/// Read the existing content of the file at the given path, if it exists and
+/// is valid UTF-8.
+///
+/// Formatted output is always valid UTF-8, so a file which isn't can never be
+/// unchanged.
+fn read_existing(path: &Path) -> io::Result<Option<String>> {
+    match fs::read(path) {
+        Ok(existing) => Ok(String::from_utf8(existing).ok()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Summary of the paths affected by [FileSet::write_to], relative to the
+/// output directory.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FileSetSummary {
+    written: Vec<RelativePathBuf>,
+    skipped: Vec<RelativePathBuf>,
+    removed: Vec<RelativePathBuf>,
+}
+
+impl FileSetSummary {
+    /// Files which were written.
+    pub fn written(&self) -> &[RelativePathBuf] {
+        &self.written
+    }
+
+    /// Files which were left untouched since their content was unchanged.
+    pub fn skipped(&self) -> &[RelativePathBuf] {
+        &self.skipped
+    }
+
+    /// Previously generated files which were removed.
+    pub fn removed(&self) -> &[RelativePathBuf] {
+        &self.removed
+    }
+}
@@ -1,10 +1,15 @@
 use crate::fmt;
-use crate::fmt::config::{Config, Indentation};
+use crate::fmt::config::{Config, Header, HeaderPlacement, Indentation};
 use crate::fmt::cursor;
+use crate::fmt::span::{Span, SpanListener};
 use crate::lang::Lang;
-use crate::tokens::Item;
+#[cfg(feature = "locations")]
+use crate::tokens::Location;
+use crate::tokens::{validator, Item, ValidationError};
+use alloc::string::String;
+use alloc::vec::Vec;
 
-use std::mem;
+use core::mem;
 
 /// Buffer used as indentation source.
 static SPACES: &str = "                                                                                                    ";
@@ -12,9 +17,10 @@ static SPACES: &str = "
 static TABS: &str =
     "\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t\t";
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
 enum Whitespace {
     Initial,
+    #[default]
     None,
     Push,
     Line,
@@ -35,9 +41,39 @@ impl Whitespace {
     }
 }
 
-impl Default for Whitespace {
-    fn default() -> Self {
-        Self::None
+/// A soft break which has been encountered, but where it's not yet known
+/// whether the text following it fits on the current line.
+#[derive(Debug, Clone, Copy)]
+struct SoftBreak {
+    /// The line of the soft break.
+    line: usize,
+    /// The column at which the soft break is written as a space.
+    column: usize,
+    /// The position of the soft break in the output.
+    #[cfg(feature = "locations")]
+    position: (usize, usize),
+    /// The number of source map mappings before the soft break.
+    #[cfg(feature = "locations")]
+    mappings: usize,
+}
+
+/// A soft break which was broken, used to adjust positions recorded before
+/// it was.
+#[derive(Debug, Clone, Copy)]
+struct Wrap {
+    line: usize,
+    column: usize,
+    indent: usize,
+}
+
+impl Wrap {
+    /// Adjust a position recorded before the line was broken.
+    fn adjust(&self, (line, column): (usize, usize)) -> (usize, usize) {
+        if line == self.line && column > self.column {
+            (line + 1, column - self.column - 1 + self.indent)
+        } else {
+            (line, column)
+        }
     }
 }
 
@@ -58,6 +94,51 @@ pub struct Formatter<'a> {
     spaces: usize,
     /// Current indentation level.
     indent: i16,
+    /// The column of the output, as measured by the configured
+    /// [WidthMode][fmt::WidthMode].
+    column: usize,
+    /// How to measure the width of written text, or `None` if nothing
+    /// consumes the column.
+    width_measure: Option<fmt::WidthMode>,
+    /// The line of the output, counting line endings written so far.
+    current_line: usize,
+    /// Where the item currently being formatted started being written, if it
+    /// has written anything, and the number of wraps at that time.
+    span_start: Option<((usize, usize), usize)>,
+    /// Callback receiving the span of every item written.
+    span_listener: Option<&'a SpanListener>,
+    /// If items being written belong to the preamble of a file, as marked
+    /// through [preamble][Self::preamble].
+    in_preamble: bool,
+    /// The width at which lines are broken at soft breaks.
+    max_width: Option<usize>,
+    /// The number of quotes currently open, inside of which soft breaks are
+    /// never broken.
+    quotes: usize,
+    /// The soft break which is pending, if any.
+    soft_break: Option<SoftBreak>,
+    /// Text written after the pending soft break.
+    soft_buf: String,
+    /// Spans written after the pending soft break.
+    soft_spans: Vec<Span>,
+    /// Soft breaks which were broken, as long as spans are being reported.
+    wraps: Vec<Wrap>,
+    /// The number of token streams currently being formatted.
+    depth: usize,
+    /// Diagnostic for the first token stream which failed to format.
+    error: Option<ValidationError>,
+    /// The last character written, used to separate tokens when minifying.
+    last: Option<char>,
+    /// The line and column of the output, where the column is counted in
+    /// UTF-16 code units like source maps expect.
+    #[cfg(feature = "locations")]
+    position: (usize, usize),
+    /// Location of the item about to be written.
+    #[cfg(feature = "locations")]
+    location: Option<Location>,
+    /// Source map being collected, if enabled.
+    #[cfg(feature = "locations")]
+    source_map: Option<fmt::SourceMap>,
 }
 
 impl<'a> Formatter<'a> {
@@ -68,41 +149,422 @@ impl<'a> Formatter<'a> {
             line: Whitespace::Initial,
             spaces: 0usize,
             indent: 0i16,
+            column: 0,
+            current_line: 0,
+            span_start: None,
+            width_measure: config.width_measure.or_else(|| {
+                let consumed = config.span_listener.is_some() || config.max_width.is_some();
+                consumed.then_some(fmt::WidthMode::Chars)
+            }),
+            span_listener: config.span_listener.as_ref(),
+            in_preamble: false,
+            max_width: config.max_width,
+            quotes: 0,
+            soft_break: None,
+            soft_buf: String::new(),
+            soft_spans: Vec::new(),
+            wraps: Vec::new(),
+            depth: 0,
+            error: None,
+            last: None,
             config,
+            #[cfg(feature = "locations")]
+            position: (0, 0),
+            #[cfg(feature = "locations")]
+            location: None,
+            #[cfg(feature = "locations")]
+            source_map: None,
         }
     }
 
+    /// The column at which the next item will be written in the current
+    /// line.
+    ///
+    /// Width is measured according to the configured
+    /// [WidthMode][fmt::WidthMode]. Whitespace which is still pending, like
+    /// spaces which are only written once they are followed by another item,
+    /// is not included.
+    ///
+    /// Only indentation and whitespace are counted unless columns are
+    /// tracked, see
+    /// [Config::with_width_measure][fmt::Config::with_width_measure].
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The line which is currently being written, starting at 0.
+    ///
+    /// Like with [column][Self::column], pending line endings which are only
+    /// written once they are followed by another item are not included.
+    pub fn line(&self) -> usize {
+        self.current_line
+    }
+
+    /// Diagnose why formatting failed, if it failed because a token stream
+    /// was malformed.
+    ///
+    /// This is the same diagnostic as is reported by
+    /// [Tokens::validate][crate::Tokens::validate], and indexes refer to the
+    /// token stream which failed to format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    /// use genco::tokens::{Item, ValidationError};
+    ///
+    /// let mut tokens: rust::Tokens = quote!(foo);
+    /// tokens.extend([Item::CloseQuote]);
+    ///
+    /// let mut w = fmt::FmtWriter::new(String::new());
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    /// let mut formatter = w.as_formatter(&fmt);
+    ///
+    /// let config = rust::Config::default();
+    /// let format = rust::Format::default();
+    /// assert!(tokens.format(&mut formatter, &config, &format).is_err());
+    ///
+    /// assert_eq!(
+    ///     Some(&ValidationError::UnexpectedCloseQuote { index: 1 }),
+    ///     formatter.error()
+    /// );
+    /// ```
+    pub fn error(&self) -> Option<&ValidationError> {
+        self.error.as_ref()
+    }
+
+    /// Access the source map collected while formatting, if it has been
+    /// enabled by the language being formatted.
+    ///
+    /// This requires the `locations` feature to be enabled.
+    #[cfg(feature = "locations")]
+    pub fn source_map(&self) -> Option<&fmt::SourceMap> {
+        self.source_map.as_ref()
+    }
+
+    /// Start collecting a source map for all subsequently formatted items.
+    #[cfg(all(feature = "locations", feature = "std"))]
+    pub(crate) fn enable_source_map(&mut self) {
+        self.source_map.get_or_insert_with(Default::default);
+    }
+
     /// Format the given stream of tokens.
     pub(crate) fn format_items<L>(
         &mut self,
         items: &[Item<L>],
+        #[cfg(feature = "locations")] locations: &[(usize, Location)],
         config: &L::Config,
         format: &L::Format,
     ) -> fmt::Result<()>
     where
         L: Lang,
     {
-        let mut cursor = cursor::Cursor::new(items);
-        self.format_cursor(&mut cursor, config, format, false)
+        let mut cursor = cursor::Cursor::new(
+            items,
+            #[cfg(feature = "locations")]
+            locations,
+        );
+
+        self.enter()?;
+        let mut result = self.format_cursor(&mut cursor, config, format, false);
+        self.depth -= 1;
+
+        if self.depth == 0 && result.is_ok() {
+            result = self.commit_soft_break();
+        }
+
+        if result.is_err() && self.error.is_none() {
+            self.error = validator::validate_structure(items).err();
+        }
+
+        result
     }
 
-    /// Forcibly write a line ending, at the end of a file.
+    /// Record an error which makes formatting fail, unless an error has
+    /// already been recorded.
+    pub(crate) fn report(&mut self, error: ValidationError) -> fmt::Result {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+
+        Err(core::fmt::Error)
+    }
+
+    /// Enter a nested token stream, erroring if the configured maximum depth
+    /// is exceeded or if the configured line ending is invalid.
+    fn enter(&mut self) -> fmt::Result {
+        if self.depth == 0 {
+            self.check_newline()?;
+        }
+
+        if let Some(max_depth) = self.config.max_depth {
+            if self.depth >= max_depth {
+                return self.report(ValidationError::MaxDepthExceeded {
+                    index: 0,
+                    max_depth,
+                });
+            }
+        }
+
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Error if the configured line ending isn't one we support.
+    fn check_newline(&mut self) -> fmt::Result {
+        let newline = self.config.newline;
+
+        if !matches!(newline, "\n" | "\r\n") {
+            return self.report(ValidationError::InvalidNewline { newline });
+        }
+
+        Ok(())
+    }
+
+    /// Write the shebang or byte order mark configured for files.
+    ///
+    /// A shebang in the formatting configuration takes precedence over one
+    /// configured for the language.
+    fn write_shebang<L>(&mut self, config: &L::Config) -> fmt::Result
+    where
+        L: Lang,
+    {
+        let fmt = self.config;
+        let shebang = fmt.shebang.as_deref().or_else(|| L::shebang(config));
+
+        if let Some(shebang) = shebang {
+            if fmt.bom {
+                return self.report(ValidationError::ShebangWithBom);
+            }
+
+            self.write_str(shebang)?;
+            self.line = Whitespace::Push;
+
+            // NB: the shebang must be on a line of its own.
+            if fmt.minify {
+                self.write_line()?;
+                self.line = Whitespace::Initial;
+            }
+        } else if fmt.bom {
+            self.write_raw("\u{feff}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the preamble of a file, or anything else which is written around
+    /// the token stream being formatted as a file, through the given closure.
+    ///
+    /// Spans of items written by the closure are reported as being part of
+    /// the preamble through [Span::in_preamble]. This is intended for
+    /// implementations of [Lang::format_file], which should use it for
+    /// everything except the token stream being formatted.
+    ///
+    /// [Span::in_preamble]: crate::fmt::Span::in_preamble
+    pub fn preamble<F>(&mut self, f: F) -> fmt::Result
+    where
+        F: FnOnce(&mut Self) -> fmt::Result,
+    {
+        let in_preamble = mem::replace(&mut self.in_preamble, true);
+        let result = f(self);
+        self.in_preamble = in_preamble;
+        result
+    }
+
+    /// Write the shebang, the lines which the language requires to come first
+    /// in a file, and the configured prelude and header in the order given by
+    /// the header placement.
+    pub(crate) fn write_file_header<L>(&mut self, config: &L::Config) -> fmt::Result
+    where
+        L: Lang,
+    {
+        self.check_newline()?;
+        self.write_shebang::<L>(config)?;
+
+        match self.config.header_placement {
+            HeaderPlacement::BeforeDirectives => {
+                self.write_prelude()?;
+                self.write_header(L::line_comment())?;
+                L::format_file_directives(self, config)?;
+            }
+            HeaderPlacement::AfterDirectives => {
+                L::format_file_directives(self, config)?;
+                self.write_prelude()?;
+                self.write_header(L::line_comment())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the configured prelude.
+    fn write_prelude(&mut self) -> fmt::Result {
+        let config = self.config;
+
+        let Some(prelude) = &config.prelude else {
+            return Ok(());
+        };
+
+        self.push();
+
+        for (n, line) in prelude.lines().enumerate() {
+            if n > 0 {
+                self.write_line()?;
+            }
+
+            self.write_str(line)?;
+        }
+
+        self.line = Whitespace::Line;
+
+        // NB: the prelude is typically made up of line comments, which must
+        // not be joined with what follows.
+        if config.minify {
+            self.write_line()?;
+            self.line = Whitespace::Initial;
+        }
+
+        Ok(())
+    }
+
+    /// Write the configured header, using the given line comment prefix.
+    fn write_header(&mut self, comment: &str) -> fmt::Result {
+        let config = self.config;
+
+        let Some(header) = &config.header else {
+            return Ok(());
+        };
+
+        self.push();
+
+        match header {
+            Header::Lines(lines) => {
+                for (n, line) in lines.iter().enumerate() {
+                    if n > 0 {
+                        self.write_line()?;
+                    }
+
+                    self.write_str(comment)?;
+
+                    if !line.is_empty() {
+                        self.write_str(" ")?;
+                        self.write_str(line)?;
+                    }
+                }
+            }
+            Header::Raw(header) => {
+                for (n, line) in header.lines().enumerate() {
+                    if n > 0 {
+                        self.write_line()?;
+                    }
+
+                    self.write_str(line)?;
+                }
+            }
+        }
+
+        self.line = Whitespace::Line;
+
+        // NB: the header is made up of comments, which must not be joined
+        // with what follows.
+        if config.minify {
+            self.write_line()?;
+            self.line = Whitespace::Initial;
+        }
+
+        Ok(())
+    }
+
+    /// Forcibly write a line ending at the end of a file, unless disabled
+    /// through [Config::with_trailing_newline][fmt::Config::with_trailing_newline].
     ///
     /// This will also reset any whitespace we have pending.
     pub(crate) fn write_trailing_line(&mut self) -> fmt::Result {
+        self.commit_soft_break()?;
         self.line = Whitespace::default();
         self.spaces = 0;
-        self.write.write_trailing_line(self.config)?;
+        self.column = 0;
+
+        if self.config.trailing_newline {
+            self.write.write_trailing_line(self.config)?;
+            self.current_line += 1;
+        }
+
         Ok(())
     }
 
     /// Write the given string.
     fn write_str(&mut self, s: &str) -> fmt::Result {
         if !s.is_empty() {
-            self.flush_whitespace()?;
+            self.flush_whitespace(s)?;
+
+            #[cfg(feature = "locations")]
+            if let Some(location) = self.location.take() {
+                if let Some(source_map) = &mut self.source_map {
+                    source_map.push(self.position.0, self.position.1, location);
+                }
+            }
+
+            if self.span_start.is_none() {
+                self.span_start = Some(((self.current_line, self.column), self.wraps.len()));
+            }
+
+            self.write_raw(s)?;
+            self.measure(s);
+
+            if matches!(self.max_width, Some(max_width) if self.column > max_width) {
+                self.break_soft_break()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Advance the column by the width of the given string, if columns are
+    /// tracked.
+    fn measure(&mut self, s: &str) {
+        if let Some(width_measure) = self.width_measure {
+            self.column += width_measure.measure(s);
+        }
+    }
+
+    /// Write the given string to the underlying writer, keeping track of the
+    /// position in the output.
+    ///
+    /// If a soft break is pending, the string is buffered until it's known
+    /// whether the line should be broken.
+    fn write_raw(&mut self, s: &str) -> fmt::Result {
+        if self.soft_break.is_some() {
+            self.soft_buf.push_str(s);
+        } else {
             self.write.write_str(s)?;
         }
 
+        if let Some(c) = s.chars().next_back() {
+            self.last = Some(c);
+        }
+
+        #[cfg(feature = "locations")]
+        {
+            self.position.1 += s.encode_utf16().count();
+        }
+
+        Ok(())
+    }
+
+    /// Write a line ending to the underlying writer.
+    fn write_line(&mut self) -> fmt::Result {
+        self.commit_soft_break()?;
+        self.write.write_line(self.config)?;
+        self.column = 0;
+        self.current_line += 1;
+
+        #[cfg(feature = "locations")]
+        {
+            self.position = (self.position.0 + 1, 0);
+        }
+
         Ok(())
     }
 
@@ -117,7 +579,7 @@ impl<'a> Formatter<'a> {
     }
 
     /// Push a new line.
-    fn line(&mut self) {
+    fn push_line(&mut self) {
         self.line = match self.line {
             Whitespace::Initial => return,
             _ => Whitespace::Line,
@@ -126,11 +588,174 @@ impl<'a> Formatter<'a> {
         self.spaces = 0;
     }
 
+    /// Handle a line ending which was written as text, by adding one more
+    /// line ending to the whitespace which is pending.
+    fn write_newline(&mut self) -> fmt::Result {
+        if self.config.minify {
+            self.push_line();
+            return Ok(());
+        }
+
+        self.line = match self.line {
+            Whitespace::None => Whitespace::Push,
+            Whitespace::Push => Whitespace::Line,
+            // NB: more line endings than can be pending are written directly.
+            line @ (Whitespace::Initial | Whitespace::Line) => {
+                self.write_line()?;
+                line
+            }
+        };
+
+        self.spaces = 0;
+        Ok(())
+    }
+
     /// Push a space.
     fn space(&mut self) {
         self.spaces += 1;
     }
 
+    /// Push a soft break, which is only treated as one if a maximum width is
+    /// configured and we're not inside of a quote.
+    fn soft_break(&mut self) -> fmt::Result {
+        if self.max_width.is_none() || self.quotes > 0 || !matches!(self.line, Whitespace::None) {
+            self.space();
+            return Ok(());
+        }
+
+        // NB: nothing has been written since the last soft break.
+        if self.soft_break.is_some() && self.soft_buf.is_empty() {
+            return Ok(());
+        }
+
+        self.commit_soft_break()?;
+        self.spaces = 0;
+
+        self.soft_break = Some(SoftBreak {
+            line: self.current_line,
+            column: self.column,
+            #[cfg(feature = "locations")]
+            position: self.position,
+            #[cfg(feature = "locations")]
+            mappings: self.source_map.as_ref().map_or(0, |s| s.mappings().len()),
+        });
+
+        // Account for the space the soft break is written as.
+        self.column += 1;
+
+        #[cfg(feature = "locations")]
+        {
+            self.position.1 += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Write the pending soft break as a space, since the text following it
+    /// fits on the current line.
+    fn commit_soft_break(&mut self) -> fmt::Result {
+        if self.soft_break.take().is_none() {
+            return Ok(());
+        }
+
+        // NB: don't write trailing spaces.
+        if !self.soft_buf.is_empty() {
+            self.write.write_str(" ")?;
+            self.write.write_str(&self.soft_buf)?;
+            self.soft_buf.clear();
+        }
+
+        self.flush_soft_spans();
+        Ok(())
+    }
+
+    /// Break the line at the pending soft break, continuing the text which
+    /// follows it on the next line with an extra level of indentation.
+    fn break_soft_break(&mut self) -> fmt::Result {
+        let Some(soft_break) = self.soft_break.take() else {
+            return Ok(());
+        };
+
+        self.write.write_line(self.config)?;
+        self.current_line += 1;
+        self.column = 0;
+
+        #[cfg(feature = "locations")]
+        {
+            self.position = (self.position.0 + 1, 0);
+        }
+
+        self.write_indentation(i16::max(self.indent, 0) as usize + 1, 0)?;
+
+        let wrap = Wrap {
+            line: soft_break.line,
+            column: soft_break.column,
+            indent: self.column,
+        };
+
+        #[cfg(feature = "locations")]
+        if let Some(source_map) = &mut self.source_map {
+            source_map.wrap(
+                soft_break.mappings,
+                soft_break.position.0,
+                soft_break.position.1,
+                self.position.1,
+            );
+        }
+
+        let buf = mem::take(&mut self.soft_buf);
+        self.write_raw(&buf)?;
+        self.measure(&buf);
+        self.soft_buf = buf;
+        self.soft_buf.clear();
+
+        for span in &mut self.soft_spans {
+            span.start = wrap.adjust(span.start);
+            span.end = wrap.adjust(span.end);
+        }
+
+        if self.span_listener.is_some() {
+            self.wraps.push(wrap);
+        }
+
+        self.flush_soft_spans();
+        Ok(())
+    }
+
+    /// Report spans which were held back by a soft break.
+    fn flush_soft_spans(&mut self) {
+        if let Some(listener) = self.span_listener {
+            for span in self.soft_spans.drain(..) {
+                (listener.0)(span);
+            }
+        }
+    }
+
+    /// Report the span of an item which started being written at the given
+    /// position.
+    fn report_span(&mut self, index: usize, (start, wraps): ((usize, usize), usize)) {
+        let Some(listener) = self.span_listener else {
+            return;
+        };
+
+        let start = self.wraps[wraps..]
+            .iter()
+            .fold(start, |start, wrap| wrap.adjust(start));
+
+        let span = Span {
+            index,
+            start,
+            end: (self.current_line, self.column),
+            in_preamble: self.in_preamble,
+        };
+
+        if self.soft_break.is_some() {
+            self.soft_spans.push(span);
+        } else {
+            (listener.0)(span);
+        }
+    }
+
     /// Increase indentation level.
     fn indentation(&mut self, n: i16) {
         self.push();
@@ -156,6 +781,14 @@ impl<'a> Formatter<'a> {
         stack.push(Frame::default());
 
         while let (Some(item), Some(head)) = (cursor.next(), stack.last_mut()) {
+            #[cfg(feature = "locations")]
+            if let Some(location) = cursor.location() {
+                self.location = Some(location);
+            }
+
+            let index = cursor.index();
+            let outer = self.span_start.take();
+
             let Frame {
                 in_quote,
                 has_eval,
@@ -166,15 +799,21 @@ impl<'a> Formatter<'a> {
                 Item::Register(..) => (),
                 Item::Indentation(0) => (),
                 Item::Literal(literal) => {
-                    if *in_quote {
+                    if *in_quote && *has_eval {
+                        L::write_eval_quoted(self, literal)?;
+                    } else if *in_quote {
                         L::write_quoted(self, literal)?;
                     } else {
                         self.write_str(literal)?;
                     }
                 }
+                Item::Escaped(literal) => {
+                    self.write_str(literal)?;
+                }
                 Item::OpenQuote(e) if !*in_quote => {
                     *has_eval = *e;
                     *in_quote = true;
+                    self.quotes += 1;
                     L::open_quote(self, config, format, *has_eval)?;
                 }
                 // Warning: slow path which will buffer a string internally.
@@ -182,8 +821,15 @@ impl<'a> Formatter<'a> {
                 //
                 // Evaluating quotes are not supported.
                 Item::OpenQuote(false) if *in_quote => {
-                    self.quoted_quote(cursor, &mut buf, config, format)?;
-                    L::write_quoted(self, &buf)?;
+                    let result = self.quoted_quote(cursor, &mut buf, config, format);
+                    self.nested(index, result)?;
+
+                    if *has_eval {
+                        L::write_eval_quoted(self, &buf)?;
+                    } else {
+                        L::write_quoted(self, &buf)?;
+                    }
+
                     buf.clear();
                 }
                 Item::CloseQuote if end_on_close_quote => {
@@ -191,17 +837,23 @@ impl<'a> Formatter<'a> {
                 }
                 Item::CloseQuote if *in_quote => {
                     *in_quote = false;
+                    self.quotes -= 1;
                     L::close_quote(self, config, format, mem::take(has_eval))?;
                 }
                 Item::Lang(_, lang) => {
-                    lang.format(self, config, format)?;
+                    let result = lang.format(self, config, format);
+                    self.nested(index, result)?;
+                }
+                Item::Shared(_, tokens) => {
+                    let result = tokens.format(self, config, format);
+                    self.nested(index, result)?;
                 }
                 // whitespace below
                 Item::Push => {
                     self.push();
                 }
                 Item::Line => {
-                    self.line();
+                    self.push_line();
                 }
                 Item::Space => {
                     self.space();
@@ -209,6 +861,19 @@ impl<'a> Formatter<'a> {
                 Item::Indentation(n) => {
                     self.indentation(*n);
                 }
+                Item::TrailingComma => {
+                    if self.config.trailing_comma {
+                        self.write_str(",")?;
+                    }
+                }
+                Item::OperatorSpace => {
+                    if self.config.operator_spacing {
+                        self.space();
+                    }
+                }
+                Item::SoftBreak => {
+                    self.soft_break()?;
+                }
                 Item::OpenEval if *in_quote => {
                     if cursor.peek::<cursor::Literal>() && cursor.peek1::<cursor::CloseEval>() {
                         let literal = cursor.parse::<cursor::Literal>()?;
@@ -231,9 +896,17 @@ impl<'a> Formatter<'a> {
                 }
                 _ => {
                     // Anything else is an illegal state for formatting.
-                    return Err(std::fmt::Error);
+                    return Err(core::fmt::Error);
                 }
             }
+
+            if let Some(start) = self.span_start {
+                self.report_span(index, start);
+            }
+
+            // Items containing nested streams start where their first item
+            // started.
+            self.span_start = outer.or(self.span_start);
         }
 
         return Ok(());
@@ -246,6 +919,18 @@ impl<'a> Formatter<'a> {
         }
     }
 
+    /// Report a nested token stream which exceeded the maximum depth at the
+    /// index of the item holding it.
+    fn nested(&mut self, index: usize, result: fmt::Result) -> fmt::Result {
+        if result.is_err() {
+            if let Some(ValidationError::MaxDepthExceeded { index: i, .. }) = &mut self.error {
+                *i = index;
+            }
+        }
+
+        result
+    }
+
     /// Support for evaluating an interior quote and returning it as a string.
     fn quoted_quote<L>(
         &mut self,
@@ -261,43 +946,91 @@ impl<'a> Formatter<'a> {
 
         let mut w = FmtWriter::new(buf);
         let out = &mut Formatter::new(&mut w, self.config);
-        L::open_quote(out, config, format, false)?;
-        out.format_cursor(cursor, config, format, true)?;
-        L::close_quote(out, config, format, false)?;
-        Ok(())
+        // NB: spans are reported for the quote as a whole, and quotes are
+        // never broken.
+        out.span_listener = None;
+        out.max_width = None;
+        out.depth = self.depth;
+        let result = out.enter().and_then(|()| {
+            L::open_quote(out, config, format, false)?;
+            out.format_cursor(cursor, config, format, true)?;
+            L::close_quote(out, config, format, false)
+        });
+
+        if result.is_err() && self.error.is_none() {
+            self.error = out.error.take();
+        }
+
+        result
     }
 
     // Realize any pending whitespace just prior to writing a non-whitespace
-    // item.
-    fn flush_whitespace(&mut self) -> fmt::Result {
-        let mut spaces = mem::take(&mut self.spaces);
+    // item, which starts with the given string.
+    fn flush_whitespace(&mut self, next: &str) -> fmt::Result {
+        let spaces = mem::take(&mut self.spaces);
+
+        if self.config.minify {
+            let line = mem::take(&mut self.line);
+
+            let separate = match line {
+                Whitespace::Initial => false,
+                Whitespace::None => spaces > 0,
+                Whitespace::Push | Whitespace::Line => {
+                    matches!((self.last, next.chars().next()), (Some(a), Some(b)) if needs_separator(a, b))
+                }
+            };
+
+            if separate {
+                self.write_raw(" ")?;
+                self.column += 1;
+            }
+
+            return Ok(());
+        }
 
         if let Some(lines) = mem::take(&mut self.line).into_indent() {
             for _ in 0..lines {
-                self.write.write_line(self.config)?;
+                self.write_line()?;
             }
 
             let level = i16::max(self.indent, 0) as usize;
+            return self.write_indentation(level, spaces);
+        }
+
+        self.write_indentation(0, spaces)
+    }
+
+    /// Write the given level of indentation followed by the given number of
+    /// spaces.
+    fn write_indentation(&mut self, level: usize, mut spaces: usize) -> fmt::Result {
+        match &self.config.indentation {
+            Indentation::Space(n) => {
+                spaces += level * n;
+            }
+            Indentation::Tab => {
+                let mut tabs = level;
 
-            match self.config.indentation {
-                Indentation::Space(n) => {
-                    spaces += level * n;
+                while tabs > 0 {
+                    let len = usize::min(tabs, TABS.len());
+                    self.write_raw(&TABS[0..len])?;
+                    self.column += len;
+                    tabs -= len;
                 }
-                Indentation::Tab => {
-                    let mut tabs = level;
+            }
+            Indentation::Custom(indentation) => {
+                let width = indentation.chars().count();
 
-                    while tabs > 0 {
-                        let len = usize::min(tabs, TABS.len());
-                        self.write.write_str(&TABS[0..len])?;
-                        tabs -= len;
-                    }
+                for _ in 0..level {
+                    self.write_raw(indentation)?;
+                    self.column += width;
                 }
             }
         }
 
         while spaces > 0 {
             let len = usize::min(spaces, SPACES.len());
-            self.write.write_str(&SPACES[0..len])?;
+            self.write_raw(&SPACES[0..len])?;
+            self.column += len;
             spaces -= len;
         }
 
@@ -305,18 +1038,48 @@ impl<'a> Formatter<'a> {
     }
 }
 
-impl<'a> std::fmt::Write for Formatter<'a> {
+/// Test if the characters `a` and `b` would be read as part of the same token
+/// if whitespace between them was removed, like in `return x`, `a - -b`, or
+/// `a / /re/`.
+///
+/// Word characters following a `/` are also kept apart, since they would
+/// otherwise be read as the flags of a regular expression literal.
+fn needs_separator(a: char, b: char) -> bool {
+    fn is_word(c: char) -> bool {
+        c.is_alphanumeric() || matches!(c, '_' | '$') || !c.is_ascii()
+    }
+
+    (is_word(a) && is_word(b))
+        || (a == b && matches!(a, '+' | '-'))
+        || (a == '/' && (matches!(b, '/' | '*') || is_word(b)))
+}
+
+/// Writing to the formatter, like with [write!], is subject to the same
+/// indentation and whitespace rules as items in a token stream.
+///
+/// Outside of quotes, line endings in the written text are treated as line
+/// breaks, so the next line written is indented according to the current
+/// indentation.
+impl<'a> core::fmt::Write for Formatter<'a> {
     fn write_str(&mut self, s: &str) -> fmt::Result {
-        if !s.is_empty() {
-            Formatter::write_str(self, s)?;
+        if self.quotes > 0 {
+            return Formatter::write_str(self, s);
+        }
+
+        for (n, line) in s.split('\n').enumerate() {
+            if n > 0 {
+                self.write_newline()?;
+            }
+
+            Formatter::write_str(self, line.strip_suffix('\r').unwrap_or(line))?;
         }
 
         Ok(())
     }
 }
 
-impl<'a> std::fmt::Debug for Formatter<'a> {
-    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<'a> core::fmt::Debug for Formatter<'a> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         fmt.debug_struct("Formatter")
             .field("line", &self.line)
             .field("spaces", &self.spaces)
@@ -33,11 +33,16 @@ use std::io;
 /// assert_eq!("let mut m = HashMap::new();\nm.insert(1u32, 2u32);", string);
 /// # Ok::<_, anyhow::Error>(())
 /// ```
+///
+/// If writing to the underlying writer fails, formatting fails with a
+/// [fmt::Error][core::fmt::Error]. The [io::Error][std::io::Error] which
+/// caused it can be retrieved with [take_error][IoWriter::take_error].
 pub struct IoWriter<W>
 where
     W: io::Write,
 {
     writer: W,
+    error: Option<io::Error>,
 }
 
 impl<W> IoWriter<W>
@@ -46,7 +51,10 @@ where
 {
     /// Construct a new line writer from the underlying writer.
     pub fn new(writer: W) -> Self {
-        Self { writer }
+        Self {
+            writer,
+            error: None,
+        }
     }
 
     /// Convert into a formatter.
@@ -58,24 +66,69 @@ where
     pub fn into_inner(self) -> W {
         self.writer
     }
+
+    /// Take the I/O error which caused formatting to fail, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    ///
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// struct Closed;
+    ///
+    /// impl io::Write for Closed {
+    ///     fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+    ///         Err(io::Error::new(io::ErrorKind::BrokenPipe, "pipe is closed"))
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let tokens: rust::Tokens = quote!(fn foo() {});
+    ///
+    /// let mut w = fmt::IoWriter::new(Closed);
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    ///
+    /// let result = tokens.format_file(&mut w.as_formatter(&fmt), &rust::Config::default());
+    /// assert!(result.is_err());
+    ///
+    /// let error = w.take_error().expect("io error");
+    /// assert_eq!(io::ErrorKind::BrokenPipe, error.kind());
+    /// assert!(w.take_error().is_none());
+    /// ```
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Write all of the given bytes, recording the error if it fails.
+    fn write_all(&mut self, bytes: &[u8]) -> core::fmt::Result {
+        match self.writer.write_all(bytes) {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                self.error = Some(error);
+                Err(core::fmt::Error)
+            }
+        }
+    }
 }
 
-impl<W> std::fmt::Write for IoWriter<W>
+impl<W> core::fmt::Write for IoWriter<W>
 where
     W: io::Write,
 {
     #[inline(always)]
-    fn write_char(&mut self, c: char) -> std::fmt::Result {
-        self.writer
-            .write_all(c.encode_utf8(&mut [0; 4]).as_bytes())
-            .map_err(|_| std::fmt::Error)
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
+        self.write_all(c.encode_utf8(&mut [0; 4]).as_bytes())
     }
 
     #[inline(always)]
-    fn write_str(&mut self, s: &str) -> std::fmt::Result {
-        self.writer
-            .write_all(s.as_bytes())
-            .map_err(|_| std::fmt::Error)
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.write_all(s.as_bytes())
     }
 }
 
@@ -85,8 +138,6 @@ where
 {
     #[inline(always)]
     fn write_line(&mut self, config: &fmt::Config) -> fmt::Result {
-        self.writer
-            .write_all(config.newline.as_bytes())
-            .map_err(|_| std::fmt::Error)
+        self.write_all(config.newline.as_bytes())
     }
 }
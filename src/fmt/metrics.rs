@@ -0,0 +1,96 @@
+use crate::fmt;
+
+/// Writer which measures the output of the formatter instead of storing it.
+///
+/// This counts lines, the width of the widest line, and the total number of
+/// bytes written, including line endings. Widths are measured according to
+/// the [WidthMode][fmt::WidthMode] of the configuration passed to
+/// [as_formatter][Metrics::as_formatter].
+///
+/// See also [Tokens::measure][crate::Tokens::measure].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::fmt;
+///
+/// let tokens: rust::Tokens = quote! {
+///     fn foo() {
+///         bar();
+///     }
+/// };
+///
+/// let mut w = fmt::Metrics::new();
+///
+/// let fmt = fmt::Config::from_lang::<Rust>();
+/// let config = rust::Config::default();
+///
+/// tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+///
+/// assert_eq!(w.lines(), 3);
+/// assert_eq!(w.max_width(), 10);
+/// assert_eq!(w.bytes(), 24);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    lines: usize,
+    max_width: usize,
+    bytes: usize,
+    width: usize,
+    line_bytes: usize,
+    width_measure: fmt::WidthMode,
+}
+
+impl Metrics {
+    /// Construct a new empty set of metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert into a formatter.
+    pub fn as_formatter<'a>(&'a mut self, config: &'a fmt::Config) -> fmt::Formatter<'a> {
+        self.width_measure = config.width_measure.unwrap_or_default();
+        fmt::Formatter::new(self, config)
+    }
+
+    /// The number of lines written.
+    ///
+    /// A final line without a line ending is counted.
+    pub fn lines(&self) -> usize {
+        self.lines + usize::from(self.line_bytes > 0)
+    }
+
+    /// The width of the widest line written, not including line endings.
+    pub fn max_width(&self) -> usize {
+        self.max_width
+    }
+
+    /// The total number of bytes written, including line endings.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+}
+
+impl core::fmt::Write for Metrics {
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.bytes += s.len();
+        self.line_bytes += s.len();
+        self.width += self.width_measure.measure(s);
+        self.max_width = self.max_width.max(self.width);
+        Ok(())
+    }
+}
+
+impl fmt::Write for Metrics {
+    #[inline(always)]
+    fn write_line(&mut self, config: &fmt::Config) -> fmt::Result {
+        self.bytes += config.newline.len();
+        self.lines += 1;
+        self.width = 0;
+        self.line_bytes = 0;
+        Ok(())
+    }
+}
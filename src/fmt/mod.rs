@@ -10,6 +10,14 @@
 //!   implementing [fmt::Write][std::fmt::Write].
 //! * [fmt::IoWriter][IoWriter]- To write the result into something implementing
 //!   [io::Write][std::io::Write].
+//! * [fmt::PrefixWriter][PrefixWriter] - To write the result into something
+//!   implementing [fmt::Write][std::fmt::Write], prefixing every line.
+//! * `fmt::AsyncIoWriter` - To write the result into something implementing
+//!   `AsyncWrite` from tokio. This requires the `tokio` feature.
+//! * [fmt::Metrics][Metrics] - To measure the result without storing it.
+//! * [fmt::ComparingWriter][ComparingWriter] - To check whether the result
+//!   differs from existing content without storing it.
+//! * [fmt::FileSet][FileSet] - To write many files to a directory at once.
 //!
 //! # Examples
 //!
@@ -25,6 +33,7 @@
 //! use genco::fmt;
 //!
 //! # fn main() -> fmt::Result {
+//! # #[cfg(feature = "std")] {
 //! let map = rust::import("std::collections", "HashMap");
 //!
 //! let tokens: rust::Tokens = quote! {
@@ -43,30 +52,61 @@
 //! let format = rust::Format::default();
 //!
 //! tokens.format(&mut w.as_formatter(&fmt), &config, &format)?;
+//! # }
 //! # Ok(())
 //! # }
 //! ```
 
+#[cfg(feature = "tokio")]
+mod async_io_writer;
+mod comparing_writer;
 mod config;
 mod cursor;
+#[cfg(feature = "std")]
+mod file_set;
 mod fmt_writer;
 mod formatter;
+#[cfg(feature = "std")]
 mod io_writer;
+mod metrics;
+mod parse_config_error;
+mod prefix_writer;
+mod profile;
+#[cfg(feature = "locations")]
+mod source_map;
+mod span;
 mod vec_writer;
+#[cfg(feature = "std")]
+mod write_file_error;
 
-pub use self::config::{Config, Indentation};
+#[cfg(feature = "tokio")]
+pub use self::async_io_writer::AsyncIoWriter;
+pub use self::comparing_writer::ComparingWriter;
+pub use self::config::{Config, HeaderPlacement, Indentation, WidthMode};
+#[cfg(feature = "std")]
+pub use self::file_set::{FileSet, FileSetSummary};
 pub use self::fmt_writer::FmtWriter;
 pub use self::formatter::Formatter;
+#[cfg(feature = "std")]
 pub use self::io_writer::IoWriter;
+pub use self::metrics::Metrics;
+pub use self::parse_config_error::ParseConfigError;
+pub use self::prefix_writer::PrefixWriter;
+pub use self::profile::Profile;
+#[cfg(feature = "locations")]
+pub use self::source_map::{Mapping, SourceMap};
+pub use self::span::Span;
 pub use self::vec_writer::VecWriter;
+#[cfg(feature = "std")]
+pub use self::write_file_error::WriteFileError;
 
 /// Result type for the `fmt` module.
-pub type Result<T = ()> = std::result::Result<T, std::fmt::Error>;
+pub type Result<T = ()> = core::result::Result<T, core::fmt::Error>;
 /// Error for the `fmt` module.
-pub type Error = std::fmt::Error;
+pub type Error = core::fmt::Error;
 
 /// Trait that defines a line writer.
-pub(crate) trait Write: std::fmt::Write {
+pub(crate) trait Write: core::fmt::Write {
     /// Implement for writing a line.
     fn write_line(&mut self, config: &Config) -> Result;
 
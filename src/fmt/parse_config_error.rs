@@ -0,0 +1,43 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Error raised when parsing a [Config][crate::fmt::Config] from a string
+/// through its [FromStr][core::str::FromStr] implementation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseConfigError {
+    /// An option which is not of the form `key=value`.
+    MissingValue {
+        /// The option as it was written.
+        option: String,
+    },
+    /// An option with a key which is not recognized.
+    UnknownKey {
+        /// The unrecognized key.
+        key: String,
+    },
+    /// An option with a value which is not valid for its key.
+    InvalidValue {
+        /// The key of the option.
+        key: String,
+        /// The invalid value.
+        value: String,
+    },
+}
+
+impl fmt::Display for ParseConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingValue { option } => {
+                write!(f, "option `{}` is missing a value", option)
+            }
+            Self::UnknownKey { key } => write!(f, "unknown option `{}`", key),
+            Self::InvalidValue { key, value } => {
+                write!(f, "invalid value `{}` for option `{}`", value, key)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseConfigError {}
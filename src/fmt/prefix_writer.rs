@@ -0,0 +1,167 @@
+use crate::fmt;
+use crate::tokens::ItemStr;
+
+/// Helper struct to format a token stream to an underlying writer implementing
+/// [fmt::Write][std::fmt::Write], writing a prefix at the start of every line.
+///
+/// This is useful when embedding generated code in documentation comments or
+/// indented Markdown code blocks. The prefix is written before any
+/// indentation.
+///
+/// Empty lines are not prefixed by default, which can be changed with
+/// [with_empty_lines][PrefixWriter::with_empty_lines].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::fmt;
+///
+/// let map = rust::import("std::collections", "HashMap");
+///
+/// let tokens: rust::Tokens = quote! {
+///     fn main() {
+///         let mut m = $map::new();
+///         m.insert(1u32, 2u32);
+///     }
+/// };
+///
+/// let mut w = fmt::PrefixWriter::new(String::new(), "/// ");
+///
+/// let fmt = fmt::Config::from_lang::<Rust>();
+/// let config = rust::Config::default();
+///
+/// tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+///
+/// assert_eq!(
+///     vec![
+///         "/// use std::collections::HashMap;",
+///         "",
+///         "/// fn main() {",
+///         "///     let mut m = HashMap::new();",
+///         "///     m.insert(1u32, 2u32);",
+///         "/// }",
+///     ],
+///     w.into_inner().lines().collect::<Vec<_>>()
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub struct PrefixWriter<W>
+where
+    W: core::fmt::Write,
+{
+    writer: W,
+    prefix: ItemStr,
+    empty_lines: bool,
+    line_start: bool,
+}
+
+impl<W> PrefixWriter<W>
+where
+    W: core::fmt::Write,
+{
+    /// Construct a new prefix writer from the underlying writer and the
+    /// prefix to write at the start of every line.
+    pub fn new<P>(writer: W, prefix: P) -> Self
+    where
+        P: Into<ItemStr>,
+    {
+        Self {
+            writer,
+            prefix: prefix.into(),
+            empty_lines: false,
+            line_start: true,
+        }
+    }
+
+    /// Set whether empty lines should be prefixed as well.
+    ///
+    /// Trailing whitespace is trimmed from the prefix when it's written on an
+    /// empty line, so a `/// ` prefix results in `///`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn foo() {}
+    ///
+    ///     fn bar() {}
+    /// };
+    ///
+    /// let mut w = fmt::PrefixWriter::new(String::new(), "/// ").with_empty_lines(true);
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    /// let config = rust::Config::default();
+    ///
+    /// tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!("/// fn foo() {}\n///\n/// fn bar() {}\n", w.into_inner());
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_empty_lines(self, empty_lines: bool) -> Self {
+        Self {
+            empty_lines,
+            ..self
+        }
+    }
+
+    /// Convert into a formatter.
+    pub fn as_formatter<'a>(&'a mut self, config: &'a fmt::Config) -> fmt::Formatter<'a> {
+        fmt::Formatter::new(self, config)
+    }
+
+    /// Convert into underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Write the prefix if we're at the start of a line.
+    #[inline(always)]
+    fn write_prefix(&mut self) -> core::fmt::Result {
+        if self.line_start {
+            self.line_start = false;
+            self.writer.write_str(&self.prefix)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W> core::fmt::Write for PrefixWriter<W>
+where
+    W: core::fmt::Write,
+{
+    #[inline(always)]
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
+        self.write_prefix()?;
+        self.writer.write_char(c)
+    }
+
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+
+        self.write_prefix()?;
+        self.writer.write_str(s)
+    }
+}
+
+impl<W> fmt::Write for PrefixWriter<W>
+where
+    W: core::fmt::Write,
+{
+    #[inline(always)]
+    fn write_line(&mut self, config: &fmt::Config) -> fmt::Result {
+        if self.line_start && self.empty_lines {
+            self.writer.write_str(self.prefix.trim_end())?;
+        }
+
+        self.line_start = true;
+        self.writer.write_str(config.newline)
+    }
+}
@@ -0,0 +1,35 @@
+/// A preset combination of indentation, line endings, and trailing newline
+/// used by [Config::profile][crate::fmt::Config::profile].
+///
+/// | Profile   | Indentation | Newline | Trailing newline |
+/// |-----------|-------------|---------|------------------|
+/// | `Google`  | 2 spaces    | `\n`    | yes              |
+/// | `Rustfmt` | 4 spaces    | `\n`    | yes              |
+/// | `GoFmt`   | tabs        | `\n`    | yes              |
+/// | `Compact` | 1 space     | `\n`    | no               |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// Two space indentation, as used by the Google style guides.
+    Google,
+    /// Four space indentation, as used by `rustfmt`.
+    Rustfmt,
+    /// Tab indentation, as used by `gofmt`.
+    GoFmt,
+    /// Single space indentation without a trailing newline, for output which
+    /// should take up as little space as possible while staying readable.
+    Compact,
+}
+
+impl Profile {
+    /// Parse the name of a profile, as used in the `profile` option when
+    /// parsing a [Config][crate::fmt::Config].
+    pub(super) fn parse(s: &str) -> Option<Self> {
+        match s {
+            "google" => Some(Self::Google),
+            "rustfmt" => Some(Self::Rustfmt),
+            "gofmt" => Some(Self::GoFmt),
+            "compact" => Some(Self::Compact),
+            _ => None,
+        }
+    }
+}
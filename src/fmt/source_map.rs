@@ -0,0 +1,170 @@
+use crate::tokens::Location;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Base64 alphabet used by VLQ encoding.
+static BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A single mapping from a position in the formatted output to the location
+/// in Rust source that produced it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mapping {
+    line: usize,
+    column: usize,
+    source: usize,
+    location: Location,
+}
+
+impl Mapping {
+    /// The line in the output at which the mapped item was written, starting
+    /// at 0.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column in the output at which the mapped item was written,
+    /// starting at 0.
+    ///
+    /// This is counted in UTF-16 code units as [source map v3] requires, so
+    /// characters outside of the Basic Multilingual Plane like emoji count as
+    /// two columns.
+    ///
+    /// [source map v3]: https://sourcemaps.info/spec.html
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The index of the source file of [location()][Self::location] in
+    /// [SourceMap::sources].
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// The location which produced the mapped item.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+}
+
+/// A source map collected while formatting, mapping positions in the output
+/// back to the locations recorded for the formatted items.
+///
+/// This is populated by languages which support source maps, like
+/// [JavaScript][crate::lang::JavaScript], and can be accessed through
+/// [Formatter::source_map][crate::fmt::Formatter::source_map].
+///
+/// This requires the `locations` feature to be enabled.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    sources: Vec<&'static str>,
+    mappings: Vec<Mapping>,
+}
+
+impl SourceMap {
+    /// The source files referenced by the mappings, in order of first
+    /// appearance.
+    pub fn sources(&self) -> &[&'static str] {
+        &self.sources
+    }
+
+    /// The collected mappings, in output order.
+    pub fn mappings(&self) -> &[Mapping] {
+        &self.mappings
+    }
+
+    /// Encode the mappings as a [source map v3] `mappings` string.
+    ///
+    /// Source indexes refer to [sources()][Self::sources].
+    ///
+    /// [source map v3]: https://sourcemaps.info/spec.html
+    pub fn to_mappings_string(&self) -> String {
+        let mut out = String::new();
+
+        let mut line = 0;
+        let mut column = 0;
+        let mut source = 0;
+        let mut source_line = 0;
+        let mut source_column = 0;
+
+        for m in &self.mappings {
+            if m.line > line {
+                for _ in line..m.line {
+                    out.push(';');
+                }
+
+                line = m.line;
+                column = 0;
+            } else if !out.is_empty() && !out.ends_with(';') {
+                out.push(',');
+            }
+
+            let original_line = m.location.line().saturating_sub(1);
+
+            encode_vlq(&mut out, m.column as i64 - column as i64);
+            encode_vlq(&mut out, m.source as i64 - source as i64);
+            encode_vlq(&mut out, original_line as i64 - source_line as i64);
+            encode_vlq(&mut out, m.location.column() as i64 - source_column as i64);
+
+            column = m.column;
+            source = m.source;
+            source_line = original_line;
+            source_column = m.location.column();
+        }
+
+        out
+    }
+
+    /// Move mappings from `from` onwards which are past `column` on `line`
+    /// onto the next line, where the text past the column is continued from
+    /// `indent`.
+    pub(super) fn wrap(&mut self, from: usize, line: usize, column: usize, indent: usize) {
+        for m in self.mappings.iter_mut().skip(from) {
+            if m.line == line && m.column > column {
+                m.line += 1;
+                m.column = m.column - column - 1 + indent;
+            }
+        }
+    }
+
+    /// Add a mapping.
+    pub(super) fn push(&mut self, line: usize, column: usize, location: Location) {
+        let source = match self.sources.iter().position(|s| *s == location.file()) {
+            Some(source) => source,
+            None => {
+                self.sources.push(location.file());
+                self.sources.len() - 1
+            }
+        };
+
+        self.mappings.push(Mapping {
+            line,
+            column,
+            source,
+            location,
+        });
+    }
+}
+
+/// Encode a single value as a base64 VLQ.
+fn encode_vlq(out: &mut String, value: i64) {
+    let mut value = if value < 0 {
+        ((-value as u64) << 1) | 1
+    } else {
+        (value as u64) << 1
+    };
+
+    loop {
+        let mut digit = (value & 0b11111) as usize;
+        value >>= 5;
+
+        if value > 0 {
+            digit |= 0b100000;
+        }
+
+        out.push(BASE64[digit] as char);
+
+        if value == 0 {
+            break;
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use alloc::sync::Arc;
+use core::fmt;
+
+/// The region of the output which an item was written to.
+///
+/// Spans are reported to the listener configured through
+/// [Config::with_span_listener][crate::fmt::Config::with_span_listener].
+/// Lines and columns start at 0, and columns are measured according to the
+/// configured [WidthMode][crate::fmt::WidthMode].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub(super) index: usize,
+    pub(super) start: (usize, usize),
+    pub(super) end: (usize, usize),
+    pub(super) in_preamble: bool,
+}
+
+impl Span {
+    /// The index of the item in the token stream it belongs to.
+    ///
+    /// Nested token streams, like the imports written by
+    /// [Tokens::format_file][crate::Tokens::format_file], report the indexes
+    /// of their own items. Use [in_preamble][Self::in_preamble] to tell them
+    /// apart from the items of the file.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Test if the item was written by the language as part of formatting a
+    /// file rather than being an item of the token stream being formatted.
+    ///
+    /// This is the case for items of the preamble, like imports and package
+    /// declarations, and for anything the language writes around the token
+    /// stream, like the closing brace of a namespace.
+    pub fn in_preamble(&self) -> bool {
+        self.in_preamble
+    }
+
+    /// The line and column at which the item starts.
+    pub fn start(&self) -> (usize, usize) {
+        self.start
+    }
+
+    /// The line and column just past the end of the item.
+    pub fn end(&self) -> (usize, usize) {
+        self.end
+    }
+}
+
+/// A shared callback receiving the span of every item written.
+#[derive(Clone)]
+pub(super) struct SpanListener(pub(super) Arc<dyn Fn(Span) + Send + Sync>);
+
+impl fmt::Debug for SpanListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SpanListener").finish()
+    }
+}
@@ -1,4 +1,6 @@
 use crate::fmt;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 /// Helper struct to format a token stream as a vector of strings.
 ///
@@ -41,6 +43,7 @@ use crate::fmt;
 pub struct VecWriter {
     line_buffer: String,
     target: Vec<String>,
+    keep_trailing: bool,
 }
 
 impl VecWriter {
@@ -49,6 +52,38 @@ impl VecWriter {
         Self::default()
     }
 
+    /// Set whether the trailing line ending of files should be kept, which
+    /// results in a final empty line.
+    ///
+    /// This is disabled by default. Enabling it makes the output match
+    /// splitting the file on line endings, which is useful when comparing
+    /// against files on disk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let tokens: rust::Tokens = quote!(fn foo() {});
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    /// let config = rust::Config::default();
+    ///
+    /// let mut w = fmt::VecWriter::new().with_keep_trailing(true);
+    /// tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// let expected = tokens.to_file_string()?;
+    /// assert_eq!(expected.split('\n').collect::<Vec<_>>(), w.into_vec());
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_keep_trailing(self, keep_trailing: bool) -> Self {
+        Self {
+            keep_trailing,
+            ..self
+        }
+    }
+
     /// Convert into a formatter.
     pub fn as_formatter<'a>(&'a mut self, config: &'a fmt::Config) -> fmt::Formatter<'a> {
         fmt::Formatter::new(self, config)
@@ -61,14 +96,14 @@ impl VecWriter {
     }
 }
 
-impl std::fmt::Write for VecWriter {
+impl core::fmt::Write for VecWriter {
     #[inline(always)]
-    fn write_char(&mut self, c: char) -> std::fmt::Result {
+    fn write_char(&mut self, c: char) -> core::fmt::Result {
         self.line_buffer.write_char(c)
     }
 
     #[inline(always)]
-    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
         self.line_buffer.write_str(s)
     }
 }
@@ -81,8 +116,12 @@ impl fmt::Write for VecWriter {
         Ok(())
     }
 
-    // NB: trailing line is ignored for vector writer.
-    fn write_trailing_line(&mut self, _: &fmt::Config) -> fmt::Result {
+    // NB: trailing line is ignored for vector writer unless it's kept.
+    fn write_trailing_line(&mut self, config: &fmt::Config) -> fmt::Result {
+        if self.keep_trailing {
+            self.write_line(config)?;
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,46 @@
+use core::fmt;
+use std::error;
+use std::io;
+
+/// Error raised when writing a token stream to a file through
+/// [Tokens::to_file_path][crate::Tokens::to_file_path].
+///
+/// This distinguishes failures in the underlying filesystem from errors
+/// raised while formatting the token stream.
+#[derive(Debug)]
+pub enum WriteFileError {
+    /// An I/O error occured while creating directories or writing the file.
+    Io(io::Error),
+    /// An error occured while formatting the token stream.
+    Format(fmt::Error),
+}
+
+impl fmt::Display for WriteFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteFileError::Io(..) => write!(f, "failed to write file"),
+            WriteFileError::Format(..) => write!(f, "failed to format file"),
+        }
+    }
+}
+
+impl error::Error for WriteFileError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            WriteFileError::Io(e) => Some(e),
+            WriteFileError::Format(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for WriteFileError {
+    fn from(error: io::Error) -> Self {
+        WriteFileError::Io(error)
+    }
+}
+
+impl From<fmt::Error> for WriteFileError {
+    fn from(error: fmt::Error) -> Self {
+        WriteFileError::Format(error)
+    }
+}
@@ -1,11 +1,90 @@
 //! Specialization for C code generation.
+//!
+//! Includes are collected and de-duplicated at the top of the file, with
+//! system headers using `<>` and local headers using `""`.
+//!
+//! # Examples
+//!
+//! ```
+//! use genco::prelude::*;
+//!
+//! let printf = &c::include_system("stdio.h", "printf");
+//! let greet = &c::include("greet.h", "greet");
+//!
+//! let tokens = quote! {
+//!     int main() {
+//!         $printf($(quoted("Hello\n")));
+//!         $printf($(quoted("World\n")));
+//!         $greet();
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "#include \"greet.h\"",
+//!         "#include <stdio.h>",
+//!         "",
+//!         "int main() {",
+//!         "    printf(\"Hello\\n\");",
+//!         "    printf(\"World\\n\");",
+//!         "    greet();",
+//!         "}",
+//!     ],
+//!     tokens.to_file_vec()?
+//! );
+//! # Ok::<_, genco::fmt::Error>(())
+//! ```
 
 use crate as genco;
 use crate::fmt;
 use crate::quote_in;
 use crate::tokens::{quoted, ItemStr};
-use std::collections::BTreeSet;
-use std::fmt::Write as _;
+use alloc::collections::BTreeSet;
+use core::fmt::Write as _;
+use core::iter;
+
+/// Reserved words in C, which can't be used as identifiers.
+///
+/// See <https://en.cppreference.com/w/c/keyword>.
+const KEYWORDS: &[&str] = &[
+    "auto",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+    "_Bool",
+    "_Complex",
+    "_Imaginary",
+];
 
 /// Tokens container specialization for C.
 pub type Tokens = crate::Tokens<C>;
@@ -17,6 +96,10 @@ impl_lang! {
         type Format = Format;
         type Item = Import;
 
+        fn null_literal() -> &'static str {
+            "NULL"
+        }
+
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             super::c_family_write_quoted(out, input)
         }
@@ -30,7 +113,7 @@ impl_lang! {
 
             Self::imports(&mut header, tokens);
             let format = Format::default();
-            header.format(out, config, &format)?;
+            out.preamble(|out| header.format(out, config, &format))?;
             tokens.format(out, config, &format)?;
             Ok(())
         }
@@ -41,6 +124,10 @@ impl_lang! {
             out.write_str(&self.item)?;
             Ok(())
         }
+
+        fn reserved_name(&self) -> Option<&str> {
+            crate::lang::find_reserved(KEYWORDS, iter::once(&*self.item))
+        }
     }
 }
 
@@ -70,7 +157,7 @@ impl C {
     fn imports(out: &mut Tokens, tokens: &Tokens) {
         let mut includes = BTreeSet::new();
 
-        for include in tokens.walk_imports() {
+        for include in tokens.walk_imports_dedup() {
             includes.insert((&include.path, include.system));
         }
 
@@ -16,6 +16,30 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # String Interpolation in C#
+//!
+//! C# supports [interpolated strings] like `$"Hello {name}"`, which are used
+//! when quoting with `$[str](<content>)`. Literal braces in the surrounding
+//! text of an interpolated string are escaped by doubling them.
+//!
+//! [interpolated strings]: https://learn.microsoft.com/en-us/dotnet/csharp/language-reference/tokens/interpolated
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: csharp::Tokens = quote!($[str](Hello $(name)));
+//! assert_eq!("$\"Hello {name}\"", toks.to_string()?);
+//!
+//! let toks: csharp::Tokens = quote!($[str]($[const]("{") $(a + b) $[const]("}")));
+//! assert_eq!("$\"{{ {a + b} }}\"", toks.to_string()?);
+//!
+//! let toks: csharp::Tokens = quote!($[str](Hello $[const]("{world}")));
+//! assert_eq!("\"Hello {world}\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
 
 mod block_comment;
 mod comment;
@@ -24,8 +48,11 @@ use crate as genco;
 use crate::fmt;
 use crate::quote_in;
 use crate::tokens::ItemStr;
-use std::collections::{BTreeSet, HashMap, HashSet};
-use std::fmt::Write as _;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::string::ToString;
+use core::fmt::Write as _;
+use core::iter;
 
 pub use self::block_comment::BlockComment;
 pub use self::comment::Comment;
@@ -33,6 +60,8 @@ pub use self::comment::Comment;
 /// Tokens container specialization for C#.
 pub type Tokens = crate::Tokens<Csharp>;
 
+impl crate::lang::LangSupportsEval for Csharp {}
+
 impl_lang! {
     /// Language specialization for C#.
     pub Csharp {
@@ -40,11 +69,59 @@ impl_lang! {
         type Format = Format;
         type Item = Import;
 
+        fn open_quote(
+            out: &mut fmt::Formatter<'_>,
+            _config: &Self::Config,
+            _format: &Self::Format,
+            has_eval: bool,
+        ) -> fmt::Result {
+            if has_eval {
+                out.write_char('$')?;
+            }
+
+            out.write_char('"')?;
+            Ok(())
+        }
+
+        fn start_string_eval(
+            out: &mut fmt::Formatter<'_>,
+            _config: &Self::Config,
+            _format: &Self::Format,
+        ) -> fmt::Result {
+            out.write_char('{')?;
+            Ok(())
+        }
+
+        fn end_string_eval(
+            out: &mut fmt::Formatter<'_>,
+            _config: &Self::Config,
+            _format: &Self::Format,
+        ) -> fmt::Result {
+            out.write_char('}')?;
+            Ok(())
+        }
+
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             // From: https://csharpindepth.com/articles/Strings
             super::c_family_write_quoted(out, input)
         }
 
+        fn write_eval_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // Braces are escaped by doubling them in interpolated strings.
+            let mut start = 0;
+
+            for (n, c) in input.char_indices() {
+                if let '{' | '}' = c {
+                    super::c_family_write_quoted(out, &input[start..n])?;
+                    out.write_char(c)?;
+                    out.write_char(c)?;
+                    start = n + c.len_utf8();
+                }
+            }
+
+            super::c_family_write_quoted(out, &input[start..])
+        }
+
         fn format_file(
             tokens: &Tokens,
             out: &mut fmt::Formatter<'_>,
@@ -56,19 +133,32 @@ impl_lang! {
 
             Self::imports(&mut file, tokens, config, &mut format.imported_names);
 
-            if let Some(namespace) = &config.namespace {
-                quote_in! { file =>
-                    namespace $namespace {
-                        $tokens
-                    }
-                }
+            // NB: the namespace is written around the body rather than
+            // interpolating it, so that spans of the body aren't reported as
+            // part of the preamble.
+            let Some(namespace) = &config.namespace else {
+                out.preamble(|out| file.format(out, config, &format))?;
+                tokens.format(out, config, &format)?;
+                return Ok(());
+            };
 
-                file.format(out, config, &format)?;
-            } else {
-                file.format(out, config, &format)?;
+            if config.file_scoped_namespace {
+                quote_in!(file => namespace $namespace;);
+                file.line();
+                out.preamble(|out| file.format(out, config, &format))?;
                 tokens.format(out, config, &format)?;
+                return Ok(());
             }
 
+            quote_in!(file => namespace $namespace $("{"));
+            file.indent();
+            out.preamble(|out| file.format(out, config, &format))?;
+            tokens.format(out, config, &format)?;
+
+            let mut end: Tokens = Tokens::new();
+            end.unindent();
+            end.append("}");
+            out.preamble(|out| end.format(out, config, &format))?;
             Ok(())
         }
     }
@@ -106,12 +196,99 @@ impl_lang! {
                 false
             }
         }
+
+        fn reserved_name(&self) -> Option<&str> {
+            crate::lang::find_reserved(KEYWORDS, iter::once(&*self.name))
+        }
     }
 }
 
 /// Separator between types and modules in C#.
 const SEP: &str = ".";
 
+/// Reserved words in C#, which can't be used as identifiers.
+///
+/// See <https://learn.microsoft.com/en-us/dotnet/csharp/language-reference/keywords/>.
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "base",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "checked",
+    "class",
+    "const",
+    "continue",
+    "decimal",
+    "default",
+    "delegate",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "float",
+    "for",
+    "foreach",
+    "goto",
+    "if",
+    "implicit",
+    "in",
+    "int",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "object",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "ref",
+    "return",
+    "sbyte",
+    "sealed",
+    "short",
+    "sizeof",
+    "stackalloc",
+    "static",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "uint",
+    "ulong",
+    "unchecked",
+    "unsafe",
+    "ushort",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+];
+
 /// State using during formatting of C# language items.
 #[derive(Debug, Default)]
 pub struct Format {
@@ -119,7 +296,7 @@ pub struct Format {
     /// their use has to be qualified or not.
     ///
     /// A missing name means that it has to be used in a qualified manner.
-    imported_names: HashMap<String, String>,
+    imported_names: BTreeMap<String, String>,
 }
 
 /// Config data for Csharp formatting.
@@ -127,16 +304,96 @@ pub struct Format {
 pub struct Config {
     /// namespace to use.
     namespace: Option<ItemStr>,
+    /// Whether the namespace is declared for the whole file.
+    file_scoped_namespace: bool,
 }
 
 impl Config {
     /// Set the namespace name to build.
+    ///
+    /// The generated file is wrapped in a namespace block, with the body
+    /// indented one level, unless a [file-scoped
+    /// namespace][Self::with_file_scoped_namespace] is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: csharp::Tokens = quote! {
+    ///     class Foo {
+    ///     }
+    /// };
+    ///
+    /// let config = csharp::Config::default().with_namespace("Example");
+    /// let fmt = fmt::Config::from_lang::<Csharp>();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "namespace Example {",
+    ///         "    class Foo {",
+    ///         "    }",
+    ///         "}",
+    ///     ],
+    ///     tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
     pub fn with_namespace<N>(self, namespace: N) -> Self
     where
         N: Into<ItemStr>,
     {
         Self {
             namespace: Some(namespace.into()),
+            ..self
+        }
+    }
+
+    /// Set whether the [namespace][Self::with_namespace] should be declared
+    /// as a file-scoped namespace, like `namespace Foo;`, which applies to
+    /// the whole file without indenting it.
+    ///
+    /// File-scoped namespaces require C# 10 or later, so this is disabled by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let list = csharp::import("System.Collections.Generic", "List");
+    ///
+    /// let tokens: csharp::Tokens = quote! {
+    ///     class Foo {
+    ///         $list<int> values;
+    ///     }
+    /// };
+    ///
+    /// let config = csharp::Config::default()
+    ///     .with_namespace("Example")
+    ///     .with_file_scoped_namespace(true);
+    /// let fmt = fmt::Config::from_lang::<Csharp>();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "using System.Collections.Generic;",
+    ///         "",
+    ///         "namespace Example;",
+    ///         "",
+    ///         "class Foo {",
+    ///         "    List<int> values;",
+    ///         "}",
+    ///     ],
+    ///     tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_file_scoped_namespace(self, file_scoped_namespace: bool) -> Self {
+        Self {
+            file_scoped_namespace,
+            ..self
         }
     }
 }
@@ -170,11 +427,11 @@ impl Csharp {
         out: &mut Tokens,
         tokens: &Tokens,
         config: &Config,
-        imported_names: &mut HashMap<String, String>,
+        imported_names: &mut BTreeMap<String, String>,
     ) {
         let mut modules = BTreeSet::new();
 
-        for import in tokens.walk_imports() {
+        for import in tokens.walk_imports_dedup() {
             modules.insert((&*import.namespace, &*import.name));
         }
 
@@ -182,7 +439,7 @@ impl Csharp {
             return;
         }
 
-        let mut imported = HashSet::new();
+        let mut imported = BTreeSet::new();
 
         for (namespace, name) in modules {
             if Some(namespace) == config.namespace.as_deref() {
@@ -40,13 +40,28 @@ pub use self::doc_comment::DocComment;
 use crate as genco;
 use crate::fmt;
 use crate::quote_in;
-use crate::tokens::{quoted, ItemStr};
-use std::fmt::Write as _;
+use crate::tokens::{from_fn, quoted, FormatInto, ItemStr};
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+use core::iter;
+use relative_path::{RelativePath, RelativePathBuf};
 
 const SEP: &str = ".";
 /// dart:core package.
 const DART_CORE: &str = "dart:core";
 
+/// Reserved words in Dart, which can't be used as identifiers.
+///
+/// See <https://dart.dev/language/keywords>.
+const KEYWORDS: &[&str] = &[
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void", "while",
+    "with",
+];
+
 /// Tokens container specialization for Dart.
 pub type Tokens = crate::Tokens<Dart>;
 
@@ -132,10 +147,22 @@ impl_lang! {
             out: &mut fmt::Formatter<'_>,
             config: &Self::Config,
         ) -> fmt::Result {
-            let mut imports: Tokens = Tokens::new();
-            Self::imports(&mut imports, tokens, config);
+            let mut header: Tokens = Tokens::new();
+
+            if let Some(part_of) = &config.part_of {
+                // Parts can't have imports of their own.
+                if tokens.walk_imports().any(|import| &*import.path != DART_CORE) {
+                    return Err(core::fmt::Error);
+                }
+
+                quote_in!(header => part of $(quoted(part_of)););
+                header.line();
+            } else {
+                Self::imports(&mut header, tokens, config);
+            }
+
             let format = Format::default();
-            imports.format(out, config, &format)?;
+            out.preamble(|out| header.format(out, config, &format))?;
             tokens.format(out, config, &format)?;
             Ok(())
         }
@@ -151,6 +178,13 @@ impl_lang! {
             out.write_str(&self.name)?;
             Ok(())
         }
+
+        fn reserved_name(&self) -> Option<&str> {
+            crate::lang::find_reserved(
+                KEYWORDS,
+                iter::once(&*self.name).chain(self.alias.as_deref()),
+            )
+        }
     }
 }
 
@@ -160,7 +194,195 @@ pub struct Format {}
 
 /// Config data for Dart formatting.
 #[derive(Debug, Default)]
-pub struct Config {}
+pub struct Config {
+    /// The library this file is a part of.
+    part_of: Option<ItemStr>,
+    /// Path of the file being rendered, relative to the package root.
+    current_file: Option<RelativePathBuf>,
+    /// Name of the package being rendered.
+    package_name: Option<ItemStr>,
+}
+
+impl Config {
+    /// Format the file as a part of the given library, emitting a `part of`
+    /// directive.
+    ///
+    /// Parts can't have imports of their own, so formatting the file fails
+    /// if any imports are used. They should instead be imported by the
+    /// library.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let toks: dart::Tokens = quote! {
+    ///     class Foo {}
+    /// };
+    ///
+    /// let config = dart::Config::default().with_part_of("lib.dart");
+    /// let fmt = fmt::Config::from_lang::<Dart>();
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "part of \"lib.dart\";",
+    ///         "",
+    ///         "class Foo {}",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    ///
+    /// let http = dart::import("package:http/http.dart", "Client");
+    /// let toks: dart::Tokens = quote!($http());
+    ///
+    /// let config = dart::Config::default().with_part_of("lib.dart");
+    /// let mut w = fmt::VecWriter::new();
+    /// assert!(toks.format_file(&mut w.as_formatter(&fmt), &config).is_err());
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_part_of(self, part_of: impl Into<ItemStr>) -> Self {
+        Self {
+            part_of: Some(part_of.into()),
+            ..self
+        }
+    }
+
+    /// Configure the path of the file being rendered, relative to the root of
+    /// the package, like `"lib/src/a/b.dart"`.
+    ///
+    /// When set, imports of files in the same package are normalized. They
+    /// can be specified either as a path relative to the root of the package,
+    /// like `"lib/src/c.dart"`, or as a `package:` URI of the current package.
+    ///
+    /// Files in `lib/src` import other files relatively, while other files
+    /// import files in `lib` through `package:` URIs if the name of the
+    /// package has been configured with [with_package_name()]. `dart:` imports
+    /// and `package:` imports of other packages are left untouched.
+    ///
+    /// [with_package_name()]: Self::with_package_name
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let a = dart::import("lib/src/c.dart", "A");
+    /// let b = dart::import("package:my_pkg/src/a/d.dart", "B");
+    /// let c = dart::import("lib/e.dart", "C");
+    /// let d = dart::import("package:http/http.dart", "D");
+    ///
+    /// let toks: dart::Tokens = quote!($a $b $c $d);
+    ///
+    /// let fmt = fmt::Config::from_lang::<Dart>();
+    ///
+    /// let config = dart::Config::default()
+    ///     .with_package_name("my_pkg")
+    ///     .with_current_file("lib/src/a/b.dart");
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import \"../../e.dart\";",
+    ///         "import \"../c.dart\";",
+    ///         "import \"d.dart\";",
+    ///         "import \"package:http/http.dart\";",
+    ///         "",
+    ///         "A B C D",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    ///
+    /// let config = dart::Config::default()
+    ///     .with_package_name("my_pkg")
+    ///     .with_current_file("test/a_test.dart");
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import \"package:http/http.dart\";",
+    ///         "import \"package:my_pkg/e.dart\";",
+    ///         "import \"package:my_pkg/src/a/d.dart\";",
+    ///         "import \"package:my_pkg/src/c.dart\";",
+    ///         "",
+    ///         "A B C D",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_current_file(self, current_file: impl Into<RelativePathBuf>) -> Self {
+        Self {
+            current_file: Some(current_file.into()),
+            ..self
+        }
+    }
+
+    /// Configure the name of the package being rendered, which is used to
+    /// normalize imports as described in [with_current_file()].
+    ///
+    /// [with_current_file()]: Self::with_current_file
+    pub fn with_package_name(self, package_name: impl Into<ItemStr>) -> Self {
+        Self {
+            package_name: Some(package_name.into()),
+            ..self
+        }
+    }
+
+    /// Normalize the path of an import according to the configuration.
+    fn normalize(&self, path: &ItemStr) -> ItemStr {
+        let current_file = match &self.current_file {
+            Some(current_file) => current_file,
+            None => return path.clone(),
+        };
+
+        let target = match self.package_path(path) {
+            Some(target) => target,
+            None => return path.clone(),
+        };
+
+        if let (Some(package_name), Some(rest)) =
+            (&self.package_name, target.strip_prefix("lib").ok())
+        {
+            if !current_file.starts_with("lib/src") {
+                return ItemStr::from(format!("package:{}/{}", package_name, rest));
+            }
+        }
+
+        let dir = current_file
+            .parent()
+            .unwrap_or_else(|| RelativePath::new(""));
+        ItemStr::from(dir.relative(&target).into_string())
+    }
+
+    /// Get the path of an import relative to the root of the package, if it
+    /// refers to a file in the current package.
+    fn package_path(&self, path: &str) -> Option<RelativePathBuf> {
+        if let Some(rest) = path.strip_prefix("package:") {
+            let (package, rest) = rest.split_once('/')?;
+
+            if Some(package) != self.package_name.as_deref() {
+                return None;
+            }
+
+            return Some(RelativePath::new("lib").join(rest));
+        }
+
+        if path.contains(':') || path.starts_with('.') || path.starts_with('/') {
+            return None;
+        }
+
+        Some(RelativePath::new(path).normalize())
+    }
+}
 
 /// The import of a Dart type `import "dart:math";`.
 ///
@@ -173,6 +395,14 @@ pub struct Import {
     name: ItemStr,
     /// Alias of module.
     alias: Option<ItemStr>,
+    /// If the import is deferred, which requires an alias.
+    deferred: bool,
+    /// Conditional alternatives to the path, as pairs of condition and path.
+    conditions: Vec<(ItemStr, ItemStr)>,
+    /// If the imported name should be listed in a `show` combinator.
+    show: bool,
+    /// Names to list in a `hide` combinator.
+    hide: Vec<ItemStr>,
 }
 
 impl Import {
@@ -183,34 +413,205 @@ impl Import {
             ..self
         }
     }
+
+    /// Import the library lazily with `deferred as <alias>`.
+    ///
+    /// Uses of the import are qualified with the alias, like `lazyLib.Foo`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let foo = dart::import("package:x/y.dart", "Foo").deferred_as("lazyLib");
+    /// let bar = dart::import("package:x/y.dart", "Bar");
+    ///
+    /// let toks = quote! {
+    ///     $foo
+    ///     $bar
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import \"package:x/y.dart\";",
+    ///         "import \"package:x/y.dart\" deferred as lazyLib;",
+    ///         "",
+    ///         "lazyLib.Foo",
+    ///         "Bar",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn deferred_as(self, alias: impl Into<ItemStr>) -> Import {
+        Self {
+            alias: Some(alias.into()),
+            deferred: true,
+            ..self
+        }
+    }
+
+    /// Add a conditional alternative path to the import, like `if
+    /// (dart.library.io) "b.dart"`.
+    ///
+    /// Conditions are rendered in the order they are added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let client = dart::import("src/stub.dart", "Client")
+    ///     .with_condition("dart.library.io", "src/io.dart")
+    ///     .with_condition("dart.library.html", "src/html.dart");
+    ///
+    /// let toks = quote!($client);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import \"src/stub.dart\" if (dart.library.io) \"src/io.dart\" if (dart.library.html) \"src/html.dart\";",
+    ///         "",
+    ///         "Client",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_condition(
+        mut self,
+        condition: impl Into<ItemStr>,
+        path: impl Into<ItemStr>,
+    ) -> Import {
+        self.conditions.push((condition.into(), path.into()));
+        self
+    }
+
+    /// List the imported name in a `show` combinator of the import.
+    ///
+    /// The combinator is only rendered if every use of the import asks for
+    /// it, since otherwise other names from the library would be hidden.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let a = dart::import("package:x/y.dart", "A").show();
+    /// let b = dart::import("package:x/y.dart", "B").show();
+    /// let c = dart::import("package:x/z.dart", "C").with_hide("D");
+    /// let e = dart::import("src/stub.dart", "E")
+    ///     .with_condition("dart.library.io", "src/io.dart")
+    ///     .deferred_as("lazy")
+    ///     .show();
+    ///
+    /// let toks = quote! {
+    ///     $a
+    ///     $b
+    ///     $c
+    ///     $e
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import \"package:x/y.dart\" show A, B;",
+    ///         "import \"package:x/z.dart\" hide D;",
+    ///         "import \"src/stub.dart\" if (dart.library.io) \"src/io.dart\" deferred as lazy show E;",
+    ///         "",
+    ///         "A",
+    ///         "B",
+    ///         "C",
+    ///         "lazy.E",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn show(self) -> Import {
+        Self { show: true, ..self }
+    }
+
+    /// Add a name to the `hide` combinator of the import.
+    ///
+    /// See [show()][Self::show] for an example.
+    pub fn with_hide(mut self, name: impl Into<ItemStr>) -> Import {
+        self.hide.push(name.into());
+        self
+    }
+}
+
+/// Combinators collected for a single import directive.
+#[derive(Default)]
+struct Combinators {
+    /// If any use of the import doesn't use a `show` combinator.
+    show_all: bool,
+    show: BTreeSet<ItemStr>,
+    hide: BTreeSet<ItemStr>,
 }
 
 impl Dart {
     /// Resolve all imports.
-    fn imports(out: &mut Tokens, input: &Tokens, _: &Config) {
-        use std::collections::BTreeSet;
+    fn imports(out: &mut Tokens, input: &Tokens, config: &Config) {
+        use alloc::collections::BTreeMap;
 
-        let mut modules = BTreeSet::new();
+        let mut modules = BTreeMap::new();
 
-        for import in input.walk_imports() {
+        for import in input.walk_imports_dedup() {
             if &*import.path == DART_CORE {
                 continue;
             }
 
-            modules.insert((import.path.clone(), import.alias.clone()));
+            let conditions = import
+                .conditions
+                .iter()
+                .map(|(condition, path)| (condition, config.normalize(path)))
+                .collect::<Vec<_>>();
+
+            let combinators: &mut Combinators = modules
+                .entry((
+                    config.normalize(&import.path),
+                    conditions,
+                    import.deferred,
+                    &import.alias,
+                ))
+                .or_default();
+
+            if import.show {
+                combinators.show.insert(import.name.clone());
+            } else {
+                combinators.show_all = true;
+            }
+
+            combinators.hide.extend(import.hide.iter().cloned());
         }
 
         if modules.is_empty() {
             return;
         }
 
-        for (name, alias) in modules {
+        for ((path, conditions, deferred, alias), combinators) in modules {
+            quote_in!(*out => import $(quoted(path)));
+
+            for (condition, path) in conditions {
+                quote_in!(*out => $[' ']if ($condition) $(quoted(path)));
+            }
+
+            if deferred {
+                quote_in!(*out => $[' ']deferred);
+            }
+
             if let Some(alias) = alias {
-                quote_in!(*out => import $(quoted(name)) as $alias;);
-            } else {
-                quote_in!(*out => import $(quoted(name)););
+                quote_in!(*out => $[' ']as $alias);
+            }
+
+            if !combinators.show_all {
+                quote_in!(*out => $[' ']show $(for name in combinators.show join (, ) => $name));
             }
 
+            if !combinators.hide.is_empty() {
+                quote_in!(*out => $[' ']hide $(for name in combinators.hide join (, ) => $name));
+            }
+
+            quote_in!(*out => ;);
             out.push();
         }
 
@@ -260,9 +661,51 @@ where
         path: path.into(),
         alias: None,
         name: name.into(),
+        deferred: false,
+        conditions: Vec::new(),
+        show: false,
+        hide: Vec::new(),
     }
 }
 
+/// Format a `part` directive, including the given file in the library.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let http = dart::import("package:http/http.dart", "Client");
+///
+/// let toks = quote! {
+///     $(dart::part("foo.g.dart"))
+///
+///     final client = $http();
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "import \"package:http/http.dart\";",
+///         "",
+///         "part \"foo.g.dart\";",
+///         "",
+///         "final client = Client();",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn part<P>(path: P) -> impl FormatInto<Dart>
+where
+    P: Into<ItemStr>,
+{
+    let path = path.into();
+
+    from_fn(move |t| {
+        quote_in!(*t => $['\r']part $(quoted(path));$['\r']);
+    })
+}
+
 /// Format a doc comment where each line is preceeded by `///`.
 ///
 /// # Examples
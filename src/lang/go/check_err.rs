@@ -0,0 +1,49 @@
+use crate::lang::Go;
+use crate::tokens::{self, block, from_fn, ItemStr};
+use crate::Tokens;
+
+/// Format an error check, like `if err != nil { return err }`.
+///
+/// This struct is created by the [check_err][super::check_err()] function.
+pub struct CheckErr<T> {
+    pub(super) value: T,
+    pub(super) var: ItemStr,
+}
+
+impl<T> CheckErr<T> {
+    /// Set the name of the error variable being checked.
+    ///
+    /// This defaults to `err`.
+    pub fn with_var<N>(self, var: N) -> Self
+    where
+        N: Into<ItemStr>,
+    {
+        Self {
+            var: var.into(),
+            ..self
+        }
+    }
+}
+
+impl<T> tokens::FormatInto<Go> for CheckErr<T>
+where
+    T: tokens::FormatInto<Go>,
+{
+    fn format_into(self, tokens: &mut Tokens<Go>) {
+        let value = self.value;
+
+        tokens.append(tokens::static_literal("if"));
+        tokens.space();
+        tokens.append(self.var);
+        tokens.space();
+        tokens.append(tokens::static_literal("!="));
+        tokens.space();
+        tokens.append(tokens::static_literal("nil"));
+        tokens.space();
+        tokens.append(block(from_fn(move |t| {
+            t.append(tokens::static_literal("return"));
+            t.space();
+            t.append(value);
+        })));
+    }
+}
@@ -0,0 +1,18 @@
+use crate::lang::Go;
+use crate::tokens;
+use crate::Tokens;
+
+/// Format a compiler directive, like `//go:generate stringer -type=Pill`.
+///
+/// This struct is created by the [directive][super::directive()] function.
+pub struct Directive<T>(pub(super) T);
+
+impl<T> tokens::FormatInto<Go> for Directive<T>
+where
+    T: tokens::FormatInto<Go>,
+{
+    fn format_into(self, tokens: &mut Tokens<Go>) {
+        tokens.append(tokens::static_literal("//"));
+        tokens.append(self.0);
+    }
+}
@@ -0,0 +1,409 @@
+//! Specialization for Go code generation.
+//!
+//! # Examples
+//!
+//! Basic example:
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: go::Tokens = quote! {
+//!     func foo(v string) string {
+//!         return v + ", World"
+//!     }
+//!
+//!     foo("Hello")
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "func foo(v string) string {",
+//!         "\treturn v + \", World\"",
+//!         "}",
+//!         "",
+//!         "foo(\"Hello\")",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! String quoting in Go:
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: go::Tokens = quote!("start π 😊 \n \x7f end");
+//! assert_eq!("\"start \\u03c0 \\U0001f60a \\n \\x7f end\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+mod check_err;
+mod directive;
+
+pub use self::check_err::CheckErr;
+pub use self::directive::Directive;
+
+use crate as genco;
+use crate::fmt;
+use crate::tokens::{quoted, FormatInto, Function, ItemStr};
+use crate::{quote, quote_in};
+use alloc::collections::BTreeSet;
+use core::fmt::Write as _;
+use core::iter;
+
+const MODULE_SEP: &str = "/";
+const SEP: &str = ".";
+
+/// Reserved words in Go, which can't be used as identifiers.
+///
+/// See <https://go.dev/ref/spec#Keywords>.
+const KEYWORDS: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+];
+
+/// Tokens container specialization for Go.
+pub type Tokens = crate::Tokens<Go>;
+
+impl_lang! {
+    /// Language specialization for Go.
+    pub Go {
+        type Config = Config;
+        type Format = Format;
+        type Item = Import;
+
+        fn default_indentation() -> fmt::Indentation {
+            // NB: gofmt indents with tabs.
+            fmt::Indentation::Tab
+        }
+
+        fn null_literal() -> &'static str {
+            "nil"
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://golang.org/src/strconv/quote.go
+            super::c_family_write_quoted(out, input)
+        }
+
+        fn format_file_directives(
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut header = Tokens::new();
+
+            if let Some(constraint) = &config.build_constraint {
+                header.append(directive(quote!(go:build $constraint)));
+                header.line();
+            }
+
+            header.format(out, config, &Format::default())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut header = Tokens::new();
+
+            if let Some(package) = &config.package {
+                quote_in!(header => package $package);
+                header.line();
+            }
+
+            Self::imports(&mut header, tokens);
+            let format = Format::default();
+            out.preamble(|out| header.format(out, config, &format))?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            if let Some(module) = self.module.rsplit(MODULE_SEP).next() {
+                out.write_str(module)?;
+                out.write_str(SEP)?;
+            }
+
+            out.write_str(&self.name)?;
+            Ok(())
+        }
+
+        fn reserved_name(&self) -> Option<&str> {
+            let module = self.module.rsplit(MODULE_SEP).next();
+            crate::lang::find_reserved(KEYWORDS, module.into_iter().chain(iter::once(&*self.name)))
+        }
+    }
+}
+
+/// The import of a Go type `import "foo/bar"`.
+///
+/// Created using the [import()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+pub struct Import {
+    /// Module of the imported name.
+    module: ItemStr,
+    /// Name imported.
+    name: ItemStr,
+}
+
+/// Format for Go.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Config data for Go.
+#[derive(Debug, Default)]
+pub struct Config {
+    package: Option<ItemStr>,
+    build_constraint: Option<ItemStr>,
+}
+
+impl Config {
+    /// Configure the specified package.
+    pub fn with_package<P: Into<ItemStr>>(self, package: P) -> Self {
+        Self {
+            package: Some(package.into()),
+            ..self
+        }
+    }
+
+    /// Configure a build constraint for the file, like `linux && amd64`.
+    ///
+    /// The constraint is rendered as a `//go:build` directive at the top of
+    /// the file, separated from the package clause by an empty line as
+    /// required by Go.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let tokens: go::Tokens = quote! {
+    ///     func main() {}
+    /// };
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    ///
+    /// let fmt = fmt::Config::from_lang::<Go>();
+    /// let config = go::Config::default()
+    ///     .with_package("main")
+    ///     .with_build_constraint("linux && amd64");
+    ///
+    /// tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "//go:build linux && amd64",
+    ///         "",
+    ///         "package main",
+    ///         "",
+    ///         "func main() {}",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_build_constraint<C: Into<ItemStr>>(self, constraint: C) -> Self {
+        Self {
+            build_constraint: Some(constraint.into()),
+            ..self
+        }
+    }
+}
+
+impl crate::lang::LangFunction for Go {
+    fn format_function(function: &Function<Self>, t: &mut Tokens) {
+        quote_in! { *t =>
+            func $(function.name())($(for (name, ty) in function.params() join (, ) => $name $ty))$(if let Some(ty) = function.return_type() => $[' ']$ty)
+        }
+
+        if let Some(body) = function.block() {
+            if body.is_empty() {
+                quote_in!(*t => $[' ']{});
+            } else {
+                quote_in! { *t =>
+                    $[' ']{
+                        $body
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Go {
+    fn imports(out: &mut Tokens, tokens: &Tokens) {
+        let mut modules = BTreeSet::new();
+
+        for import in tokens.walk_imports_dedup() {
+            modules.insert(&import.module);
+        }
+
+        if modules.is_empty() {
+            return;
+        }
+
+        for module in modules {
+            quote_in!(*out => import $(quoted(module)));
+            out.push();
+        }
+
+        out.line();
+    }
+}
+
+/// The import of a Go type `import "foo/bar"`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let ty = go::import("foo/bar", "Debug");
+///
+/// let toks = quote! {
+///     $ty
+/// };
+///
+/// assert_eq!(
+///     vec![
+///        "import \"foo/bar\"",
+///        "",
+///        "bar.Debug",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn import<M, N>(module: M, name: N) -> Import
+where
+    M: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        module: module.into(),
+        name: name.into(),
+    }
+}
+
+/// Format a compiler directive, like `//go:generate` or `//go:embed`.
+///
+/// The text is rendered directly after the `//` without a space, since that
+/// is what distinguishes a directive from a regular comment.
+///
+/// Build constraints must appear before the package clause, so use
+/// [Config::with_build_constraint] for them instead.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: go::Tokens = quote! {
+///     $(go::directive("go:generate stringer -type=Pill"))
+///     type Pill int
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "//go:generate stringer -type=Pill",
+///         "type Pill int",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn directive<T>(text: T) -> Directive<T>
+where
+    T: FormatInto<Go>,
+{
+    Directive(text)
+}
+
+/// Format the canonical error check, which returns the given value if the
+/// error variable is not `nil`.
+///
+/// The error variable defaults to `err`, and can be changed using
+/// [with_var][CheckErr::with_var].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let errors = go::import("errors", "New");
+///
+/// let toks: go::Tokens = quote! {
+///     func run() (int, error) {
+///         n, err := count()
+///         $(go::check_err(quote!(0, err)))
+///
+///         _, closeErr := close()
+///         $(go::check_err(quote!(0, $errors("close failed"))).with_var("closeErr"))
+///
+///         return n, nil
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "import \"errors\"",
+///         "",
+///         "func run() (int, error) {",
+///         "\tn, err := count()",
+///         "\tif err != nil {",
+///         "\t\treturn 0, err",
+///         "\t}",
+///         "",
+///         "\t_, closeErr := close()",
+///         "\tif closeErr != nil {",
+///         "\t\treturn 0, errors.New(\"close failed\")",
+///         "\t}",
+///         "",
+///         "\treturn n, nil",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn check_err<T>(value: T) -> CheckErr<T>
+where
+    T: FormatInto<Go>,
+{
+    CheckErr {
+        value,
+        var: ItemStr::Static("err"),
+    }
+}
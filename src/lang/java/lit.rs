@@ -0,0 +1,81 @@
+use crate::lang::Java;
+use crate::tokens;
+use crate::Tokens;
+use alloc::format;
+use alloc::string::String;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A numeric type which can be formatted as a Java literal.
+///
+/// This trait is sealed and is implemented for the primitive types which
+/// correspond to Java's signed numeric types.
+pub trait Numeric: sealed::Sealed {
+    #[doc(hidden)]
+    fn format_literal(&self) -> String;
+}
+
+macro_rules! integer {
+    ($($ty:ident => $format:literal),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl Numeric for $ty {
+                fn format_literal(&self) -> String {
+                    format!($format, self)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! float {
+    ($($ty:ident => $class:literal, $suffix:literal),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl Numeric for $ty {
+                fn format_literal(&self) -> String {
+                    if self.is_nan() {
+                        String::from(concat!($class, ".NaN"))
+                    } else if self.is_infinite() && self.is_sign_positive() {
+                        String::from(concat!($class, ".POSITIVE_INFINITY"))
+                    } else if self.is_infinite() {
+                        String::from(concat!($class, ".NEGATIVE_INFINITY"))
+                    } else {
+                        format!("{}{}", self, $suffix)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer! {
+    i8 => "(byte) {}",
+    i16 => "(short) {}",
+    i32 => "{}",
+    i64 => "{}L",
+}
+
+float! {
+    f32 => "Float", "F",
+    f64 => "Double", "D",
+}
+
+/// Format an idiomatic numeric literal, like `4L` or `(byte) 4`.
+///
+/// This struct is created by the [lit][super::lit()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct Lit<T>(pub(super) T);
+
+impl<T> tokens::FormatInto<Java> for Lit<T>
+where
+    T: Numeric,
+{
+    fn format_into(self, tokens: &mut Tokens<Java>) {
+        tokens.append(self.0.format_literal());
+    }
+}
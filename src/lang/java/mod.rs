@@ -16,15 +16,20 @@
 //! ```
 
 mod block_comment;
+mod lit;
 
 pub use self::block_comment::BlockComment;
+pub use self::lit::{Lit, Numeric};
 
 use crate as genco;
 use crate::fmt;
 use crate::tokens::ItemStr;
 use crate::{quote, quote_in};
-use std::collections::{BTreeSet, HashMap};
-use std::fmt::Write as _;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::string::ToString;
+use core::fmt::Write as _;
+use core::iter;
 
 /// Tokens container specialized for Java.
 pub type Tokens = crate::Tokens<Java>;
@@ -38,7 +43,7 @@ impl_lang! {
 
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             // From: https://docs.oracle.com/javase/tutorial/java/data/characters.html
-            use std::fmt::Write as _;
+            use core::fmt::Write as _;
 
             for c in input.chars() {
                 match c {
@@ -77,7 +82,7 @@ impl_lang! {
 
             let mut format = Format::default();
             Self::imports(&mut header, tokens, config, &mut format.imported);
-            header.format(out, config, &format)?;
+            out.preamble(|out| header.format(out, config, &format))?;
             tokens.format(out, config, &format)?;
             Ok(())
         }
@@ -97,17 +102,81 @@ impl_lang! {
             out.write_str(&self.name)?;
             Ok(())
         }
+
+        fn reserved_name(&self) -> Option<&str> {
+            crate::lang::find_reserved(KEYWORDS, iter::once(&*self.name))
+        }
     }
 }
 
 const JAVA_LANG: &str = "java.lang";
 const SEP: &str = ".";
 
+/// Reserved words in Java, which can't be used as identifiers.
+///
+/// See <https://docs.oracle.com/javase/specs/jls/se17/html/jls-3.html#jls-3.9>.
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "true",
+    "false",
+    "null",
+    "_",
+];
+
 /// Formtat state for Java.
 #[derive(Debug, Default)]
 pub struct Format {
     /// Types which has been imported into the local namespace.
-    imported: HashMap<String, String>,
+    imported: BTreeMap<String, String>,
 }
 
 /// Configuration for Java.
@@ -120,6 +189,10 @@ pub struct Config {
 impl Config {
     /// Configure package to use for the file generated.
     ///
+    /// The `package` declaration is emitted before any imports. Without a
+    /// configured package the declaration is omitted, placing the file in the
+    /// default package.
+    ///
     /// # Examples
     ///
     /// ```
@@ -145,6 +218,37 @@ impl Config {
     ///     ],
     ///     w.into_vec(),
     /// );
+    ///
+    /// let list = java::import("java.util", "List");
+    /// let toks = quote!($list);
+    ///
+    /// let config = java::Config::default().with_package("com.example");
+    /// let mut w = fmt::VecWriter::new();
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "package com.example;",
+    ///         "",
+    ///         "import java.util.List;",
+    ///         "",
+    ///         "List",
+    ///     ],
+    ///     w.into_vec(),
+    /// );
+    ///
+    /// let config = java::Config::default();
+    /// let mut w = fmt::VecWriter::new();
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import java.util.List;",
+    ///         "",
+    ///         "List",
+    ///     ],
+    ///     w.into_vec(),
+    /// );
     /// # Ok::<_, genco::fmt::Error>(())
     /// ```
     pub fn with_package<P>(self, package: P) -> Self
@@ -173,13 +277,13 @@ impl Java {
         out: &mut Tokens,
         tokens: &Tokens,
         config: &Config,
-        imported: &mut HashMap<String, String>,
+        imported: &mut BTreeMap<String, String>,
     ) {
         let mut modules = BTreeSet::new();
 
         let file_package = config.package.as_ref().map(|p| p.as_ref());
 
-        for import in tokens.walk_imports() {
+        for import in tokens.walk_imports_dedup() {
             modules.insert((import.package.clone(), import.name.clone()));
         }
 
@@ -282,3 +386,42 @@ where
 {
     BlockComment(comment)
 }
+
+/// Format a numeric literal the way it's idiomatically written in Java.
+///
+/// Integers which would otherwise be inferred as `int` are cast or suffixed,
+/// so `4i8` is written as `(byte) 4` and `4i64` as `4L`. Non-finite floating
+/// point values are written as the corresponding constants, like
+/// `Double.NaN`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: java::Tokens = quote! {
+///     byte a = $(java::lit(4i8));
+///     int b = $(java::lit(4i32));
+///     long c = $(java::lit(4i64));
+///     float d = $(java::lit(1.5f32));
+///     double e = $(java::lit(f64::NEG_INFINITY));
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "byte a = (byte) 4;",
+///         "int b = 4;",
+///         "long c = 4L;",
+///         "float d = 1.5F;",
+///         "double e = Double.NEGATIVE_INFINITY;",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn lit<T>(value: T) -> Lit<T>
+where
+    T: Numeric,
+{
+    Lit(value)
+}
@@ -51,9 +51,63 @@
 
 use crate::fmt;
 use crate::tokens::ItemStr;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use core::fmt::Write as _;
+use core::iter;
 use relative_path::{RelativePath, RelativePathBuf};
-use std::collections::{BTreeMap, BTreeSet};
-use std::fmt::Write as _;
+
+/// Reserved words in JavaScript, which can't be used as identifiers.
+///
+/// See <https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Lexical_grammar#reserved_words>.
+const KEYWORDS: &[&str] = &[
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
 
 /// Tokens container specialization for Rust.
 pub type Tokens = crate::Tokens<JavaScript>;
@@ -74,7 +128,7 @@ impl_lang! {
             _format: &Self::Format,
             has_eval: bool,
         ) -> fmt::Result {
-            use std::fmt::Write as _;
+            use core::fmt::Write as _;
 
             if has_eval {
                 out.write_char('`')?;
@@ -92,7 +146,7 @@ impl_lang! {
             _format: &Self::Format,
             has_eval: bool,
         ) -> fmt::Result {
-            use std::fmt::Write as _;
+            use core::fmt::Write as _;
 
             if has_eval {
                 out.write_char('`')?;
@@ -165,7 +219,13 @@ impl_lang! {
             let mut imports = Tokens::new();
             Self::imports(&mut imports, tokens, config);
             let format = Format::default();
-            imports.format(out, config, &format)?;
+            out.preamble(|out| imports.format(out, config, &format))?;
+
+            #[cfg(feature = "locations")]
+            if config.source_map {
+                out.enable_source_map();
+            }
+
             tokens.format(out, config, &format)?;
             Ok(())
         }
@@ -180,6 +240,13 @@ impl_lang! {
 
             out.write_str(name)
         }
+
+        fn reserved_name(&self) -> Option<&str> {
+            crate::lang::find_reserved(
+                KEYWORDS,
+                iter::once(&*self.name).chain(self.alias.as_deref()),
+            )
+        }
     }
 }
 
@@ -191,6 +258,9 @@ pub struct Format {}
 #[derive(Debug, Default)]
 pub struct Config {
     module_path: Option<RelativePathBuf>,
+    /// Whether to collect a source map while formatting.
+    #[cfg(feature = "locations")]
+    source_map: bool,
 }
 
 impl Config {
@@ -237,13 +307,56 @@ impl Config {
     /// );
     /// # Ok::<_, genco::fmt::Error>(())
     /// ```
-    pub fn with_module_path<M>(self, module_path: M) -> Self
+    pub fn with_module_path<M>(mut self, module_path: M) -> Self
     where
         M: Into<RelativePathBuf>,
     {
-        Self {
-            module_path: Some(module_path.into()),
-        }
+        self.module_path = Some(module_path.into());
+        self
+    }
+
+    /// Configure whether a source map should be collected while formatting a
+    /// file.
+    ///
+    /// The source map maps positions in the formatted file back to the
+    /// locations in Rust source where the tokens were produced, and is
+    /// accessible through [Formatter::source_map] once formatting is done.
+    /// Imports generated by the file preamble are not mapped.
+    ///
+    /// This requires the `locations` feature to be enabled.
+    ///
+    /// [Formatter::source_map]: crate::fmt::Formatter::source_map
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let toks: js::Tokens = quote! {
+    ///     function foo() {
+    ///         return 42;
+    ///     }
+    /// };
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    ///
+    /// let config = js::Config::default().with_source_map(true);
+    /// let fmt = fmt::Config::from_lang::<JavaScript>();
+    /// let mut formatter = w.as_formatter(&fmt);
+    ///
+    /// toks.format_file(&mut formatter, &config)?;
+    ///
+    /// let source_map = formatter.source_map().expect("source map enabled");
+    /// assert_eq!(vec![file!()], source_map.sources());
+    ///
+    /// let lines = source_map.mappings().iter().map(|m| m.line()).collect::<Vec<_>>();
+    /// assert_eq!(vec![0, 0, 0, 1, 1, 2], lines);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    #[cfg(feature = "locations")]
+    pub fn with_source_map(self, source_map: bool) -> Self {
+        Self { source_map, ..self }
     }
 }
 
@@ -420,7 +533,7 @@ impl JavaScript {
         let mut modules = BTreeMap::<&Module, ResolvedModule<'_>>::new();
         let mut wildcards = BTreeSet::new();
 
-        for import in tokens.walk_imports() {
+        for import in tokens.walk_imports_dedup() {
             match import.kind {
                 ImportKind::Named => {
                     let module = modules.entry(&import.module).or_default();
@@ -19,9 +19,11 @@
 
 pub mod c;
 pub mod csharp;
+#[cfg(feature = "std")]
 pub mod dart;
 pub mod go;
 pub mod java;
+#[cfg(feature = "std")]
 pub mod js;
 pub mod nix;
 pub mod python;
@@ -30,9 +32,11 @@ pub mod swift;
 
 pub use self::c::C;
 pub use self::csharp::Csharp;
+#[cfg(feature = "std")]
 pub use self::dart::Dart;
 pub use self::go::Go;
 pub use self::java::Java;
+#[cfg(feature = "std")]
 pub use self::js::JavaScript;
 pub use self::nix::Nix;
 pub use self::python::Python;
@@ -48,7 +52,7 @@ use crate::Tokens;
 /// module.
 pub trait Lang
 where
-    Self: 'static + Sized + Copy + Eq + Ord + std::hash::Hash + std::fmt::Debug,
+    Self: 'static + Sized + Copy + Eq + Ord + core::hash::Hash + core::fmt::Debug,
 {
     /// Configuration associated with building a formatting element.
     type Config;
@@ -57,11 +61,50 @@ where
     /// The type used when resolving imports.
     type Item: LangItem<Self>;
 
-    /// Provide the default indentation.
+    /// Provide the idiomatic indentation for the language, which is used by
+    /// [fmt::Config::from_lang].
+    ///
+    /// This defaults to four spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt::Indentation;
+    /// use genco::lang::Lang;
+    /// use genco::prelude::*;
+    ///
+    /// assert_eq!(Indentation::Tab, Go::default_indentation());
+    /// assert_eq!(Indentation::Space(4), Python::default_indentation());
+    /// ```
     fn default_indentation() -> fmt::Indentation {
         fmt::Indentation::Space(4)
     }
 
+    /// The prefix used for line comments, like `//`.
+    ///
+    /// This is used by [comment()][crate::tokens::comment()].
+    fn line_comment() -> &'static str {
+        "//"
+    }
+
+    /// The literal used for a null value, like `null` or `None`.
+    ///
+    /// This is used by [null()][crate::tokens::null()].
+    fn null_literal() -> &'static str {
+        "null"
+    }
+
+    /// The literal used for a boolean value, like `true` or `True`.
+    ///
+    /// This is used by [boolean()][crate::tokens::boolean()].
+    fn bool_literal(value: bool) -> &'static str {
+        if value {
+            "true"
+        } else {
+            "false"
+        }
+    }
+
     /// Start a string quote.
     fn open_quote(
         out: &mut fmt::Formatter<'_>,
@@ -69,7 +112,7 @@ where
         _format: &Self::Format,
         _has_eval: bool,
     ) -> fmt::Result {
-        use std::fmt::Write as _;
+        use core::fmt::Write as _;
         out.write_char('"')?;
         Ok(())
     }
@@ -81,7 +124,7 @@ where
         _format: &Self::Format,
         _has_eval: bool,
     ) -> fmt::Result {
-        use std::fmt::Write as _;
+        use core::fmt::Write as _;
         out.write_char('"')?;
         Ok(())
     }
@@ -93,7 +136,7 @@ where
         format: &Self::Format,
         literal: &str,
     ) -> fmt::Result {
-        use std::fmt::Write as _;
+        use core::fmt::Write as _;
 
         Self::start_string_eval(out, config, format)?;
         out.write_str(literal)?;
@@ -121,12 +164,49 @@ where
 
     /// Performing string quoting according to language convention.
     fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
-        use std::fmt::Write as _;
+        use core::fmt::Write as _;
 
         out.write_str(input)
     }
 
+    /// Performing string quoting for a string which contains interpolated
+    /// values, like `$[str](Hello $(name))`.
+    ///
+    /// This is only used for literal parts of the string, and defaults to
+    /// [write_quoted][Lang::write_quoted].
+    fn write_eval_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+        Self::write_quoted(out, input)
+    }
+
+    /// Write the lines which have to come first in a file, like a shebang
+    /// or build constraints.
+    ///
+    /// This is called by [Tokens::format_file][crate::Tokens::format_file]
+    /// before [format_file][Self::format_file], and determines where the
+    /// [header][fmt::Config::with_header] of the file is written according
+    /// to its [placement][fmt::HeaderPlacement].
+    fn format_file_directives(
+        _out: &mut fmt::Formatter<'_>,
+        _config: &Self::Config,
+    ) -> fmt::Result {
+        Ok(())
+    }
+
+    /// Get the interpreter directive which the language configuration
+    /// requires to be written as the first line of files, like
+    /// [python::Config::with_shebang][crate::lang::python::Config::with_shebang].
+    ///
+    /// A shebang set through [fmt::Config::with_shebang] takes precedence,
+    /// in which case this isn't written.
+    fn shebang(_config: &Self::Config) -> Option<&str> {
+        None
+    }
+
     /// Write a file according to the specified language convention.
+    ///
+    /// Anything written other than the token stream itself, like imports,
+    /// should be written through [Formatter::preamble][fmt::Formatter::preamble]
+    /// so that it's told apart from the token stream.
     fn format_file(
         tokens: &Tokens<Self>,
         out: &mut fmt::Formatter<'_>,
@@ -143,6 +223,13 @@ where
 /// [quoted string interpolation]: https://docs.rs/genco/0/genco/macro.quote.html#quoted-string-interpolation
 pub trait LangSupportsEval: Lang {}
 
+/// Trait implemented by languages which know how to render a
+/// [Function][crate::tokens::Function].
+pub trait LangFunction: Lang {
+    /// Format the given function into the token stream.
+    fn format_function(function: &crate::tokens::Function<Self>, tokens: &mut Tokens<Self>);
+}
+
 /// Dummy implementation for a language.
 impl Lang for () {
     type Config = ();
@@ -166,7 +253,7 @@ where
 pub trait LangItem<L>
 where
     L: Lang,
-    Self: 'static + Clone + Eq + Ord + std::hash::Hash + std::fmt::Debug,
+    Self: 'static + Clone + Eq + Ord + core::hash::Hash + core::fmt::Debug,
 {
     /// Format the language item appropriately.
     fn format(
@@ -175,6 +262,39 @@ where
         config: &L::Config,
         format: &L::Format,
     ) -> fmt::Result;
+
+    /// Get a name introduced by this item which collides with a reserved word
+    /// in the language, if any.
+    ///
+    /// Items which escape reserved words when they are formatted should only
+    /// report the ones which can't be escaped. Rust imports are written as
+    /// raw identifiers like `r#type`, so only `crate`, `self`, `Self` and
+    /// `super` are reported for them.
+    ///
+    /// This is used by [Tokens::validate][crate::Tokens::validate].
+    fn reserved_name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get a token stream which is scoped inside of this item, like the body
+    /// of an inline module, whose imports are rendered by the item itself.
+    ///
+    /// Such streams are not visited by [Tokens::walk_imports], but are
+    /// visited by [Tokens::imports], [Tokens::has_import] and
+    /// [Tokens::validate].
+    fn scoped_tokens(&self) -> Option<&Tokens<L>> {
+        None
+    }
+}
+
+/// Find the first name which is in the given list of reserved words.
+///
+/// This is a helper for implementing [LangItem::reserved_name].
+pub(crate) fn find_reserved<'a, I>(keywords: &[&str], names: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    names.into_iter().find(|name| keywords.contains(name))
 }
 
 /// Escape the given string according to a C-family escape sequence.
@@ -184,7 +304,7 @@ where
 /// This is one of the more common escape sequences and is provided here so you
 /// can use it if a language you've implemented requires it.
 pub fn c_family_write_quoted(out: &mut fmt::Formatter, input: &str) -> fmt::Result {
-    use std::fmt::Write as _;
+    use core::fmt::Write as _;
 
     for c in input.chars() {
         match c {
@@ -3,8 +3,17 @@ use crate as genco;
 use crate::fmt;
 use crate::quote_in;
 use crate::tokens::ItemStr;
-use std::collections::BTreeSet;
-use std::fmt::Write as _;
+use alloc::collections::BTreeSet;
+use alloc::string::ToString;
+use core::fmt::Write as _;
+use core::iter;
+
+/// Reserved words in Nix, which can't be used as identifiers.
+///
+/// See <https://nix.dev/manual/nix/stable/language/syntax>.
+const KEYWORDS: &[&str] = &[
+    "assert", "else", "if", "in", "inherit", "let", "or", "rec", "then", "with",
+];
 
 /// Tokens
 pub type Tokens = crate::Tokens<Nix>;
@@ -16,6 +25,10 @@ impl_lang! {
         type Format = Format;
         type Item = Import;
 
+        fn line_comment() -> &'static str {
+            "#"
+        }
+
         fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
             super::c_family_write_quoted(out, input)
         }
@@ -33,7 +46,7 @@ impl_lang! {
             Self::withs(&mut header, tokens);
             Self::imports(&mut header, tokens);
             let format = Format::default();
-            header.format(out, config, &format)?;
+            out.preamble(|out| header.format(out, config, &format))?;
             tokens.format(out, config, &format)?;
             Ok(())
         }
@@ -49,6 +62,17 @@ impl_lang! {
             }
             Ok(())
         }
+
+        fn reserved_name(&self) -> Option<&str> {
+            let name = match self {
+                Import::Argument(import) => &import.0,
+                Import::Inherit(import) => &import.name,
+                Import::Variable(import) => &import.name,
+                Import::With(import) => &import.name,
+            };
+
+            crate::lang::find_reserved(KEYWORDS, iter::once(&**name))
+        }
     }
 }
 
@@ -117,7 +141,7 @@ impl Nix {
     fn arguments(out: &mut Tokens, tokens: &Tokens) {
         let mut arguments = BTreeSet::new();
 
-        for imports in tokens.walk_imports() {
+        for imports in tokens.walk_imports_dedup() {
             match imports {
                 Import::Argument(argument) => {
                     arguments.insert(argument.0.to_string());
@@ -130,7 +154,7 @@ impl Nix {
                 }
                 Import::Variable(variable) => {
                     let value = &variable.value;
-                    for import in value.walk_imports() {
+                    for import in value.walk_imports_dedup() {
                         match import {
                             Import::Inherit(inherit) => {
                                 let argument = inherit.path.split('.').next();
@@ -173,7 +197,7 @@ impl Nix {
     fn withs(out: &mut Tokens, tokens: &Tokens) {
         let mut withs = BTreeSet::new();
 
-        for imports in tokens.walk_imports() {
+        for imports in tokens.walk_imports_dedup() {
             if let Import::With(with) = imports {
                 withs.insert(&with.argument);
             }
@@ -195,14 +219,14 @@ impl Nix {
         let mut inherits = BTreeSet::new();
         let mut variables = BTreeSet::new();
 
-        for imports in tokens.walk_imports() {
+        for imports in tokens.walk_imports_dedup() {
             match imports {
                 Import::Inherit(inherit) => {
                     inherits.insert((&inherit.path, &inherit.name));
                 }
                 Import::Variable(variable) => {
                     let value = &variable.value;
-                    for import in value.walk_imports() {
+                    for import in value.walk_imports_dedup() {
                         if let Import::Inherit(inherit) = import {
                             inherits.insert((&inherit.path, &inherit.name));
                         }
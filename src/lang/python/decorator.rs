@@ -0,0 +1,117 @@
+use crate::lang::Python;
+use crate::tokens;
+use crate::Tokens;
+use alloc::vec::Vec;
+
+/// Format a decorator, like `@functools.lru_cache` or `@app.route("/")`.
+///
+/// This struct is created by the [decorator][super::decorator()] function.
+pub struct Decorator<T> {
+    pub(super) target: T,
+    pub(super) arguments: Option<Vec<Tokens<Python>>>,
+}
+
+impl<T> Decorator<T> {
+    /// Add an argument to the decorator call.
+    ///
+    /// Adding an argument causes the decorator to be rendered as a call, like
+    /// `@route("/")`. Strings should be wrapped in [quoted()] so that they are
+    /// quoted according to Python rules.
+    ///
+    /// [quoted()]: crate::tokens::quoted()
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks: python::Tokens = quote! {
+    ///     $(python::decorator("app.route").with_argument(quoted("/")))
+    ///     def hello():
+    ///         return "Hello World!"
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "@app.route(\"/\")",
+    ///         "def hello():",
+    ///         "    return \"Hello World!\"",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_argument<A>(mut self, argument: A) -> Self
+    where
+        A: tokens::FormatInto<Python>,
+    {
+        let mut tokens = Tokens::new();
+        tokens.append(argument);
+        self.arguments.get_or_insert_with(Vec::new).push(tokens);
+        self
+    }
+
+    /// Add a keyword argument to the decorator call, like `methods=["GET"]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let decorator = python::decorator("app.route")
+    ///     .with_argument(quoted("/x"))
+    ///     .with_keyword("methods", quote!([$(quoted("GET"))]));
+    ///
+    /// let toks: python::Tokens = quote!($decorator);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "@app.route(\"/x\", methods=[\"GET\"])",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_keyword<N, A>(mut self, name: N, argument: A) -> Self
+    where
+        N: Into<tokens::ItemStr>,
+        A: tokens::FormatInto<Python>,
+    {
+        let mut tokens = Tokens::new();
+        tokens.append(name.into());
+        tokens.append(tokens::static_literal("="));
+        tokens.append(argument);
+        self.arguments.get_or_insert_with(Vec::new).push(tokens);
+        self
+    }
+}
+
+impl<T> tokens::FormatInto<Python> for Decorator<T>
+where
+    T: tokens::FormatInto<Python>,
+{
+    fn format_into(self, tokens: &mut Tokens<Python>) {
+        tokens.push();
+        tokens.append(tokens::static_literal("@"));
+        tokens.append(self.target);
+
+        if let Some(arguments) = self.arguments {
+            tokens.append(tokens::static_literal("("));
+
+            let mut it = arguments.into_iter().peekable();
+
+            while let Some(argument) = it.next() {
+                tokens.append(argument);
+
+                if it.peek().is_some() {
+                    tokens.append(tokens::static_literal(","));
+                    tokens.space();
+                }
+            }
+
+            tokens.append(tokens::static_literal(")"));
+        }
+
+        tokens.push();
+    }
+}
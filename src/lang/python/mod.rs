@@ -0,0 +1,948 @@
+//! Specialization for Python code generation.
+//!
+//! # Examples
+//!
+//! String quoting in Python:
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: python::Tokens = quote!("hello \n world");
+//! assert_eq!("\"hello \\n world\"", toks.to_string()?);
+//!
+//! let toks: python::Tokens = quote!($(quoted("hello \n world")));
+//! assert_eq!("\"hello \\n world\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+
+mod decorator;
+
+pub use self::decorator::Decorator;
+
+use crate as genco;
+use crate::fmt;
+use crate::tokens::{static_literal, FormatInto, ItemStr};
+use crate::{quote, quote_in};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+use core::iter;
+
+/// Tokens container specialization for Python.
+pub type Tokens = crate::Tokens<Python>;
+
+impl_lang! {
+    /// Language specialization for Python.
+    pub Python {
+        type Config = Config;
+        type Format = Format;
+        type Item = Any;
+
+        fn line_comment() -> &'static str {
+            "#"
+        }
+
+        fn null_literal() -> &'static str {
+            "None"
+        }
+
+        fn bool_literal(value: bool) -> &'static str {
+            if value {
+                "True"
+            } else {
+                "False"
+            }
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://docs.python.org/3/reference/lexical_analysis.html#string-and-bytes-literals
+            super::c_family_write_quoted(out, input)
+        }
+
+        fn shebang(config: &Self::Config) -> Option<&str> {
+            config.shebang.as_deref()
+        }
+
+        fn format_file_directives(
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut directives = Tokens::new();
+            Self::directives(&mut directives, config);
+            directives.format(out, config, &Format::default())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut header = Tokens::new();
+            Self::header(&mut header, config);
+            let mut format = Format::default();
+            Self::imports(&mut header, tokens, config, &mut format);
+            out.preamble(|out| header.format(out, config, &format))?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, format: &Format) -> fmt::Result {
+            match &self.module {
+                TypeModule::Qualified { module, alias } => {
+                    out.write_str(alias.as_ref().unwrap_or(module))?;
+                    out.write_str(SEP)?;
+                }
+                TypeModule::Unqualified { module } => {
+                    let key = (module.clone(), self.name.clone(), self.alias.clone());
+
+                    match format.resolved.get(&key) {
+                        Some(Resolved::Qualified) => {
+                            out.write_str(module)?;
+                            out.write_str(SEP)?;
+                            out.write_str(&self.name)?;
+                            return Ok(());
+                        }
+                        Some(Resolved::Aliased(alias)) => {
+                            out.write_str(alias)?;
+                            return Ok(());
+                        }
+                        None => {}
+                    }
+                }
+            }
+
+            let name = match &self.alias {
+                Some(alias) => alias,
+                None => &self.name,
+            };
+
+            out.write_str(name)?;
+            Ok(())
+        }
+
+        fn reserved_name(&self) -> Option<&str> {
+            crate::lang::find_reserved(
+                KEYWORDS,
+                iter::once(&*self.name).chain(self.alias.as_deref()),
+            )
+        }
+    }
+
+    ImportModule {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            let module = match &self.alias {
+                Some(alias) => alias,
+                None => &self.module,
+            };
+
+            out.write_str(module)?;
+            Ok(())
+        }
+
+        fn reserved_name(&self) -> Option<&str> {
+            crate::lang::find_reserved(
+                KEYWORDS,
+                self.module.split(SEP).chain(self.alias.as_deref()),
+            )
+        }
+    }
+}
+
+/// Formatting state for python.
+#[derive(Debug, Default)]
+pub struct Format {
+    /// Imports which had their binding changed to resolve a name conflict,
+    /// keyed by module, name, and alias.
+    resolved: BTreeMap<(ItemStr, ItemStr, Option<ItemStr>), Resolved>,
+}
+
+/// How a conflicting import was resolved.
+#[derive(Debug)]
+enum Resolved {
+    /// The module is imported and the name is referenced through it.
+    Qualified,
+    /// The name is imported under the given alias.
+    Aliased(ItemStr),
+}
+
+/// Configuration for python.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// How to resolve names which are imported from multiple modules.
+    conflict_mode: ConflictMode,
+    /// Shebang line to emit first in the file.
+    shebang: Option<ItemStr>,
+    /// Whether to emit a coding declaration.
+    coding: bool,
+    /// Docstring of the module.
+    module_docstring: Option<ItemStr>,
+}
+
+impl Config {
+    /// Configure how to resolve names which are imported from multiple
+    /// modules.
+    ///
+    /// See [ConflictMode] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let a = python::import("a", "Client");
+    /// let b = python::import("b", "Client");
+    ///
+    /// let toks: python::Tokens = quote! {
+    ///     $a()
+    ///     $b()
+    /// };
+    ///
+    /// let config = python::Config::default().with_conflict_mode(python::ConflictMode::Aliased);
+    /// let fmt = fmt::Config::from_lang::<Python>();
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "from a import Client",
+    ///         "from b import Client as b_Client",
+    ///         "",
+    ///         "Client()",
+    ///         "b_Client()",
+    ///     ],
+    ///     w.into_vec(),
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_conflict_mode(self, conflict_mode: ConflictMode) -> Self {
+        Self {
+            conflict_mode,
+            ..self
+        }
+    }
+
+    /// Configure a shebang line, like `#!/usr/bin/env python3`, to emit as
+    /// the first line of the file.
+    ///
+    /// A shebang set through [fmt::Config::with_shebang] takes precedence,
+    /// in which case this isn't written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let os = python::import_module("os");
+    ///
+    /// let toks: python::Tokens = quote! {
+    ///     print($os.getcwd())
+    /// };
+    ///
+    /// let config = python::Config::default().with_shebang("#!/usr/bin/env python3");
+    /// let fmt = fmt::Config::from_lang::<Python>();
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "#!/usr/bin/env python3",
+    ///         "",
+    ///         "import os",
+    ///         "",
+    ///         "print(os.getcwd())",
+    ///     ],
+    ///     w.into_vec(),
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_shebang<S>(self, shebang: S) -> Self
+    where
+        S: Into<ItemStr>,
+    {
+        Self {
+            shebang: Some(shebang.into()),
+            ..self
+        }
+    }
+
+    /// Configure whether to emit a `# -*- coding: utf-8 -*-` declaration
+    /// after the shebang.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let toks: python::Tokens = quote!(pass);
+    ///
+    /// let config = python::Config::default()
+    ///     .with_coding(true)
+    ///     .with_module_docstring("Does nothing.");
+    ///
+    /// let fmt = fmt::Config::from_lang::<Python>();
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "# -*- coding: utf-8 -*-",
+    ///         "\"\"\"Does nothing.\"\"\"",
+    ///         "",
+    ///         "pass",
+    ///     ],
+    ///     w.into_vec(),
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_coding(self, coding: bool) -> Self {
+        Self { coding, ..self }
+    }
+
+    /// Configure a docstring to emit at the top of the module, before any
+    /// imports.
+    ///
+    /// Backslashes and double quotes in the docstring are escaped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let annotations = python::import("__future__", "annotations");
+    /// let os = python::import_module("os");
+    ///
+    /// let toks: python::Tokens = quote! {
+    ///     $(register(annotations))
+    ///     print($os.getcwd())
+    /// };
+    ///
+    /// let config = python::Config::default()
+    ///     .with_shebang("#!/usr/bin/env python3")
+    ///     .with_coding(true)
+    ///     .with_module_docstring("Print the current directory.\n\nUsed for testing.");
+    ///
+    /// let fmt = fmt::Config::from_lang::<Python>();
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "#!/usr/bin/env python3",
+    ///         "# -*- coding: utf-8 -*-",
+    ///         "\"\"\"Print the current directory.",
+    ///         "",
+    ///         "Used for testing.",
+    ///         "\"\"\"",
+    ///         "",
+    ///         "from __future__ import annotations",
+    ///         "",
+    ///         "import os",
+    ///         "",
+    ///         "print(os.getcwd())",
+    ///     ],
+    ///     w.into_vec(),
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_module_docstring<S>(self, module_docstring: S) -> Self
+    where
+        S: Into<ItemStr>,
+    {
+        Self {
+            module_docstring: Some(module_docstring.into()),
+            ..self
+        }
+    }
+}
+
+/// How to resolve a name which is imported from more than one module, like
+/// `from a import Client` and `from b import Client`.
+///
+/// Imports are processed in module order, and the first module to bind a name
+/// keeps it. Names bound by module imports, like `import b`, always take
+/// precedence over names imported from modules.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let a = python::import("a", "Client");
+/// let b = python::import("b", "Client");
+///
+/// let toks: python::Tokens = quote! {
+///     $a()
+///     $b()
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "from a import Client",
+///         "import b",
+///         "",
+///         "Client()",
+///         "b.Client()",
+///     ],
+///     toks.to_file_vec()?,
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConflictMode {
+    /// Import the module of any later conflicting names and reference them
+    /// through it, like `import b` and `b.Client`.
+    ///
+    /// If the name of the module is already bound to something else, the
+    /// name is aliased like with [ConflictMode::Aliased].
+    #[default]
+    Qualified,
+    /// Import later conflicting names under an alias derived from their
+    /// module, like `from b import Client as b_Client`.
+    Aliased,
+}
+
+static SEP: &str = ".";
+static FUTURE: &str = "__future__";
+static DOCSTRING: &str = "\"\"\"";
+
+/// Reserved words in Python, which can't be used as identifiers.
+///
+/// See <https://docs.python.org/3/reference/lexical_analysis.html#keywords>.
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+enum TypeModule {
+    Unqualified {
+        /// Name of imported module.
+        module: ItemStr,
+    },
+    Qualified {
+        /// Name of imported module.
+        module: ItemStr,
+        /// Alias of imported module.
+        alias: Option<ItemStr>,
+    },
+}
+
+impl TypeModule {
+    fn qualified(self) -> Self {
+        match self {
+            Self::Unqualified { module } => Self::Qualified {
+                module,
+                alias: None,
+            },
+            other => other,
+        }
+    }
+
+    fn with_alias<T>(self, alias: T) -> Self
+    where
+        T: Into<ItemStr>,
+    {
+        match self {
+            Self::Qualified { module, .. } | Self::Unqualified { module } => Self::Qualified {
+                module,
+                alias: Some(alias.into()),
+            },
+        }
+    }
+}
+
+/// The import of a Python name `from module import foo`.
+///
+/// Created through the [import()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+pub struct Import {
+    /// Module of the imported name.
+    module: TypeModule,
+    /// The name that was imported.
+    name: ItemStr,
+    /// Alias of the name imported.
+    alias: Option<ItemStr>,
+}
+
+impl Import {
+    /// Configure the importe name with the specified alias.
+    ///
+    /// This implised that the import is not qualified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks = quote! {
+    ///     $(python::import("collections", "namedtuple").with_alias("nt"))
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "from collections import namedtuple as nt",
+    ///         "",
+    ///         "nt",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_alias<T>(self, alias: T) -> Self
+    where
+        T: Into<ItemStr>,
+    {
+        Self {
+            alias: Some(alias.into()),
+            ..self
+        }
+    }
+
+    /// Indicate that the import is qualified (module prefixed).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks = quote! {
+    ///     $(python::import("collections", "namedtuple").qualified())
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import collections",
+    ///         "",
+    ///         "collections.namedtuple",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn qualified(self) -> Self {
+        Self {
+            module: self.module.qualified(),
+            ..self
+        }
+    }
+
+    /// Configure the imported name with the specified alias.
+    ///
+    /// This implies that the import is qualified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks = quote! {
+    ///     $(python::import("collections", "namedtuple").with_module_alias("c"))
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import collections as c",
+    ///         "",
+    ///         "c.namedtuple",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_module_alias<T>(self, module_alias: T) -> Self
+    where
+        T: Into<ItemStr>,
+    {
+        Self {
+            module: self.module.with_alias(module_alias),
+            ..self
+        }
+    }
+}
+
+/// The import of a Python module `import module`.
+///
+/// Created through the [import_module()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+pub struct ImportModule {
+    /// Module of the imported name.
+    module: ItemStr,
+
+    /// Alias of module imported.
+    alias: Option<ItemStr>,
+}
+
+impl ImportModule {
+    /// Set alias for imported module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks = quote! {
+    ///     $(python::import_module("collections").with_alias("c"))
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import collections as c",
+    ///         "",
+    ///         "c",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_alias<N>(self, new_alias: N) -> Self
+    where
+        N: Into<ItemStr>,
+    {
+        Self {
+            alias: Some(new_alias.into()),
+            ..self
+        }
+    }
+}
+
+impl Python {
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config, format: &mut Format) {
+        let mut unresolved = BTreeMap::new();
+        let mut imports = BTreeSet::new();
+
+        for import in tokens.walk_imports_dedup() {
+            match import {
+                Any::Import(Import {
+                    module,
+                    alias,
+                    name,
+                }) => match module {
+                    TypeModule::Qualified { module, alias } => {
+                        imports.insert((module, alias));
+                    }
+                    TypeModule::Unqualified { module } => {
+                        unresolved
+                            .entry(module)
+                            .or_insert_with(BTreeSet::new)
+                            .insert((name, alias));
+                    }
+                },
+                Any::ImportModule(ImportModule { module, alias }) => {
+                    imports.insert((module, alias));
+                }
+            }
+        }
+
+        let mut imported_from = BTreeMap::new();
+        let mut bound = BTreeSet::new();
+        // Top-level packages bound by module imports without an alias, which
+        // any number of `import <package>.<module>` can share.
+        let mut packages = BTreeSet::new();
+
+        // NB: module imports can't be renamed, so they bind their names
+        // first.
+        for (module, alias) in &imports {
+            match alias {
+                Some(alias) => {
+                    bound.insert(alias.to_string());
+                }
+                None => {
+                    let package = Self::package(module);
+                    bound.insert(package.to_string());
+                    packages.insert(package.to_string());
+                }
+            }
+        }
+
+        for (module, names) in unresolved {
+            for (name, alias) in names {
+                let binding = alias.as_ref().unwrap_or(name);
+
+                if bound.insert(binding.to_string()) {
+                    imported_from
+                        .entry(module.clone())
+                        .or_insert_with(BTreeSet::new)
+                        .insert((name.clone(), alias.clone()));
+                    continue;
+                }
+
+                let key = (module.clone(), name.clone(), alias.clone());
+
+                if let ConflictMode::Qualified = config.conflict_mode {
+                    let package = Self::package(module);
+
+                    if packages.contains(package) || bound.insert(package.to_string()) {
+                        packages.insert(package.to_string());
+                        imports.insert((module, &None));
+                        format.resolved.insert(key, Resolved::Qualified);
+                        continue;
+                    }
+                }
+
+                // NB: also used when the module of a qualified import is
+                // bound to something else.
+                let base = format!("{}_{}", module.replace('.', "_"), name);
+                let mut alias = base.clone();
+                let mut n = 2;
+
+                while bound.contains(&alias) {
+                    alias = format!("{}{}", base, n);
+                    n += 1;
+                }
+
+                bound.insert(alias.clone());
+                let alias = ItemStr::from(alias);
+
+                imported_from
+                    .entry(module.clone())
+                    .or_insert_with(BTreeSet::new)
+                    .insert((name.clone(), Some(alias.clone())));
+                format.resolved.insert(key, Resolved::Aliased(alias));
+            }
+        }
+
+        if imported_from.is_empty() && imports.is_empty() {
+            return;
+        }
+
+        let (futures, imported_from): (Vec<_>, Vec<_>) = imported_from
+            .into_iter()
+            .partition(|(module, _)| &**module == FUTURE);
+
+        if !futures.is_empty() {
+            for (module, imports) in futures {
+                Self::import_from(out, module, imports);
+            }
+
+            out.line();
+        }
+
+        for (module, imports) in imported_from {
+            Self::import_from(out, module, imports);
+        }
+
+        for (module, alias) in imports {
+            out.push();
+
+            quote_in! {*out =>
+                import $module$(if let Some(a) = alias => $[' ']as $a)
+            }
+        }
+
+        out.line();
+    }
+
+    /// The top-level package bound by `import <module>`.
+    fn package(module: &str) -> &str {
+        module.split(SEP).next().unwrap_or(module)
+    }
+
+    fn import_from(
+        out: &mut Tokens,
+        module: ItemStr,
+        imports: BTreeSet<(ItemStr, Option<ItemStr>)>,
+    ) {
+        out.push();
+
+        let imports = imports
+            .into_iter()
+            .map(|(name, alias)| quote!($name$(if let Some(a) = alias => $[' ']as $a)))
+            .collect::<Vec<_>>();
+
+        if imports.len() == 1 {
+            quote_in! {*out =>
+                from $module import $(imports.into_iter().next())
+            }
+        } else {
+            quote_in! {*out =>
+                from $module import $(for i in imports join (, ) => $i)
+            }
+        }
+    }
+
+    /// Lines which have to come first in the file after the shebang, which is
+    /// the coding declaration.
+    fn directives(out: &mut Tokens, config: &Config) {
+        if config.coding {
+            out.push();
+            out.append(static_literal("# -*- coding: utf-8 -*-"));
+        }
+    }
+
+    fn header(out: &mut Tokens, config: &Config) {
+        if let Some(docstring) = &config.module_docstring {
+            out.push();
+            out.append(static_literal(DOCSTRING));
+
+            for (n, line) in docstring.lines().enumerate() {
+                if line.is_empty() {
+                    // NB: A leading empty line only breaks the line after
+                    // the opening quotes.
+                    if n == 0 {
+                        out.push();
+                    } else {
+                        out.line();
+                    }
+
+                    continue;
+                }
+
+                if n > 0 {
+                    out.push();
+                }
+
+                // NB: Every double quote is escaped, since a quote at the end
+                // of the docstring or a run of quotes would otherwise
+                // terminate it early.
+                out.append(line.replace('\\', "\\\\").replace('"', "\\\""));
+            }
+
+            if docstring.contains('\n') {
+                out.push();
+            }
+
+            out.append(static_literal(DOCSTRING));
+        }
+
+        out.line();
+    }
+}
+
+/// The import of a Python name `from module import foo`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks = quote! {
+///     $(python::import("collections", "namedtuple").with_alias("nt"))
+///     $(python::import("collections", "namedtuple"))
+///     $(python::import("collections", "namedtuple").qualified())
+///     $(python::import("collections", "namedtuple").with_module_alias("c"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "from collections import namedtuple, namedtuple as nt",
+///         "import collections",
+///         "import collections as c",
+///         "",
+///         "nt",
+///         "namedtuple",
+///         "collections.namedtuple",
+///         "c.namedtuple",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn import<M, N>(module: M, name: N) -> Import
+where
+    M: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        module: TypeModule::Unqualified {
+            module: module.into(),
+        },
+        name: name.into(),
+        alias: None,
+    }
+}
+
+/// The import of a Python module `import module`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks = quote! {
+///     $(python::import_module("collections"))
+///     $(python::import_module("collections").with_alias("c"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "import collections",
+///         "import collections as c",
+///         "",
+///         "collections",
+///         "c",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn import_module<M>(module: M) -> ImportModule
+where
+    M: Into<ItemStr>,
+{
+    ImportModule {
+        module: module.into(),
+        alias: None,
+    }
+}
+
+/// Format a decorator, like `@functools.lru_cache`.
+///
+/// The target can be anything implementing [FormatInto], including imports
+/// which will be added to the file as needed. Multiple decorators stacked on
+/// top of each other will each be rendered on their own line.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let lru_cache = python::import("functools", "lru_cache").qualified();
+/// let route = python::decorator("app.route")
+///     .with_argument(quoted("/x"))
+///     .with_keyword("methods", quote!([$(quoted("GET"))]));
+///
+/// let toks = quote! {
+///     $(python::decorator(lru_cache))
+///     $route
+///     def hello():
+///         return "Hello World!"
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "import functools",
+///         "",
+///         "@functools.lru_cache",
+///         "@app.route(\"/x\", methods=[\"GET\"])",
+///         "def hello():",
+///         "    return \"Hello World!\"",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn decorator<T>(target: T) -> Decorator<T>
+where
+    T: FormatInto<Python>,
+{
+    Decorator {
+        target,
+        arguments: None,
+    }
+}
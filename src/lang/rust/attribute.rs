@@ -0,0 +1,30 @@
+use crate::lang::Rust;
+use crate::tokens;
+use crate::Tokens;
+
+/// Format an attribute, like `#[derive(Debug)]` or `#[inline]`.
+///
+/// This struct is created by the [attr][super::attr()] and
+/// [attr_path][super::attr_path()] functions.
+pub struct Attribute<P> {
+    pub(super) path: P,
+    pub(super) inner: Option<Tokens<Rust>>,
+}
+
+impl<P> tokens::FormatInto<Rust> for Attribute<P>
+where
+    P: tokens::FormatInto<Rust>,
+{
+    fn format_into(self, tokens: &mut Tokens<Rust>) {
+        tokens.append(tokens::static_literal("#["));
+        tokens.append(self.path);
+
+        if let Some(inner) = self.inner {
+            tokens.append(tokens::static_literal("("));
+            tokens.append(inner);
+            tokens.append(tokens::static_literal(")"));
+        }
+
+        tokens.append(tokens::static_literal("]"));
+    }
+}
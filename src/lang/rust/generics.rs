@@ -0,0 +1,218 @@
+use crate::lang::Rust;
+use crate::tokens::{self, ItemStr};
+use crate::Tokens;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+
+/// A list of generic parameters, like `<'a, T: Debug, const N: usize>`.
+///
+/// Lifetimes are always rendered first, followed by type and const
+/// parameters in the order they were added. Type parameters with more than
+/// one bound are rendered without bounds in the parameter list, with their
+/// bounds moved to the [where clause][Generics::where_clause].
+///
+/// This struct is created by the [generics][super::generics()] function.
+#[derive(Debug, Clone, Default)]
+pub struct Generics {
+    pub(super) lifetimes: Vec<ItemStr>,
+    pub(super) params: Vec<Param>,
+}
+
+/// A single type or const parameter.
+#[derive(Debug, Clone)]
+pub(super) enum Param {
+    Type {
+        name: ItemStr,
+        bounds: Vec<Tokens<Rust>>,
+    },
+    Const {
+        name: ItemStr,
+        ty: Tokens<Rust>,
+    },
+}
+
+impl Generics {
+    /// Add a lifetime parameter, like `'a`.
+    ///
+    /// The leading `'` is optional.
+    pub fn with_lifetime<N>(mut self, name: N) -> Self
+    where
+        N: Into<ItemStr>,
+    {
+        let name = name.into();
+
+        let name = if name.starts_with('\'') {
+            name
+        } else {
+            ItemStr::from(format!("'{}", name))
+        };
+
+        self.lifetimes.push(name);
+        self
+    }
+
+    /// Add a type parameter without any bounds, like `T`.
+    pub fn with_type<N>(mut self, name: N) -> Self
+    where
+        N: Into<ItemStr>,
+    {
+        self.params.push(Param::Type {
+            name: name.into(),
+            bounds: Vec::new(),
+        });
+
+        self
+    }
+
+    /// Add a bound to the type parameter with the given name, like `Debug` in
+    /// `T: Debug`.
+    ///
+    /// The type parameter is added if it hasn't been already. Imports used in
+    /// the bound are registered when the parameter list is formatted.
+    pub fn with_bound<N, B>(mut self, name: N, bound: B) -> Self
+    where
+        N: Into<ItemStr>,
+        B: tokens::FormatInto<Rust>,
+    {
+        let name = name.into();
+
+        let mut tokens = Tokens::new();
+        tokens.append(bound);
+
+        let existing = self.params.iter_mut().find_map(|param| match param {
+            Param::Type { name: n, bounds } if **n == *name => Some(bounds),
+            _ => None,
+        });
+
+        match existing {
+            Some(bounds) => bounds.push(tokens),
+            None => self.params.push(Param::Type {
+                name,
+                bounds: vec![tokens],
+            }),
+        }
+
+        self
+    }
+
+    /// Add a const parameter, like `const N: usize`.
+    pub fn with_const<N, T>(mut self, name: N, ty: T) -> Self
+    where
+        N: Into<ItemStr>,
+        T: tokens::FormatInto<Rust>,
+    {
+        let mut tokens = Tokens::new();
+        tokens.append(ty);
+
+        self.params.push(Param::Const {
+            name: name.into(),
+            ty: tokens,
+        });
+
+        self
+    }
+
+    /// Test if there are no generic parameters.
+    pub fn is_empty(&self) -> bool {
+        self.lifetimes.is_empty() && self.params.is_empty()
+    }
+
+    /// Construct the where clause for type parameters with more than one
+    /// bound, like `where T: Debug + Clone`.
+    ///
+    /// This is empty if no such parameters exist.
+    pub fn where_clause(&self) -> Tokens<Rust> {
+        let mut tokens = Tokens::new();
+
+        let complex = self.params.iter().filter_map(|param| match param {
+            Param::Type { name, bounds } if bounds.len() > 1 => Some((name, bounds)),
+            _ => None,
+        });
+
+        for (n, (name, bounds)) in complex.enumerate() {
+            if n == 0 {
+                tokens.append(tokens::static_literal("where"));
+            } else {
+                tokens.append(tokens::static_literal(","));
+            }
+
+            tokens.space();
+            tokens.append(name);
+            tokens.append(tokens::static_literal(":"));
+            tokens.space();
+            format_bounds(&mut tokens, bounds);
+        }
+
+        tokens
+    }
+}
+
+fn format_bounds(tokens: &mut Tokens<Rust>, bounds: &[Tokens<Rust>]) {
+    for (n, bound) in bounds.iter().enumerate() {
+        if n > 0 {
+            tokens.space();
+            tokens.append(tokens::static_literal("+"));
+            tokens.space();
+        }
+
+        tokens.append(bound);
+    }
+}
+
+impl tokens::FormatInto<Rust> for Generics {
+    fn format_into(self, tokens: &mut Tokens<Rust>) {
+        tokens.append(&self);
+    }
+}
+
+impl tokens::FormatInto<Rust> for &Generics {
+    fn format_into(self, tokens: &mut Tokens<Rust>) {
+        if self.is_empty() {
+            return;
+        }
+
+        tokens.append(tokens::static_literal("<"));
+
+        let mut first = true;
+
+        let mut separator = |tokens: &mut Tokens<Rust>| {
+            if !mem::take(&mut first) {
+                tokens.append(tokens::static_literal(","));
+                tokens.space();
+            }
+        };
+
+        for lifetime in &self.lifetimes {
+            separator(tokens);
+            tokens.append(lifetime);
+        }
+
+        for param in &self.params {
+            separator(tokens);
+
+            match param {
+                Param::Type { name, bounds } => {
+                    tokens.append(name);
+
+                    if let [bound] = &bounds[..] {
+                        tokens.append(tokens::static_literal(":"));
+                        tokens.space();
+                        tokens.append(bound);
+                    }
+                }
+                Param::Const { name, ty } => {
+                    tokens.append(tokens::static_literal("const"));
+                    tokens.space();
+                    tokens.append(name);
+                    tokens.append(tokens::static_literal(":"));
+                    tokens.space();
+                    tokens.append(ty);
+                }
+            }
+        }
+
+        tokens.append(tokens::static_literal(">"));
+    }
+}
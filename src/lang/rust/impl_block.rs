@@ -0,0 +1,57 @@
+use crate::lang::Rust;
+use crate::tokens::{self, angled, block};
+use crate::Tokens;
+
+/// An impl block, like `impl Trait for Type { ... }`.
+///
+/// This struct is created by the [impl_block][super::impl_block()] function.
+pub struct ImplBlock<T, B> {
+    pub(super) trait_: Option<Tokens<Rust>>,
+    pub(super) ty: T,
+    pub(super) body: B,
+    pub(super) generics: Option<Tokens<Rust>>,
+}
+
+impl<T, B> ImplBlock<T, B> {
+    /// Set the generic parameters of the impl block, like `T: Clone` in
+    /// `impl<T: Clone> Type<T> { ... }`.
+    pub fn with_generics<G>(self, generics: G) -> Self
+    where
+        G: tokens::FormatInto<Rust>,
+    {
+        let mut tokens = Tokens::new();
+        tokens.append(generics);
+
+        Self {
+            generics: Some(tokens),
+            ..self
+        }
+    }
+}
+
+impl<T, B> tokens::FormatInto<Rust> for ImplBlock<T, B>
+where
+    T: tokens::FormatInto<Rust>,
+    B: tokens::FormatInto<Rust>,
+{
+    fn format_into(self, tokens: &mut Tokens<Rust>) {
+        tokens.append(tokens::static_literal("impl"));
+
+        if let Some(generics) = self.generics {
+            tokens.append(angled(generics));
+        }
+
+        tokens.space();
+
+        if let Some(trait_) = self.trait_ {
+            tokens.append(trait_);
+            tokens.space();
+            tokens.append(tokens::static_literal("for"));
+            tokens.space();
+        }
+
+        tokens.append(self.ty);
+        tokens.space();
+        tokens.append(block(self.body));
+    }
+}
@@ -0,0 +1,34 @@
+use crate::fmt;
+use crate::lang::rust::{Config, Format, Rust, Tokens};
+use crate::tokens::{block, ItemStr};
+
+/// An inline module, like `mod foo { ... }`.
+///
+/// Imports used in the body of the module are rendered inside of it rather
+/// than at the top of the file.
+///
+/// This struct is created by the [module][super::module()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+pub struct InlineModule {
+    pub(super) name: ItemStr,
+    pub(super) body: Tokens,
+}
+
+impl InlineModule {
+    pub(super) fn format(
+        &self,
+        out: &mut fmt::Formatter<'_>,
+        config: &Config,
+        format: &Format,
+    ) -> fmt::Result {
+        use crate as genco;
+        use crate::quote;
+
+        let mut body = Tokens::new();
+        Rust::imports(&mut body, config, &self.body);
+        body.append(&self.body);
+
+        let tokens: Tokens = quote!(mod $(&self.name) $(block(body)));
+        tokens.format(out, config, format)
+    }
+}
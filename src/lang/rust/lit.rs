@@ -0,0 +1,72 @@
+use crate::lang::Rust;
+use crate::tokens;
+use crate::Tokens;
+use alloc::format;
+use alloc::string::String;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A numeric type which can be formatted as a suffixed Rust literal.
+///
+/// This trait is sealed and is implemented for all primitive integer and
+/// floating point types.
+pub trait Numeric: sealed::Sealed {
+    #[doc(hidden)]
+    fn format_literal(&self) -> String;
+}
+
+macro_rules! integer {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl Numeric for $ty {
+                fn format_literal(&self) -> String {
+                    format!("{}{}", self, stringify!($ty))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! float {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl Numeric for $ty {
+                fn format_literal(&self) -> String {
+                    if self.is_nan() {
+                        String::from(concat!(stringify!($ty), "::NAN"))
+                    } else if self.is_infinite() && self.is_sign_positive() {
+                        String::from(concat!(stringify!($ty), "::INFINITY"))
+                    } else if self.is_infinite() {
+                        String::from(concat!(stringify!($ty), "::NEG_INFINITY"))
+                    } else {
+                        format!("{}{}", self, stringify!($ty))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+float!(f32, f64);
+
+/// Format a numeric literal with a type suffix, like `4u8` or `1.5f64`.
+///
+/// This struct is created by the [lit][super::lit()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct Lit<T>(pub(super) T);
+
+impl<T> tokens::FormatInto<Rust> for Lit<T>
+where
+    T: Numeric,
+{
+    fn format_into(self, tokens: &mut Tokens<Rust>) {
+        tokens.append(self.0.format_literal());
+    }
+}
@@ -0,0 +1,39 @@
+use crate::lang::Rust;
+use crate::tokens::{self, block, from_fn};
+use crate::Tokens;
+
+/// A match expression, like `match value { pattern => body, ... }`.
+///
+/// This struct is created by the [match_expr][super::match_expr()] function.
+pub struct MatchExpr<S, A> {
+    pub(super) scrutinee: S,
+    pub(super) arms: A,
+}
+
+impl<S, A, P, B> tokens::FormatInto<Rust> for MatchExpr<S, A>
+where
+    S: tokens::FormatInto<Rust>,
+    A: IntoIterator<Item = (P, B)>,
+    P: tokens::FormatInto<Rust>,
+    B: tokens::FormatInto<Rust>,
+{
+    fn format_into(self, tokens: &mut Tokens<Rust>) {
+        let arms = self.arms;
+
+        tokens.append(tokens::static_literal("match"));
+        tokens.space();
+        tokens.append(self.scrutinee);
+        tokens.space();
+        tokens.append(block(from_fn(move |t| {
+            for (pattern, body) in arms {
+                t.push();
+                t.append(pattern);
+                t.space();
+                t.append(tokens::static_literal("=>"));
+                t.space();
+                t.append(body);
+                t.append(tokens::static_literal(","));
+            }
+        })));
+    }
+}
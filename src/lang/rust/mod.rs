@@ -0,0 +1,1334 @@
+//! Specialization for Rust code generation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: rust::Tokens = quote! {
+//!     fn foo() -> u32 {
+//!         42
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "fn foo() -> u32 {",
+//!         "    42",
+//!         "}",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Quoting in Rust
+//!
+//! Rust uses UTF-8 internally, string quoting is with the exception of escape
+//! sequences a one-to-one translation.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: rust::Tokens = quote!("start π 😊 \n \x7f ÿ $ end");
+//! assert_eq!("\"start π 😊 \\n \\x7f ÿ $ end\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Functions in Rust
+//!
+//! A [Function] is rendered as a `fn` item, or as a declaration ending in `;`
+//! if it doesn't have a body.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//! use genco::tokens::Function;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let map = rust::import("std::collections", "HashMap");
+//!
+//! let add = Function::<Rust>::new("add")
+//!     .param("a", "u32")
+//!     .param("b", quote!(&$map<u32, u32>))
+//!     .returns("u32")
+//!     .body(quote!(a + b[&a]));
+//!
+//! let decl = Function::<Rust>::new("len").param("value", "&str").returns("usize");
+//!
+//! let toks: rust::Tokens = quote! {
+//!     $add
+//!
+//!     trait Len {
+//!         $decl
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     vec![
+//!         "use std::collections::HashMap;",
+//!         "",
+//!         "fn add(a: u32, b: &HashMap<u32, u32>) -> u32 {",
+//!         "    a + b[&a]",
+//!         "}",
+//!         "",
+//!         "trait Len {",
+//!         "    fn len(value: &str) -> usize;",
+//!         "}",
+//!     ],
+//!     toks.to_file_vec()?
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+mod attribute;
+mod generics;
+mod impl_block;
+mod inline_module;
+mod lit;
+mod match_expr;
+
+pub use self::attribute::Attribute;
+pub use self::generics::Generics;
+pub use self::impl_block::ImplBlock;
+pub use self::inline_module::InlineModule;
+pub use self::lit::{Lit, Numeric};
+pub use self::match_expr::MatchExpr;
+
+use crate::fmt;
+use crate::tokens::{FormatInto, Function, ItemStr};
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+use core::iter;
+
+const SEP: &str = "::";
+
+/// Reserved words in Rust, which can't be used as identifiers.
+///
+/// See <https://doc.rust-lang.org/reference/keywords.html>.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "try", "typeof", "unsized", "virtual", "yield", "gen",
+];
+
+/// Keywords which can't be used as identifiers even in their raw form.
+///
+/// See <https://doc.rust-lang.org/reference/identifiers.html#raw-identifiers>.
+const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// Test if the given name has to be written as a raw identifier, like
+/// `r#type`.
+fn is_raw(name: &str) -> bool {
+    KEYWORDS.contains(&name) && !NON_RAW_KEYWORDS.contains(&name)
+}
+
+/// Write an identifier, using its raw form if it's a keyword.
+fn write_ident(out: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+    if is_raw(name) {
+        out.write_str("r#")?;
+    }
+
+    out.write_str(name)
+}
+
+/// Write a path, using the raw form of every segment which is a keyword.
+fn write_path(out: &mut fmt::Formatter<'_>, path: &str) -> fmt::Result {
+    for (n, segment) in path.split(SEP).enumerate() {
+        if n > 0 {
+            out.write_str(SEP)?;
+        }
+
+        write_ident(out, segment)?;
+    }
+
+    Ok(())
+}
+
+/// A path which is formatted using the raw form of every segment which is a
+/// keyword.
+#[derive(Clone, Copy)]
+struct Path<'a>(&'a str);
+
+impl FormatInto<Rust> for Path<'_> {
+    fn format_into(self, tokens: &mut Tokens) {
+        for (n, segment) in self.0.split(SEP).enumerate() {
+            if n > 0 {
+                tokens.append(crate::tokens::static_literal(SEP));
+            }
+
+            if is_raw(segment) {
+                tokens.append(crate::tokens::static_literal("r#"));
+            }
+
+            tokens.append(ItemStr::from(segment));
+        }
+    }
+}
+
+/// Tokens container specialization for Rust.
+pub type Tokens = crate::Tokens<Rust>;
+
+impl_lang! {
+    /// Language specialization for Rust.
+    pub Rust {
+        type Config = Config;
+        type Format = Format;
+        type Item = Any;
+
+        fn null_literal() -> &'static str {
+            "None"
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://doc.rust-lang.org/reference/tokens.html#literals
+
+            for c in input.chars() {
+                match c {
+                    // new line
+                    '\n' => out.write_str("\\n")?,
+                    // carriage return
+                    '\r' => out.write_str("\\r")?,
+                    // horizontal tab
+                    '\t' => out.write_str("\\t")?,
+                    // backslash
+                    '\\' => out.write_str("\\\\")?,
+                    // null
+                    '\0' => out.write_str("\\0")?,
+                    // Note: only relevant if we were to use single-quoted strings.
+                    // '\'' => out.write_str("\\'")?,
+                    '"' => out.write_str("\\\"")?,
+                    c if !c.is_control() => out.write_char(c)?,
+                    c if (c as u32) < 0x80 => {
+                        write!(out, "\\x{:02x}", c as u32)?;
+                    }
+                    c => {
+                        write!(out, "\\u{{{:04x}}}", c as u32)?;
+                    }
+                };
+            }
+
+            Ok(())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut imports: Tokens = Tokens::new();
+            Self::imports(&mut imports, config, tokens);
+
+            let format = Format::default();
+            out.preamble(|out| imports.format(out, config, &format))?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, config: &Config, _: &Format) -> fmt::Result {
+            match &self.module {
+                Module::Module {
+                    import: Some(ImportMode::Direct),
+                    ..
+                } => {
+                    self.write_direct(out)?;
+                }
+                Module::Module {
+                    import: Some(ImportMode::Qualified),
+                    module,
+                } => {
+                    self.write_prefixed(out, module)?;
+                }
+                Module::Module {
+                    import: None,
+                    module,
+                } => match &config.default_import {
+                    ImportMode::Direct => self.write_direct(out)?,
+                    ImportMode::Qualified => self.write_prefixed(out, module)?,
+                },
+                Module::Aliased {
+                    alias: ref module, ..
+                } => {
+                    write_ident(out, module)?;
+                    out.write_str(SEP)?;
+                    write_ident(out, &self.name)?;
+                }
+            }
+
+            Ok(())
+        }
+
+        fn reserved_name(&self) -> Option<&str> {
+            // NB: other keywords are written in their raw form.
+            crate::lang::find_reserved(
+                NON_RAW_KEYWORDS,
+                iter::once(&*self.name).chain(self.alias.as_deref()),
+            )
+        }
+    }
+
+    InlineModule {
+        fn format(&self, out: &mut fmt::Formatter<'_>, config: &Config, format: &Format) -> fmt::Result {
+            InlineModule::format(self, out, config, format)
+        }
+
+        fn scoped_tokens(&self) -> Option<&Tokens> {
+            Some(&self.body)
+        }
+    }
+}
+
+impl crate::lang::LangFunction for Rust {
+    fn format_function(function: &Function<Self>, t: &mut Tokens) {
+        use crate as genco;
+        use crate::quote_in;
+
+        quote_in! { *t =>
+            fn $(function.name())($(for (name, ty) in function.params() join (, ) => $name: $ty))$(if let Some(ty) = function.return_type() => $[' ']-> $ty)
+        }
+
+        match function.block() {
+            Some(body) if body.is_empty() => {
+                quote_in!(*t => $[' ']{});
+            }
+            Some(body) => {
+                quote_in! { *t =>
+                    $[' ']{
+                        $body
+                    }
+                }
+            }
+            None => {
+                quote_in!(*t => ;);
+            }
+        }
+    }
+}
+
+/// Format state for Rust.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// A callback which can modify the imports of a file before they're rendered.
+type ImportHook = Box<dyn Fn(&mut Vec<Import>) + Send + Sync>;
+
+/// Language configuration for Rust.
+pub struct Config {
+    default_import: ImportMode,
+    import_hook: Option<ImportHook>,
+}
+
+impl Config {
+    /// Configure the default import mode to use.
+    ///
+    /// See [Import] for more details.
+    pub fn with_default_import(self, default_import: ImportMode) -> Self {
+        Self {
+            default_import,
+            ..self
+        }
+    }
+
+    /// Set a callback which is called with the imports of a file before
+    /// they're rendered, which can be used to reorder or filter them.
+    ///
+    /// The callback receives each unique import, sorted by module. Modules
+    /// are rendered in the order in which they first appear after the
+    /// callback has been called, and imports from the same module are
+    /// grouped into a single `use` declaration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    /// let debug = rust::import("std::fmt", "Debug");
+    /// let serialize = rust::import("serde", "Serialize");
+    ///
+    /// let toks: rust::Tokens = quote! {
+    ///     #[derive($debug, $serialize)]
+    ///     struct Foo($map<u32, u32>);
+    /// };
+    ///
+    /// // Put imports from the standard library first.
+    /// let config = rust::Config::default().with_import_hook(|imports| {
+    ///     imports.sort_by_key(|import| !import.module().starts_with("std::"));
+    /// });
+    ///
+    /// let fmt = genco::fmt::Config::from_lang::<Rust>();
+    ///
+    /// assert_eq!(
+    ///     "use std::collections::HashMap;\nuse std::fmt::Debug;\nuse serde::Serialize;\n\n#[derive(Debug, Serialize)]\nstruct Foo(HashMap<u32, u32>);\n",
+    ///     toks.to_file_string_with(&config, &fmt)?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_import_hook<F>(self, import_hook: F) -> Self
+    where
+        F: 'static + Fn(&mut Vec<Import>) + Send + Sync,
+    {
+        Self {
+            import_hook: Some(Box::new(import_hook)),
+            ..self
+        }
+    }
+}
+
+impl core::fmt::Debug for Config {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Config")
+            .field("default_import", &self.default_import)
+            .field("import_hook", &self.import_hook.is_some())
+            .finish()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            default_import: ImportMode::Direct,
+            import_hook: None,
+        }
+    }
+}
+
+/// The import mode to use when generating import statements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ImportMode {
+    /// Import names without a module prefix.
+    ///
+    /// so for `std::fmt::Debug` it would import `std::fmt::Debug`, and use
+    /// `Debug`.
+    Direct,
+    /// Import qualified names with a module prefix.
+    ///
+    /// so for `std::fmt::Debug` it would import `std::fmt`, and use
+    /// `fmt::Debug`.
+    Qualified,
+}
+
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+enum Module {
+    /// Type imported directly from module with the specified mode.
+    Module {
+        import: Option<ImportMode>,
+        module: ItemStr,
+    },
+    /// Prefixed with an alias.
+    Aliased { module: ItemStr, alias: ItemStr },
+}
+
+impl Module {
+    /// The path of the module.
+    fn path(&self) -> &ItemStr {
+        match self {
+            Self::Module { module, .. } => module,
+            Self::Aliased { module, .. } => module,
+        }
+    }
+
+    /// Convert into an aliased import, or keep as same in case that's not
+    /// feasible.
+    fn into_module_aliased<A>(self, alias: A) -> Self
+    where
+        A: Into<ItemStr>,
+    {
+        match self {
+            Self::Module { module, .. } => Self::Aliased {
+                module,
+                alias: alias.into(),
+            },
+            other => other,
+        }
+    }
+
+    /// Aliasing a type explicitly means you no longer want to import it by
+    /// module. Set the correct import here.
+    fn into_aliased(self) -> Self {
+        match self {
+            Self::Module { module, .. } => Self::Module {
+                import: Some(ImportMode::Direct),
+                module,
+            },
+            other => other,
+        }
+    }
+
+    /// Switch to a direct import mode.
+    ///
+    /// See [ImportMode::Direct].
+    fn direct(self) -> Self {
+        match self {
+            Self::Module { module, .. } => Self::Module {
+                module,
+                import: Some(ImportMode::Direct),
+            },
+            other => other,
+        }
+    }
+
+    /// Switch into a qualified import mode.
+    ///
+    /// See [ImportMode::Qualified].
+    fn qualified(self) -> Self {
+        match self {
+            Self::Module { module, .. } => Self::Module {
+                module,
+                import: Some(ImportMode::Qualified),
+            },
+            other => other,
+        }
+    }
+}
+
+/// The import of a Rust type `use std::collections::HashMap`.
+///
+/// Created through the [import()] function.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+pub struct Import {
+    /// How the type is imported.
+    module: Module,
+    /// Name of type.
+    name: ItemStr,
+    /// Alias to use for the type.
+    alias: Option<ItemStr>,
+}
+
+impl Import {
+    /// The path of the module being imported from, like `std::fmt`.
+    pub fn module(&self) -> &str {
+        self.module.path()
+    }
+
+    /// The name being imported, like `Debug`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Alias the given type as it's imported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let ty = rust::import("std::fmt", "Debug").with_alias("FmtDebug");
+    ///
+    /// let toks = quote!($ty);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "use std::fmt::Debug as FmtDebug;",
+    ///         "",
+    ///         "FmtDebug",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_alias<A: Into<ItemStr>>(self, alias: A) -> Self {
+        Self {
+            module: self.module.into_aliased(),
+            alias: Some(alias.into()),
+            ..self
+        }
+    }
+
+    /// Alias the module being imported.
+    ///
+    /// This also implies that the import is [qualified()].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let ty = rust::import("std::fmt", "Debug").with_module_alias("other");
+    ///
+    /// let toks = quote!($ty);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "use std::fmt as other;",
+    ///         "",
+    ///         "other::Debug",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    ///
+    /// [qualified()]: Self::qualified()
+    pub fn with_module_alias<A: Into<ItemStr>>(self, alias: A) -> Self {
+        Self {
+            module: self.module.into_module_aliased(alias),
+            ..self
+        }
+    }
+
+    /// Switch to a qualified import mode.
+    ///
+    /// See [ImportMode::Qualified].
+    ///
+    /// So importing `std::fmt::Debug` will cause the module to be referenced as
+    /// `fmt::Debug` instead of `Debug`.
+    ///
+    /// This is implied if [with_module_alias()][Self::with_module_alias()] is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let ty = rust::import("std::fmt", "Debug").qualified();
+    ///
+    /// let toks = quote!($ty);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "use std::fmt;",
+    ///         "",
+    ///         "fmt::Debug",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn qualified(self) -> Self {
+        Self {
+            module: self.module.qualified(),
+            ..self
+        }
+    }
+
+    /// Switch into a direct import mode.
+    ///
+    /// See [ImportMode::Direct].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let ty = rust::import("std::fmt", "Debug").direct();
+    ///
+    /// let toks = quote!($ty);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "use std::fmt::Debug;",
+    ///         "",
+    ///         "Debug",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn direct(self) -> Self {
+        Self {
+            module: self.module.direct(),
+            ..self
+        }
+    }
+
+    /// Write the direct name of the type.
+    fn write_direct(&self, out: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(alias) = &self.alias {
+            write_ident(out, alias)
+        } else {
+            write_ident(out, &self.name)
+        }
+    }
+
+    /// Write the prefixed name of the type.
+    fn write_prefixed(&self, out: &mut fmt::Formatter<'_>, module: &ItemStr) -> fmt::Result {
+        if let Some(module) = module.rsplit(SEP).next() {
+            write_path(out, module)?;
+            out.write_str(SEP)?;
+        }
+
+        write_ident(out, &self.name)?;
+        Ok(())
+    }
+}
+
+impl Rust {
+    fn imports(out: &mut Tokens, config: &Config, tokens: &Tokens) {
+        use crate as genco;
+        use crate::quote_in;
+        use alloc::collections::btree_set;
+
+        let mut imports = Vec::new();
+
+        for import in tokens.walk_imports_dedup() {
+            // Inline modules render their own imports.
+            if let Any::Import(import) = import {
+                imports.push(import.clone());
+            }
+        }
+
+        imports.sort_by(|a, b| {
+            (a.module.path(), &a.name, &a.alias).cmp(&(b.module.path(), &b.name, &b.alias))
+        });
+        imports.dedup();
+
+        if let Some(hook) = &config.import_hook {
+            hook(&mut imports);
+        }
+
+        // Modules in the order in which they should be rendered.
+        let mut modules = Vec::<(&ItemStr, Import)>::new();
+        let mut indexes = BTreeMap::<&ItemStr, usize>::new();
+
+        for import in &imports {
+            let path = import.module.path();
+
+            let index = *indexes.entry(path).or_insert_with(|| {
+                modules.push((path, Import::default()));
+                modules.len() - 1
+            });
+
+            let module = &mut modules[index].1;
+
+            match &import.module {
+                Module::Module {
+                    import: Some(ImportMode::Direct),
+                    ..
+                } => {
+                    module.names.insert((&import.name, import.alias.as_ref()));
+                }
+                Module::Module {
+                    import: Some(ImportMode::Qualified),
+                    ..
+                } => {
+                    module.self_import = true;
+                }
+                Module::Module { import: None, .. } => match config.default_import {
+                    ImportMode::Direct => {
+                        module.names.insert((&import.name, import.alias.as_ref()));
+                    }
+                    ImportMode::Qualified => {
+                        module.self_import = true;
+                    }
+                },
+                Module::Aliased { alias, .. } => {
+                    module.self_aliases.insert(alias);
+                }
+            }
+        }
+
+        let mut has_any = false;
+
+        for (m, module) in modules {
+            let mut render = module.iter(m);
+            let m = Path(m);
+
+            if let Some(first) = render.next() {
+                has_any = true;
+                out.push();
+
+                // render as a group if there's more than one thing being
+                // imported.
+                if let Some(second) = render.next() {
+                    quote_in! { *out =>
+                        use $m::{$(ref o =>
+                            first.render(o);
+                            quote_in!(*o => , $(ref o => second.render(o)));
+
+                            for item in render {
+                                quote_in!(*o => , $(ref o => item.render(o)));
+                            }
+                        )};
+                    };
+                } else {
+                    match first {
+                        RenderItem::SelfImport => {
+                            quote_in!(*out => use $m;);
+                        }
+                        RenderItem::SelfAlias { alias } => {
+                            quote_in!(*out => use $m as $(Path(alias)););
+                        }
+                        RenderItem::Name {
+                            name,
+                            alias: Some(alias),
+                        } => {
+                            quote_in!(*out => use $m::$(Path(name)) as $(Path(alias)););
+                        }
+                        RenderItem::Name { name, alias: None } => {
+                            quote_in!(*out => use $m::$(Path(name)););
+                        }
+                    }
+                }
+            }
+        }
+
+        if has_any {
+            out.line();
+        }
+
+        return;
+
+        /// An imported module.
+        #[derive(Debug, Default)]
+        struct Import<'a> {
+            /// If we need the module (e.g. through an alias).
+            self_import: bool,
+            /// Aliases for the own module.
+            self_aliases: BTreeSet<&'a ItemStr>,
+            /// Set of imported names.
+            names: BTreeSet<(&'a ItemStr, Option<&'a ItemStr>)>,
+        }
+
+        impl<'a> Import<'a> {
+            fn iter(self, module: &'a str) -> ImportedIter<'a> {
+                ImportedIter {
+                    module,
+                    self_import: self.self_import,
+                    self_aliases: self.self_aliases.into_iter(),
+                    names: self.names.into_iter(),
+                }
+            }
+        }
+
+        struct ImportedIter<'a> {
+            module: &'a str,
+            self_import: bool,
+            self_aliases: btree_set::IntoIter<&'a ItemStr>,
+            names: btree_set::IntoIter<(&'a ItemStr, Option<&'a ItemStr>)>,
+        }
+
+        impl<'a> Iterator for ImportedIter<'a> {
+            type Item = RenderItem<'a>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if core::mem::take(&mut self.self_import) {
+                    // Only render self-import if it's not a top level module.
+                    if self.module.split(SEP).count() > 1 {
+                        return Some(RenderItem::SelfImport);
+                    }
+                }
+
+                if let Some(alias) = self.self_aliases.next() {
+                    return Some(RenderItem::SelfAlias { alias });
+                }
+
+                if let Some((name, alias)) = self.names.next() {
+                    return Some(RenderItem::Name { name, alias });
+                }
+
+                None
+            }
+        }
+
+        #[derive(Clone, Copy)]
+        enum RenderItem<'a> {
+            SelfImport,
+            SelfAlias {
+                alias: &'a ItemStr,
+            },
+            Name {
+                name: &'a ItemStr,
+                alias: Option<&'a ItemStr>,
+            },
+        }
+
+        impl RenderItem<'_> {
+            fn render(self, out: &mut Tokens) {
+                match self {
+                    Self::SelfImport => {
+                        quote_in!(*out => self);
+                    }
+                    Self::SelfAlias { alias } => {
+                        quote_in!(*out => self as $(Path(alias)));
+                    }
+                    Self::Name {
+                        name,
+                        alias: Some(alias),
+                    } => {
+                        quote_in!(*out => $(Path(name)) as $(Path(alias)));
+                    }
+                    Self::Name { name, alias: None } => {
+                        quote_in!(*out => $(Path(name)));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The import of a Rust type `use std::collections::HashMap`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let a = rust::import("std::fmt", "Debug").qualified();
+/// let b = rust::import("std::fmt", "Debug").with_module_alias("fmt2");
+/// let c = rust::import("std::fmt", "Debug");
+/// let d = rust::import("std::fmt", "Debug").with_alias("FmtDebug");
+///
+/// let toks = quote!{
+///     $a
+///     $b
+///     $c
+///     $d
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::fmt::{self, self as fmt2, Debug, Debug as FmtDebug};",
+///         "",
+///         "fmt::Debug",
+///         "fmt2::Debug",
+///         "Debug",
+///         "FmtDebug",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// # Example with an alias
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let ty = rust::import("std::fmt", "Debug").with_alias("FmtDebug");
+///
+/// let toks = quote!{
+///     $ty
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::fmt::Debug as FmtDebug;",
+///         "",
+///         "FmtDebug",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// # Example with a module alias
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let ty = rust::import("std::fmt", "Debug").with_module_alias("fmt2");
+///
+/// let toks = quote!{
+///     $ty
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::fmt as fmt2;",
+///         "",
+///         "fmt2::Debug",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// # Example with multiple aliases
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let a = rust::import("std::fmt", "Debug").with_alias("FmtDebug");
+/// let b = rust::import("std::fmt", "Debug").with_alias("FmtDebug2");
+///
+/// let toks = quote!{
+///     $a
+///     $b
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::fmt::{Debug as FmtDebug, Debug as FmtDebug2};",
+///         "",
+///         "FmtDebug",
+///         "FmtDebug2",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// # Example with keywords
+///
+/// Names which are keywords are written as raw identifiers.
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let ty = rust::import("foo", "type");
+///
+/// let toks = quote!($ty);
+///
+/// assert_eq!(
+///     vec![
+///         "use foo::r#type;",
+///         "",
+///         "r#type",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn import<M, N>(module: M, name: N) -> Import
+where
+    M: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        module: Module::Module {
+            import: None,
+            module: module.into(),
+        },
+        name: name.into(),
+        alias: None,
+    }
+}
+
+/// Construct an inline module, like `mod foo { ... }`.
+///
+/// Imports used in the body are rendered at the top of the module instead of
+/// at the top of the file. Since a module doesn't inherit the imports of its
+/// parent, an import which is used both inside and outside of the module is
+/// rendered in both places.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let map = rust::import("std::collections", "HashMap");
+/// let set = rust::import("std::collections", "HashSet");
+///
+/// let toks = quote! {
+///     $(rust::module("tests", quote! {
+///         fn test() {
+///             let _ = $(&map)::<u32, u32>::new();
+///         }
+///     }))
+///
+///     fn main() {
+///         let _ = $set::<u32>::new();
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::collections::HashSet;",
+///         "",
+///         "mod tests {",
+///         "    use std::collections::HashMap;",
+///         "",
+///         "    fn test() {",
+///         "        let _ = HashMap::<u32, u32>::new();",
+///         "    }",
+///         "}",
+///         "",
+///         "fn main() {",
+///         "    let _ = HashSet::<u32>::new();",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn module<N>(name: N, body: Tokens) -> InlineModule
+where
+    N: Into<ItemStr>,
+{
+    InlineModule {
+        name: name.into(),
+        body,
+    }
+}
+
+/// Format an impl block, like `impl Trait for Type { ... }`.
+///
+/// If `trait_` is `None`, this is an inherent impl like `impl Type { ... }`.
+/// Imports in the trait, the type and the body are registered as usual.
+/// Generic parameters can be added using
+/// [with_generics][ImplBlock::with_generics].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let display = rust::import("std::fmt", "Display");
+/// let formatter = rust::import("std::fmt", "Formatter");
+/// let result = rust::import("std::fmt", "Result");
+///
+/// let toks: rust::Tokens = quote! {
+///     $(rust::impl_block(None, "Foo", quote! {
+///         const ANSWER: u32 = 42;
+///     }))
+///
+///     $(rust::impl_block(Some(quote!($display)), "Foo", quote! {
+///         fn fmt(&self, f: &mut $formatter<'_>) -> $result {
+///             Ok(())
+///         }
+///     }))
+///
+///     $(rust::impl_block(Some(quote!(Iterator)), "Bar<T>", quote! {
+///         type Item = T;
+///     }).with_generics("T"))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::fmt::{Display, Formatter, Result};",
+///         "",
+///         "impl Foo {",
+///         "    const ANSWER: u32 = 42;",
+///         "}",
+///         "",
+///         "impl Display for Foo {",
+///         "    fn fmt(&self, f: &mut Formatter<'_>) -> Result {",
+///         "        Ok(())",
+///         "    }",
+///         "}",
+///         "",
+///         "impl<T> Iterator for Bar<T> {",
+///         "    type Item = T;",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn impl_block<T, B>(trait_: Option<Tokens>, ty: T, body: B) -> ImplBlock<T, B>
+where
+    T: FormatInto<Rust>,
+    B: FormatInto<Rust>,
+{
+    ImplBlock {
+        trait_,
+        ty,
+        body,
+        generics: None,
+    }
+}
+
+/// Format a match expression, like `match value { pattern => body, ... }`.
+///
+/// Each arm is a pair of a pattern and a body, which are placed on their own
+/// indented line followed by a trailing comma. Imports used in the scrutinee,
+/// patterns, and bodies are registered.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let ordering = rust::import("std::cmp", "Ordering");
+///
+/// let toks: rust::Tokens = quote! {
+///     let word = $(rust::match_expr(quote!(a.cmp(&b)), [
+///         (quote!($(&ordering)::Less), quote!($(quoted("less")))),
+///         (quote!(_), quote!($(quoted("not less")))),
+///     ]));
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::cmp::Ordering;",
+///         "",
+///         "let word = match a.cmp(&b) {",
+///         "    Ordering::Less => \"less\",",
+///         "    _ => \"not less\",",
+///         "};",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn match_expr<S, A, P, B>(scrutinee: S, arms: A) -> MatchExpr<S, A>
+where
+    S: FormatInto<Rust>,
+    A: IntoIterator<Item = (P, B)>,
+    P: FormatInto<Rust>,
+    B: FormatInto<Rust>,
+{
+    MatchExpr { scrutinee, arms }
+}
+
+/// Construct an empty list of generic parameters, like `<'a, T: Debug>`.
+///
+/// Imports used in bounds are registered when the parameter list is
+/// formatted. See [Generics] for how parameters are rendered.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let debug = rust::import("std::fmt", "Debug");
+/// let hash = rust::import("std::hash", "Hash");
+///
+/// let generics = rust::generics()
+///     .with_lifetime("a")
+///     .with_bound("T", &debug)
+///     .with_bound("K", &debug)
+///     .with_bound("K", hash)
+///     .with_const("N", "usize");
+///
+/// let toks: rust::Tokens = quote! {
+///     fn foo$(&generics)(value: &'a T, keys: [K; N]) $(generics.where_clause()) {}
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use std::fmt::Debug;",
+///         "use std::hash::Hash;",
+///         "",
+///         "fn foo<'a, T: Debug, K, const N: usize>(value: &'a T, keys: [K; N]) where K: Debug + Hash {}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn generics() -> Generics {
+    Generics::default()
+}
+
+/// Format an attribute with inner tokens, like `#[cfg(feature = "x")]`.
+///
+/// Both the path and the inner tokens can contain imports.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let serialize = rust::import("serde", "Serialize");
+///
+/// let toks: rust::Tokens = quote! {
+///     $(rust::attr("cfg", quote!(feature = $(quoted("x")))))
+///     $(rust::attr("derive", quote!(Debug, $serialize)))
+///     struct Foo {
+///         $(rust::attr("serde", quote!(rename = $(quoted("foo")))))
+///         bar: u32,
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use serde::Serialize;",
+///         "",
+///         "#[cfg(feature = \"x\")]",
+///         "#[derive(Debug, Serialize)]",
+///         "struct Foo {",
+///         "    #[serde(rename = \"foo\")]",
+///         "    bar: u32,",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn attr<P, T>(path: P, inner: T) -> Attribute<P>
+where
+    P: FormatInto<Rust>,
+    T: FormatInto<Rust>,
+{
+    let mut tokens = Tokens::new();
+    tokens.append(inner);
+
+    Attribute {
+        path,
+        inner: Some(tokens),
+    }
+}
+
+/// Format a bare attribute, like `#[inline]`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let test = rust::import("tokio", "test");
+///
+/// let toks: rust::Tokens = quote! {
+///     $(rust::attr_path("inline"))
+///     fn foo() {}
+///
+///     $(rust::attr_path(test))
+///     async fn bar() {}
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "use tokio::test;",
+///         "",
+///         "#[inline]",
+///         "fn foo() {}",
+///         "",
+///         "#[test]",
+///         "async fn bar() {}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn attr_path<P>(path: P) -> Attribute<P>
+where
+    P: FormatInto<Rust>,
+{
+    Attribute { path, inner: None }
+}
+
+/// Format a numeric literal with its type as a suffix.
+///
+/// Non-finite floating point values are formatted as the corresponding
+/// associated constants, like `f64::NAN`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks: rust::Tokens = quote! {
+///     let a = $(rust::lit(4u32));
+///     let b = $(rust::lit(1.5f64));
+///     let c = $(rust::lit(-2i8));
+///     let d = $(rust::lit(f32::INFINITY));
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "let a = 4u32;",
+///         "let b = 1.5f64;",
+///         "let c = -2i8;",
+///         "let d = f32::INFINITY;",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn lit<T>(value: T) -> Lit<T>
+where
+    T: Numeric,
+{
+    Lit(value)
+}
@@ -0,0 +1,175 @@
+use crate::lang::Swift;
+use crate::tokens;
+use crate::Tokens;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Format a doc comment where each line is preceeded by `///`, with optional
+/// `- Parameter`, `- Returns`, and `- Throws` sections.
+///
+/// The content of the comment is written as-is, so markdown is preserved.
+///
+/// This struct is created by the [doc_comment][super::doc_comment()] function.
+pub struct DocComment<T> {
+    pub(super) summary: T,
+    pub(super) parameters: Vec<(tokens::ItemStr, tokens::ItemStr)>,
+    pub(super) returns: Option<tokens::ItemStr>,
+    pub(super) throws: Option<tokens::ItemStr>,
+    pub(super) width: Option<usize>,
+}
+
+impl<T> DocComment<T> {
+    /// Document a parameter, rendered as `- Parameter <name>: <description>`.
+    pub fn with_parameter<N, D>(mut self, name: N, description: D) -> Self
+    where
+        N: Into<tokens::ItemStr>,
+        D: Into<tokens::ItemStr>,
+    {
+        self.parameters.push((name.into(), description.into()));
+        self
+    }
+
+    /// Document the return value, rendered as `- Returns: <description>`.
+    pub fn with_returns<D>(self, description: D) -> Self
+    where
+        D: Into<tokens::ItemStr>,
+    {
+        Self {
+            returns: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Document the errors thrown, rendered as `- Throws: <description>`.
+    pub fn with_throws<D>(self, description: D) -> Self
+    where
+        D: Into<tokens::ItemStr>,
+    {
+        Self {
+            throws: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Wrap lines of the comment which are longer than the given width,
+    /// including the `/// ` prefix.
+    ///
+    /// Lines are only broken at whitespace, and continuation lines of a
+    /// section are indented to be part of the same list item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let doc = swift::doc_comment(vec!["Computes the sum of two integers, wrapping on overflow."])
+    ///     .with_parameter("a", "The first integer to add together.")
+    ///     .with_width(32);
+    ///
+    /// let toks: swift::Tokens = quote!($doc);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "/// Computes the sum of two",
+    ///         "/// integers, wrapping on",
+    ///         "/// overflow.",
+    ///         "///",
+    ///         "/// - Parameter a: The first",
+    ///         "///   integer to add together.",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_width(self, width: usize) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+}
+
+impl<T> tokens::FormatInto<Swift> for DocComment<T>
+where
+    T: IntoIterator,
+    T::Item: Into<tokens::ItemStr>,
+{
+    fn format_into(self, tokens: &mut Tokens<Swift>) {
+        let width = self.width;
+        let mut has_summary = false;
+
+        for line in self.summary {
+            write_line(tokens, width, "", line.into());
+            has_summary = true;
+        }
+
+        let mut sections = Vec::new();
+
+        for (name, description) in self.parameters {
+            sections.push(format!("- Parameter {}: {}", name, description));
+        }
+
+        if let Some(returns) = self.returns {
+            sections.push(format!("- Returns: {}", returns));
+        }
+
+        if let Some(throws) = self.throws {
+            sections.push(format!("- Throws: {}", throws));
+        }
+
+        if has_summary && !sections.is_empty() {
+            write_line(tokens, None, "", tokens::ItemStr::Static(""));
+        }
+
+        for section in sections {
+            write_line(tokens, width, "  ", section.into());
+        }
+    }
+}
+
+/// Write a single line of the comment, wrapping it if necessary.
+fn write_line(
+    tokens: &mut Tokens<Swift>,
+    width: Option<usize>,
+    indent: &str,
+    line: tokens::ItemStr,
+) {
+    // Width available for text, excluding the `/// ` prefix.
+    let available = match width {
+        Some(width) if line.chars().count() + 4 > width => width.saturating_sub(4),
+        _ => {
+            push_line(tokens, line);
+            return;
+        }
+    };
+
+    let mut current = String::new();
+    let mut empty = true;
+
+    for word in line.split_whitespace() {
+        if !empty {
+            if current.chars().count() + 1 + word.chars().count() > available {
+                push_line(tokens, core::mem::take(&mut current).into());
+                current.push_str(indent);
+            } else {
+                current.push(' ');
+            }
+        }
+
+        current.push_str(word);
+        empty = false;
+    }
+
+    push_line(tokens, current.into());
+}
+
+fn push_line(tokens: &mut Tokens<Swift>, line: tokens::ItemStr) {
+    tokens.push();
+    tokens.append(tokens::static_literal("///"));
+
+    if !line.is_empty() {
+        tokens.space();
+        tokens.append(line);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::lang::Swift;
+use crate::tokens;
+use crate::Tokens;
+use alloc::vec::Vec;
+
+/// Format a conditional compilation block, like `#if os(iOS) ... #endif`.
+///
+/// Following Swift convention, the body of each branch is not indented.
+///
+/// This struct is created by the [if_config][super::if_config()] function.
+pub struct IfConfig {
+    pub(super) branches: Vec<(Tokens<Swift>, Tokens<Swift>)>,
+    pub(super) otherwise: Option<Tokens<Swift>>,
+}
+
+impl IfConfig {
+    /// Add an `#elseif` branch to the block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let toks: swift::Tokens = quote! {
+    ///     $(swift::if_config("os(iOS)", quote!(let a = 1))
+    ///         .with_else_if("os(macOS)", quote!(let a = 2))
+    ///         .with_else(quote!(let a = 3)))
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "#if os(iOS)",
+    ///         "let a = 1",
+    ///         "#elseif os(macOS)",
+    ///         "let a = 2",
+    ///         "#else",
+    ///         "let a = 3",
+    ///         "#endif",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_else_if<C, B>(mut self, condition: C, body: B) -> Self
+    where
+        C: tokens::FormatInto<Swift>,
+        B: tokens::FormatInto<Swift>,
+    {
+        self.branches
+            .push((into_tokens(condition), into_tokens(body)));
+        self
+    }
+
+    /// Add an `#else` branch to the block.
+    ///
+    /// See [with_else_if()][Self::with_else_if] for an example.
+    pub fn with_else<B>(self, body: B) -> Self
+    where
+        B: tokens::FormatInto<Swift>,
+    {
+        Self {
+            otherwise: Some(into_tokens(body)),
+            ..self
+        }
+    }
+}
+
+impl tokens::FormatInto<Swift> for IfConfig {
+    fn format_into(self, tokens: &mut Tokens<Swift>) {
+        let mut directive = tokens::static_literal("#if");
+
+        for (condition, body) in self.branches {
+            tokens.push();
+            tokens.append(directive);
+            tokens.space();
+            tokens.append(condition);
+            tokens.push();
+            tokens.append(body);
+            directive = tokens::static_literal("#elseif");
+        }
+
+        if let Some(body) = self.otherwise {
+            tokens.push();
+            tokens.append(tokens::static_literal("#else"));
+            tokens.push();
+            tokens.append(body);
+        }
+
+        tokens.push();
+        tokens.append(tokens::static_literal("#endif"));
+        tokens.push();
+    }
+}
+
+/// Convert something formattable into a token stream.
+pub(super) fn into_tokens<T>(value: T) -> Tokens<Swift>
+where
+    T: tokens::FormatInto<Swift>,
+{
+    let mut tokens = Tokens::new();
+    tokens.append(value);
+    tokens
+}
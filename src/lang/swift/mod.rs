@@ -0,0 +1,705 @@
+//! Specialization for Swift code generation.
+//!
+//! # String Quoting in Swift
+//!
+//! Swift uses UTF-8 internally, string quoting is with the exception of escape
+//! sequences a one-to-one translation.
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: swift::Tokens = quote!("start π 😊 \n \x7f ÿ $ end");
+//! assert_eq!("\"start π 😊 \\n \\u{7f} ÿ $ end\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # String Interpolation in Swift
+//!
+//! Swift supports [string interpolation] through `\(expr)`, which is used when
+//! quoting with `$[str](<content>)`. Literal backslashes are always escaped, so
+//! only interpolated values produce the `\(` sequence.
+//!
+//! [string interpolation]: https://docs.swift.org/swift-book/LanguageGuide/StringsAndCharacters.html#ID292
+//!
+//! ```rust
+//! use genco::prelude::*;
+//!
+//! # fn main() -> genco::fmt::Result {
+//! let toks: swift::Tokens = quote!($[str](Hello $(name)));
+//! assert_eq!("\"Hello \\(name)\"", toks.to_string()?);
+//!
+//! let toks: swift::Tokens = quote!($[str]($[const]("C:\\") $(a + b)));
+//! assert_eq!("\"C:\\\\ \\(a + b)\"", toks.to_string()?);
+//!
+//! let toks: swift::Tokens = quote!($(quoted("say \"\\(hi)\"")));
+//! assert_eq!("\"say \\\"\\\\(hi)\\\"\"", toks.to_string()?);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Multiline String Literals in Swift
+//!
+//! Text spanning multiple lines can be quoted as a `"""` delimited literal
+//! using [multiline_string()].
+
+mod doc_comment;
+mod if_config;
+mod multiline_string;
+
+pub use self::doc_comment::DocComment;
+pub use self::if_config::IfConfig;
+pub use self::multiline_string::MultilineString;
+
+use crate::fmt;
+use crate::tokens::{FormatInto, ItemStr};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+use core::iter;
+
+/// Reserved words in Swift, which can't be used as identifiers.
+///
+/// See <https://docs.swift.org/swift-book/documentation/the-swift-programming-language/lexicalstructure/#Keywords-and-Punctuation>.
+const KEYWORDS: &[&str] = &[
+    "associatedtype",
+    "class",
+    "deinit",
+    "enum",
+    "extension",
+    "fileprivate",
+    "func",
+    "import",
+    "init",
+    "inout",
+    "internal",
+    "let",
+    "open",
+    "operator",
+    "private",
+    "precedencegroup",
+    "protocol",
+    "public",
+    "rethrows",
+    "static",
+    "struct",
+    "subscript",
+    "typealias",
+    "var",
+    "break",
+    "case",
+    "catch",
+    "continue",
+    "default",
+    "defer",
+    "do",
+    "else",
+    "fallthrough",
+    "for",
+    "guard",
+    "if",
+    "in",
+    "repeat",
+    "return",
+    "throw",
+    "switch",
+    "where",
+    "while",
+    "Any",
+    "as",
+    "await",
+    "false",
+    "is",
+    "nil",
+    "self",
+    "Self",
+    "super",
+    "throws",
+    "true",
+    "try",
+];
+
+/// Tokens container specialization for Rust.
+pub type Tokens = crate::Tokens<Swift>;
+
+impl crate::lang::LangSupportsEval for Swift {}
+
+impl_lang! {
+    /// Swift token specialization.
+    pub Swift {
+        type Config = Config;
+        type Format = Format;
+        type Item = Import;
+
+        fn null_literal() -> &'static str {
+            "nil"
+        }
+
+        fn start_string_eval(
+            out: &mut fmt::Formatter<'_>,
+            _config: &Self::Config,
+            _format: &Self::Format,
+        ) -> fmt::Result {
+            out.write_str("\\(")?;
+            Ok(())
+        }
+
+        fn end_string_eval(
+            out: &mut fmt::Formatter<'_>,
+            _config: &Self::Config,
+            _format: &Self::Format,
+        ) -> fmt::Result {
+            out.write_char(')')?;
+            Ok(())
+        }
+
+        fn write_quoted(out: &mut fmt::Formatter<'_>, input: &str) -> fmt::Result {
+            // From: https://docs.swift.org/swift-book/LanguageGuide/StringsAndCharacters.html
+
+            for c in input.chars() {
+                match c {
+                    '\0' => out.write_str("\\0")?,
+                    '\\' => out.write_str("\\\\")?,
+                    '\t' => out.write_str("\\t")?,
+                    '\n' => out.write_str("\\n")?,
+                    '\r' => out.write_str("\\r")?,
+                    '\'' => out.write_str("\\'")?,
+                    '"' => out.write_str("\\\"")?,
+                    c if !c.is_control() => out.write_char(c)?,
+                    c => {
+                        write!(out, "\\u{{{:x}}}", c as u32)?;
+                    }
+                };
+            }
+
+            Ok(())
+        }
+
+        fn format_file(
+            tokens: &Tokens,
+            out: &mut fmt::Formatter<'_>,
+            config: &Self::Config,
+        ) -> fmt::Result {
+            let mut imports = Tokens::new();
+            Self::imports(&mut imports, tokens, config);
+            let format = Format::default();
+            out.preamble(|out| imports.format(out, config, &format))?;
+            tokens.format(out, config, &format)?;
+            Ok(())
+        }
+    }
+
+    Import {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            out.write_str(&self.name)
+        }
+
+        fn reserved_name(&self) -> Option<&str> {
+            crate::lang::find_reserved(KEYWORDS, iter::once(&*self.name))
+        }
+    }
+}
+
+/// Format state for Swift code.
+#[derive(Debug, Default)]
+pub struct Format {}
+
+/// Configuration for formatting Swift code.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// Whether a plain module import subsumes kind imports from the same
+    /// module.
+    subsume_kind_imports: bool,
+}
+
+impl Config {
+    /// Configure whether a plain import like `import Foundation` subsumes
+    /// kind imports from the same module, like `import struct
+    /// Foundation.Date`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let url = swift::import("Foundation", "URL");
+    /// let date = swift::import_kind(swift::Kind::Struct, "Foundation", "Date");
+    ///
+    /// let toks = quote! {
+    ///     $url
+    ///     $date
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import Foundation",
+    ///         "import struct Foundation.Date",
+    ///         "",
+    ///         "URL",
+    ///         "Date",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    ///
+    /// let config = swift::Config::default().with_subsume_kind_imports(true);
+    /// let fmt = fmt::Config::from_lang::<Swift>();
+    ///
+    /// let mut w = fmt::VecWriter::new();
+    /// toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import Foundation",
+    ///         "",
+    ///         "URL",
+    ///         "Date",
+    ///     ],
+    ///     w.into_vec()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn with_subsume_kind_imports(self, subsume_kind_imports: bool) -> Self {
+        Self {
+            subsume_kind_imports,
+        }
+    }
+}
+
+/// The kind of a declaration imported from a module, as in `import struct
+/// Foundation.Date`.
+///
+/// Used with the [import_kind()] function.
+#[derive(Debug, Clone, Copy, Hash, PartialOrd, Ord, PartialEq, Eq)]
+pub enum Kind {
+    /// `import typealias`.
+    Typealias,
+    /// `import struct`.
+    Struct,
+    /// `import class`.
+    Class,
+    /// `import enum`.
+    Enum,
+    /// `import protocol`.
+    Protocol,
+    /// `import let`.
+    Let,
+    /// `import var`.
+    Var,
+    /// `import func`.
+    Func,
+}
+
+impl Kind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Typealias => "typealias",
+            Self::Struct => "struct",
+            Self::Class => "class",
+            Self::Enum => "enum",
+            Self::Protocol => "protocol",
+            Self::Let => "let",
+            Self::Var => "var",
+            Self::Func => "func",
+        }
+    }
+}
+
+/// The import of a Swift type `import UIKit`.
+///
+/// Created through the [import()] and [import_kind()] functions.
+#[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
+pub struct Import {
+    /// Module of the imported name.
+    module: ItemStr,
+    /// Name imported.
+    name: ItemStr,
+    /// The kind of the declaration imported, if it's imported individually.
+    kind: Option<Kind>,
+    /// If the import is `@testable`.
+    testable: bool,
+    /// If the import is guarded by `#if canImport(<module>)`.
+    can_import_guard: bool,
+}
+
+impl Import {
+    /// Mark the import as `@testable`, giving access to internal declarations
+    /// of the module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let a = swift::import("MyModule", "Foo").testable();
+    /// let b = swift::import("MyModule", "Bar");
+    /// let c = swift::import("XCTest", "XCTestCase");
+    ///
+    /// let toks = quote! {
+    ///     $a
+    ///     $b
+    ///     $c
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import XCTest",
+    ///         "@testable import MyModule",
+    ///         "",
+    ///         "Foo",
+    ///         "Bar",
+    ///         "XCTestCase",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn testable(self) -> Self {
+        Self {
+            testable: true,
+            ..self
+        }
+    }
+
+    /// Guard the import with `#if canImport(<module>)`, for modules which are
+    /// only available on some platforms.
+    ///
+    /// The guard is only rendered if every use of the import is guarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let view = swift::import("UIKit", "UIView").can_import_guard();
+    /// let date = swift::import("Foundation", "Date");
+    ///
+    /// let toks = quote! {
+    ///     $view
+    ///     $date
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "import Foundation",
+    ///         "#if canImport(UIKit)",
+    ///         "import UIKit",
+    ///         "#endif",
+    ///         "",
+    ///         "UIView",
+    ///         "Date",
+    ///     ],
+    ///     toks.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn can_import_guard(self) -> Self {
+        Self {
+            can_import_guard: true,
+            ..self
+        }
+    }
+}
+
+/// How a collected import should be rendered.
+#[derive(Clone, Copy)]
+struct ImportFlags {
+    testable: bool,
+    can_import_guard: bool,
+}
+
+impl ImportFlags {
+    fn merge(&mut self, import: &Import) {
+        self.testable |= import.testable;
+        self.can_import_guard &= import.can_import_guard;
+    }
+}
+
+impl Swift {
+    fn imports(out: &mut Tokens, tokens: &Tokens, config: &Config) {
+        use alloc::collections::BTreeMap;
+
+        let mut modules = BTreeMap::new();
+        let mut kinds = BTreeMap::new();
+
+        for import in tokens.walk_imports_dedup() {
+            let entry = match import.kind {
+                Some(kind) => kinds.entry((&import.module, Some((kind, &import.name)))),
+                None => modules.entry((&import.module, None)),
+            };
+
+            entry
+                .or_insert(ImportFlags {
+                    testable: false,
+                    can_import_guard: true,
+                })
+                .merge(import);
+        }
+
+        if config.subsume_kind_imports {
+            kinds.retain(|(module, _), _| !modules.contains_key(&(*module, None)));
+        }
+
+        let mut testable = Vec::new();
+
+        for (import, flags) in modules.into_iter().chain(kinds) {
+            if flags.testable {
+                testable.push((import, flags));
+            } else {
+                Self::import(out, import, flags);
+            }
+        }
+
+        for (import, flags) in testable {
+            Self::import(out, import, flags);
+        }
+
+        out.line();
+    }
+
+    /// Render a single collected import.
+    fn import(out: &mut Tokens, import: (&ItemStr, Option<(Kind, &ItemStr)>), flags: ImportFlags) {
+        use crate as genco;
+        use crate::quote;
+
+        let (module, item) = import;
+
+        let line = quote! {
+            $(if flags.testable { @testable$[' '] })import $(match item {
+                Some((kind, name)) => $(kind.as_str()) $module.$name,
+                None => $module,
+            })
+        };
+
+        out.push();
+
+        if flags.can_import_guard {
+            out.append(if_config(quote!(canImport($module)), line));
+        } else {
+            out.append(line);
+        }
+    }
+}
+
+/// Wrap the given tokens in a conditional compilation block, like `#if
+/// os(iOS) ... #endif`.
+///
+/// Following Swift convention, the body is not indented. Blocks can be nested,
+/// and further branches can be added with [IfConfig::with_else_if] and
+/// [IfConfig::with_else].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let inner = swift::if_config("targetEnvironment(simulator)", quote! {
+///     let simulator = true
+/// });
+///
+/// let toks: swift::Tokens = quote! {
+///     $(swift::if_config("os(iOS)", quote! {
+///         let platform = "iOS"
+///         $inner
+///     }))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "#if os(iOS)",
+///         "let platform = \"iOS\"",
+///         "#if targetEnvironment(simulator)",
+///         "let simulator = true",
+///         "#endif",
+///         "#endif",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn if_config<C, B>(condition: C, body: B) -> IfConfig
+where
+    C: FormatInto<Swift>,
+    B: FormatInto<Swift>,
+{
+    IfConfig {
+        branches: vec![(
+            if_config::into_tokens(condition),
+            if_config::into_tokens(body),
+        )],
+        otherwise: None,
+    }
+}
+
+/// Format a doc comment where each line is preceeded by `///`.
+///
+/// Sections for parameters, the return value and thrown errors can be added
+/// through [DocComment::with_parameter], [DocComment::with_returns], and
+/// [DocComment::with_throws]. Content is written as-is, so markdown is
+/// preserved and names of types are plain text.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let doc = swift::doc_comment(vec!["Parse a `Date` from a string."])
+///     .with_parameter("input", "The string to parse.")
+///     .with_parameter("format", "The *format* to use.")
+///     .with_returns("The parsed date.")
+///     .with_throws("`ParseError` if the input is invalid.");
+///
+/// let toks: swift::Tokens = quote! {
+///     $doc
+///     func parse(input: String, format: String) throws -> Date
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "/// Parse a `Date` from a string.",
+///         "///",
+///         "/// - Parameter input: The string to parse.",
+///         "/// - Parameter format: The *format* to use.",
+///         "/// - Returns: The parsed date.",
+///         "/// - Throws: `ParseError` if the input is invalid.",
+///         "func parse(input: String, format: String) throws -> Date",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn doc_comment<T>(summary: T) -> DocComment<T>
+where
+    T: IntoIterator,
+    T::Item: Into<ItemStr>,
+{
+    DocComment {
+        summary,
+        parameters: Vec::new(),
+        returns: None,
+        throws: None,
+        width: None,
+    }
+}
+
+/// Quote the given text as a multiline string literal, delimited by `"""`.
+///
+/// Each line of the text is pushed at the current indentation, and the closing
+/// delimiter is placed at the same indentation. Swift strips the indentation of
+/// the closing delimiter from every line, so the literal evaluates to exactly
+/// the given text.
+///
+/// Backslashes and sequences of three quotes are escaped, so the text is never
+/// interpolated and can't terminate the literal. Empty lines are written as
+/// `\n` escapes, since empty lines are collapsed in a token stream.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let text = "Hello \\(name)\n\n\"\"\" quoted \"\"\"";
+///
+/// let toks: swift::Tokens = quote! {
+///     func greeting() -> String {
+///         return $(swift::multiline_string(text))
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "func greeting() -> String {",
+///         "    return \"\"\"",
+///         "    Hello \\\\(name)\\n",
+///         "    \"\"\\\" quoted \"\"\\\"",
+///         "    \"\"\"",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn multiline_string<T>(text: T) -> MultilineString<T>
+where
+    T: AsRef<str>,
+{
+    MultilineString(text)
+}
+
+/// The import of a Swift type `import UIKit`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let toks = quote!($(swift::import("Foo", "Debug")));
+///
+/// assert_eq!(
+///     vec![
+///         "import Foo",
+///         "",
+///         "Debug",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn import<M, N>(module: M, name: N) -> Import
+where
+    M: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        module: module.into(),
+        name: name.into(),
+        kind: None,
+        testable: false,
+        can_import_guard: false,
+    }
+}
+
+/// The import of an individual declaration from a Swift module, like `import
+/// struct Foundation.Date`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let date = swift::import_kind(swift::Kind::Struct, "Foundation", "Date");
+/// let sin = swift::import_kind(swift::Kind::Func, "Darwin", "sin");
+///
+/// let toks = quote! {
+///     $(&date)
+///     $sin
+///     $date
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "import func Darwin.sin",
+///         "import struct Foundation.Date",
+///         "",
+///         "Date",
+///         "sin",
+///         "Date",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn import_kind<M, N>(kind: Kind, module: M, name: N) -> Import
+where
+    M: Into<ItemStr>,
+    N: Into<ItemStr>,
+{
+    Import {
+        module: module.into(),
+        name: name.into(),
+        kind: Some(kind),
+        testable: false,
+        can_import_guard: false,
+    }
+}
@@ -0,0 +1,83 @@
+use crate::lang::Swift;
+use crate::tokens;
+use crate::Tokens;
+use alloc::string::String;
+
+use core::fmt::Write as _;
+
+/// Delimiter of a multiline string literal.
+static DELIMITER: &str = "\"\"\"";
+
+/// Format a multiline string literal, delimited by `"""`.
+///
+/// This struct is created by the [multiline_string][super::multiline_string()]
+/// function.
+pub struct MultilineString<T>(pub(super) T);
+
+impl<T> tokens::FormatInto<Swift> for MultilineString<T>
+where
+    T: AsRef<str>,
+{
+    fn format_into(self, tokens: &mut Tokens<Swift>) {
+        tokens.append(tokens::static_literal(DELIMITER));
+
+        let mut parts = self.0.as_ref().split('\n');
+        let mut current = String::new();
+
+        if let Some(first) = parts.next() {
+            escape(&mut current, first);
+        }
+
+        for part in parts {
+            // Blank lines would be collapsed by the token stream, so a line
+            // break next to an empty line is written as an escape sequence.
+            if current.is_empty() || part.is_empty() {
+                current.push_str("\\n");
+            } else {
+                tokens.push();
+                tokens.append(core::mem::take(&mut current));
+            }
+
+            escape(&mut current, part);
+        }
+
+        if !current.is_empty() {
+            tokens.push();
+            tokens.append(current);
+        }
+
+        tokens.push();
+        tokens.append(tokens::static_literal(DELIMITER));
+    }
+}
+
+/// Escape a single line of a multiline string literal.
+fn escape(out: &mut String, line: &str) {
+    // Number of unescaped quotes in sequence, which must never reach three.
+    let mut quotes = 0;
+
+    for c in line.chars() {
+        match c {
+            '"' if quotes == 2 => {
+                out.push_str("\\\"");
+                quotes = 0;
+                continue;
+            }
+            '"' => {
+                out.push('"');
+                quotes += 1;
+                continue;
+            }
+            '\0' => out.push_str("\\0"),
+            '\\' => out.push_str("\\\\"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push('\t'),
+            c if !c.is_control() => out.push(c),
+            c => {
+                let _ = write!(out, "\\u{{{:x}}}", c as u32);
+            }
+        }
+
+        quotes = 0;
+    }
+}
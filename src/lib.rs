@@ -91,6 +91,20 @@
 //!
 //! <br>
 //!
+//! ## `no_std` support
+//!
+//! genco can be used in `no_std` environments with `alloc` by disabling the
+//! default `std` feature:
+//!
+//! ```toml
+//! genco = { version = "0.17.9", default-features = false }
+//! ```
+//!
+//! This removes [IoWriter][fmt::IoWriter], writing token streams to files, and
+//! the Dart and JavaScript languages which depend on path handling.
+//!
+//! <br>
+//!
 //! ## Rust Example
 //!
 //! The following is a simple program producing Rust code to stdout with custom
@@ -157,6 +171,12 @@
 #![deny(missing_docs)]
 #![deny(rustdoc::broken_intra_doc_links)]
 #![allow(clippy::needless_doctest_main)]
+#![no_std]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
 
 /// Whitespace sensitive quasi-quoting.
 ///
@@ -171,6 +191,7 @@
 /// > information.
 ///
 /// ```
+/// # #[cfg(feature = "std")] {
 /// use genco::prelude::*;
 ///
 /// let hash_map = &dart::import("dart:collection", "HashMap");
@@ -186,6 +207,7 @@
 /// };
 ///
 /// println!("{}", tokens.to_file_string()?);
+/// # }
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
 ///
@@ -285,6 +307,10 @@
 ///   following tokens have an empty line separating them. This corresponds to
 ///   the [Tokens::line] function.
 ///
+/// * `$[soft]` — Inserts a soft break, which is a space at which the line may
+///   be broken if it would otherwise exceed the configured maximum width. This
+///   corresponds to the [Tokens::soft_break] function.
+///
 /// ```
 /// use genco::prelude::*;
 ///
@@ -398,12 +424,14 @@
 /// at compile time we can instead make use of `$[const](<content>)` like you can see with the smile below:
 ///
 /// ```
+/// # #[cfg(feature = "std")] {
 /// use genco::prelude::*;
 ///
 /// let smile = "😊";
 ///
 /// let t: js::Tokens = quote!($[str](Hello $[const](smile) $world));
 /// assert_eq!("`Hello 😊 ${world}`", t.to_string()?);
+/// # }
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
 ///
@@ -414,6 +442,7 @@
 /// have a space preserved at the end.
 ///
 /// ```
+/// # #[cfg(feature = "std")] {
 /// use genco::prelude::*;
 ///
 /// let smile = "😊";
@@ -426,6 +455,7 @@
 ///
 /// let t: js::Tokens = quote!($[str](Hello $[const](smile) $(world)));
 /// assert_eq!("`Hello 😊 ${world}`", t.to_string()?);
+/// # }
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
 ///
@@ -440,6 +470,7 @@
 /// macro for convenience. The supported mechanisms are:
 ///
 /// * [Loops](#loops) - `$(for <bindings> in <expr> [join (<quoted>)] => <quoted>)`.
+/// * [While Loops](#while-loops) - `$(while <condition> => <quoted>)`.
 /// * [Conditionals](#conditionals) - `$(if <pattern> => <quoted>)`.
 /// * [Match Statements](#match-statements) - `$(match <expr> { [<pattern> => <quoted>,]* })`.
 ///
@@ -498,6 +529,31 @@
 ///
 /// <br>
 ///
+/// # While Loops
+///
+/// To repeat a pattern for as long as a condition holds you can use
+/// `$(while <condition> { <quoted> })`, or the more compact `$(while
+/// <condition> => <quoted>)`. `<condition>` is anything which is valid as the
+/// condition of a `while` loop in Rust, including `let` patterns.
+///
+/// Like with [loops](#loops), anything bound by `<condition>` is made
+/// available to `<quoted>`.
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let mut it = vec!["a", "b", "c", "stop", "d"].into_iter();
+///
+/// let tokens: Tokens<()> = quote! {
+///     Letters: $(while let Some(letter) = it.next().filter(|l| *l != "stop") => $letter$[' '])
+/// };
+///
+/// assert_eq!("Letters: a b c", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+///
+/// <br>
+///
 /// # Conditionals
 ///
 /// You can specify a conditional with `$(if <pattern> => <then>)` where
@@ -915,6 +971,12 @@ pub use genco_macros::quote_fn;
 /// reference like `&mut rust::Tokens` will have to be dereferenced when used
 /// with this macro.
 ///
+/// Any target implementing [QuoteTarget] can be used, which includes token
+/// streams and raw vectors of [Item]s.
+///
+/// [QuoteTarget]: tokens::QuoteTarget
+/// [Item]: tokens::Item
+///
 /// ```
 /// # use genco::prelude::*;
 ///
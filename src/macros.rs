@@ -156,30 +156,42 @@ macro_rules! impl_lang {
                     $(Self::$ty(lang) => lang.format(out, config, format),)*
                 }
             }
+
+            fn reserved_name(&self) -> Option<&str> {
+                match self {
+                    $(Self::$ty(lang) => lang.reserved_name(),)*
+                }
+            }
+
+            fn scoped_tokens(&self) -> Option<&$crate::Tokens<$lang>> {
+                match self {
+                    $(Self::$ty(lang) => lang.scoped_tokens(),)*
+                }
+            }
         }
 
         $(
             impl $crate::tokens::FormatInto<$lang> for $ty {
                 fn format_into(self, tokens: &mut $crate::Tokens<$lang>) {
-                    tokens.append($crate::tokens::__lang_item::<$lang>(Box::new(self.into())));
+                    tokens.append($crate::tokens::__lang_item::<$lang>(self.into()));
                 }
             }
 
             impl<'a> $crate::tokens::FormatInto<$lang> for &'a $ty {
                 fn format_into(self, tokens: &mut $crate::Tokens<$lang>) {
-                    tokens.append($crate::tokens::__lang_item::<$lang>(Box::new(self.clone().into())));
+                    tokens.append($crate::tokens::__lang_item::<$lang>(self.clone().into()));
                 }
             }
 
             impl $crate::tokens::Register<$lang> for $ty {
                 fn register(self, tokens: &mut $crate::Tokens<$lang>) {
-                    tokens.append($crate::tokens::__lang_item_register::<$lang>(Box::new(self.into())));
+                    tokens.append($crate::tokens::__lang_item_register::<$lang>(self.into()));
                 }
             }
 
             impl<'a> $crate::tokens::Register<$lang> for &'a $ty {
                 fn register(self, tokens: &mut $crate::Tokens<$lang>) {
-                    tokens.append($crate::tokens::__lang_item_register::<$lang>(Box::new(self.clone().into())));
+                    tokens.append($crate::tokens::__lang_item_register::<$lang>(self.clone().into()));
                 }
             }
 
@@ -189,3 +201,58 @@ macro_rules! impl_lang {
         )*
     }
 }
+
+/// Assert that two token streams are equal when formatted as files.
+///
+/// Either side can be a token stream, which is formatted using
+/// [Tokens::to_file_string], or a string. Lines are compared without regard
+/// to the trailing line ending.
+///
+/// If they are not equal, this panics with a line diff which marks the first
+/// differing line and makes trailing whitespace visible.
+///
+/// [Tokens::to_file_string]: crate::Tokens::to_file_string
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::assert_tokens_eq;
+///
+/// let map = rust::import("std::collections", "HashMap");
+///
+/// let tokens: rust::Tokens = quote! {
+///     let m = $map::new();
+/// };
+///
+/// assert_tokens_eq!(
+///     "use std::collections::HashMap;\n\nlet m = HashMap::new();",
+///     tokens
+/// );
+///
+/// let other: rust::Tokens = quote! {
+///     use std::collections::HashMap;
+///
+///     let m = HashMap::new();
+/// };
+///
+/// assert_tokens_eq!(other, tokens);
+/// ```
+///
+/// A failing assertion panics with a diff like this:
+///
+/// ```text
+/// tokens are not equal, first difference at line 2
+/// --- expected
+/// +++ actual
+///    fn foo() {
+/// >-     baz();·
+///  +     bar();
+///    }
+/// ```
+#[macro_export]
+macro_rules! assert_tokens_eq {
+    ($expected:expr, $actual:expr $(,)?) => {
+        $crate::tokens::__assert_tokens_eq(&$expected, &$actual)
+    };
+}
@@ -0,0 +1,191 @@
+use crate::lang::Lang;
+use crate::Tokens;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+/// Values which can be compared using [assert_tokens_eq!].
+///
+/// This must only be used by the [assert_tokens_eq!] macro.
+///
+/// [assert_tokens_eq!]: crate::assert_tokens_eq!
+pub trait __AssertTokens {
+    /// Render the value as a file.
+    fn __render(&self) -> String;
+}
+
+impl<L> __AssertTokens for Tokens<L>
+where
+    L: Lang,
+    L::Config: Default,
+{
+    fn __render(&self) -> String {
+        match self.to_file_string() {
+            Ok(string) => string,
+            Err(error) => panic!("failed to format tokens: {}", error),
+        }
+    }
+}
+
+impl __AssertTokens for str {
+    fn __render(&self) -> String {
+        self.to_owned()
+    }
+}
+
+impl __AssertTokens for String {
+    fn __render(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<T> __AssertTokens for &T
+where
+    T: ?Sized + __AssertTokens,
+{
+    fn __render(&self) -> String {
+        (**self).__render()
+    }
+}
+
+/// Compare two values as files, panicking with a line diff if they differ.
+///
+/// This must only be used by the [assert_tokens_eq!] macro.
+///
+/// [assert_tokens_eq!]: crate::assert_tokens_eq!
+#[track_caller]
+pub fn __assert_tokens_eq<E, A>(expected: &E, actual: &A)
+where
+    E: ?Sized + __AssertTokens,
+    A: ?Sized + __AssertTokens,
+{
+    let expected = expected.__render();
+    let actual = actual.__render();
+
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+
+    if expected == actual {
+        return;
+    }
+
+    panic!("{}", diff(&expected, &actual));
+}
+
+/// The largest table used to diff the lines which differ, beyond which
+/// they are shown as removed and added in full.
+const MAX_TABLE: usize = 1 << 20;
+
+/// Produce a line diff between the expected and actual lines.
+fn diff(expected: &[&str], actual: &[&str]) -> String {
+    // NB: only diff lines between the common prefix and suffix, which is
+    // usually a small part of a file.
+    let prefix = expected
+        .iter()
+        .zip(actual)
+        .take_while(|(e, a)| e == a)
+        .count();
+
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(e, a)| e == a)
+        .count();
+
+    let mut lines = Vec::new();
+    lines.extend(expected[..prefix].iter().map(|line| (' ', *line)));
+
+    diff_lines(
+        &expected[prefix..expected.len() - suffix],
+        &actual[prefix..actual.len() - suffix],
+        &mut lines,
+    );
+
+    lines.extend(
+        expected[expected.len() - suffix..]
+            .iter()
+            .map(|line| (' ', *line)),
+    );
+
+    let mut out = String::new();
+
+    let _ = writeln!(
+        out,
+        "tokens are not equal, first difference at line {}",
+        prefix + 1
+    );
+    let _ = writeln!(out, "--- expected");
+    let _ = writeln!(out, "+++ actual");
+
+    let mut marked = false;
+
+    for (sign, line) in lines {
+        let marker = if sign != ' ' && !marked { '>' } else { ' ' };
+        marked |= sign != ' ';
+
+        let _ = writeln!(out, "{}{} {}", marker, sign, visible(line));
+    }
+
+    out
+}
+
+/// Diff lines which differ at both ends using a longest common subsequence
+/// table.
+fn diff_lines<'a>(expected: &[&'a str], actual: &[&'a str], lines: &mut Vec<(char, &'a str)>) {
+    let size = (expected.len() + 1).saturating_mul(actual.len() + 1);
+
+    if size > MAX_TABLE {
+        lines.extend(expected.iter().map(|line| ('-', *line)));
+        lines.extend(actual.iter().map(|line| ('+', *line)));
+        return;
+    }
+
+    // Longest common subsequence table, where lcs[i][j] is the length of the
+    // longest common subsequence of expected[i..] and actual[j..].
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                usize::max(lcs[i + 1][j], lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            lines.push((' ', expected[i]));
+            i += 1;
+            j += 1;
+        } else if i < expected.len() && (j == actual.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(('-', expected[i]));
+            i += 1;
+        } else {
+            lines.push(('+', actual[j]));
+            j += 1;
+        }
+    }
+}
+
+/// Make trailing whitespace in a line visible.
+fn visible(line: &str) -> String {
+    let trimmed = line.trim_end();
+    let mut out = String::from(trimmed);
+
+    for c in line[trimmed.len()..].chars() {
+        out.push(match c {
+            ' ' => '·',
+            '\t' => '→',
+            c => c,
+        });
+    }
+
+    out
+}
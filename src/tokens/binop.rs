@@ -0,0 +1,61 @@
+use crate::lang::Lang;
+use crate::tokens::{FormatInto, Item};
+use crate::Tokens;
+
+/// Format a binary operation, like `a + b`.
+///
+/// Whether the operator is surrounded by spaces is decided when formatting,
+/// through [fmt::Config::with_operator_spacing]. Spaces are written by
+/// default.
+///
+/// [fmt::Config::with_operator_spacing]: crate::fmt::Config::with_operator_spacing
+///
+/// # Examples
+///
+/// ```
+/// use genco::fmt;
+/// use genco::prelude::*;
+/// use genco::tokens::binop;
+///
+/// let tokens: rust::Tokens = quote! {
+///     let c = $(binop("a", "+", "b"));
+/// };
+///
+/// assert_eq!("let c = a + b;", tokens.to_string()?);
+///
+/// let mut w = fmt::FmtWriter::new(String::new());
+/// let fmt = fmt::Config::from_lang::<Rust>().with_operator_spacing(false);
+/// tokens.format_file(&mut w.as_formatter(&fmt), &rust::Config::default())?;
+///
+/// assert_eq!("let c = a+b;\n", w.into_inner());
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn binop<A, O, B>(lhs: A, op: O, rhs: B) -> Binop<A, O, B> {
+    Binop { lhs, op, rhs }
+}
+
+/// A binary operation.
+///
+/// This is constructed with the [binop()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct Binop<A, O, B> {
+    lhs: A,
+    op: O,
+    rhs: B,
+}
+
+impl<L, A, O, B> FormatInto<L> for Binop<A, O, B>
+where
+    L: Lang,
+    A: FormatInto<L>,
+    O: FormatInto<L>,
+    B: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        tokens.append(self.lhs);
+        tokens.item(Item::OperatorSpace);
+        tokens.append(self.op);
+        tokens.item(Item::OperatorSpace);
+        tokens.append(self.rhs);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::lang::Lang;
+use crate::tokens::{static_literal, FormatInto};
+use crate::Tokens;
+
+/// The placement of the opening brace of a [Block].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BraceStyle {
+    /// The opening brace is placed on the same line as the preceeding
+    /// tokens, like `fn foo() {`.
+    #[default]
+    KAndR,
+    /// The opening brace is placed on its own line.
+    Allman,
+}
+
+/// Construct a brace-delimited block with an indented body.
+///
+/// The block is rendered as `{`, followed by the body indented on its own
+/// lines, followed by `}` on its own line. An empty body renders as `{}`.
+///
+/// Where the opening brace is placed is determined by its [BraceStyle], which
+/// can be set using [with_brace_style][Block::with_brace_style].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::{block, BraceStyle};
+///
+/// let body: java::Tokens = quote!(return 42;);
+///
+/// let tokens: java::Tokens = quote! {
+///     public int foo() $(block(&body))
+///
+///     public int bar() $(block(&body).with_brace_style(BraceStyle::Allman))
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "public int foo() {",
+///         "    return 42;",
+///         "}",
+///         "",
+///         "public int bar()",
+///         "{",
+///         "    return 42;",
+///         "}",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn block<T>(body: T) -> Block<T> {
+    Block {
+        body,
+        brace_style: BraceStyle::default(),
+    }
+}
+
+/// A brace-delimited block.
+///
+/// This is constructed with the [block()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct Block<T> {
+    body: T,
+    brace_style: BraceStyle,
+}
+
+impl<T> Block<T> {
+    /// Set the brace style of the block.
+    pub fn with_brace_style(self, brace_style: BraceStyle) -> Self {
+        Self {
+            brace_style,
+            ..self
+        }
+    }
+}
+
+impl<L, T> FormatInto<L> for Block<T>
+where
+    L: Lang,
+    T: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        if let BraceStyle::Allman = self.brace_style {
+            tokens.push();
+        }
+
+        tokens.append(static_literal("{"));
+        tokens.indent();
+        tokens.append(self.body);
+        tokens.unindent();
+        tokens.append(static_literal("}"));
+    }
+}
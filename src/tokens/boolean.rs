@@ -0,0 +1,41 @@
+use crate::lang::Lang;
+use crate::tokens::{static_literal, FormatInto};
+use crate::Tokens;
+
+/// Format a boolean literal of the target language, as provided by
+/// [Lang::bool_literal].
+///
+/// This allows templates which are shared between languages to render the
+/// appropriate literal, like `true` in Rust or `True` in Python.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::boolean;
+///
+/// let rust: rust::Tokens = quote!(let enabled = $(boolean(true)););
+/// assert_eq!("let enabled = true;", rust.to_string()?);
+///
+/// let python: python::Tokens = quote!(enabled = $(boolean(false)));
+/// assert_eq!("enabled = False", python.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn boolean(value: bool) -> Boolean {
+    Boolean(value)
+}
+
+/// A boolean literal of the target language.
+///
+/// This is constructed with the [boolean()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct Boolean(bool);
+
+impl<L> FormatInto<L> for Boolean
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        tokens.append(static_literal(L::bool_literal(self.0)));
+    }
+}
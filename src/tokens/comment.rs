@@ -0,0 +1,98 @@
+use crate::lang::Lang;
+use crate::tokens::{static_literal, FormatInto, ItemStr};
+use crate::Tokens;
+
+/// Format line comments using the comment syntax of the target language, as
+/// provided by [Lang::line_comment].
+///
+/// Each line is placed on its own line, and lines containing newlines are
+/// split into multiple comment lines.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::comment;
+///
+/// let rust: rust::Tokens = quote! {
+///     $(comment(["Hello", "World\nand everyone"]))
+///     fn foo() {}
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "// Hello",
+///         "// World",
+///         "// and everyone",
+///         "fn foo() {}",
+///     ],
+///     rust.to_file_vec()?
+/// );
+///
+/// let python: python::Tokens = quote! {
+///     $(comment(["Hello", "", "World"]))
+///     def foo():
+///         pass
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "# Hello",
+///         "#",
+///         "# World",
+///         "def foo():",
+///         "    pass",
+///     ],
+///     python.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn comment<T>(lines: T) -> Comment<T>
+where
+    T: IntoIterator,
+    T::Item: Into<ItemStr>,
+{
+    Comment { lines }
+}
+
+/// Line comments in the syntax of the target language.
+///
+/// This is constructed with the [comment()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct Comment<T> {
+    lines: T,
+}
+
+impl<L, T> FormatInto<L> for Comment<T>
+where
+    L: Lang,
+    T: IntoIterator,
+    T::Item: Into<ItemStr>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        for line in self.lines {
+            let line = line.into();
+
+            if line.contains('\n') {
+                for line in line.split('\n') {
+                    format_line(tokens, ItemStr::from(line));
+                }
+            } else {
+                format_line(tokens, line);
+            }
+        }
+
+        fn format_line<L>(tokens: &mut Tokens<L>, line: ItemStr)
+        where
+            L: Lang,
+        {
+            tokens.push();
+            tokens.append(static_literal(L::line_comment()));
+
+            if !line.is_empty() {
+                tokens.space();
+                tokens.append(line);
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::lang::Lang;
+use crate::tokens::{FormatInto, Item, ItemStr};
+use crate::Tokens;
+use core::fmt;
+
+/// Function to build a literal from a type implementing [Debug][fmt::Debug].
+///
+/// This works like [display()][crate::tokens::display()], but uses the
+/// [Debug][fmt::Debug] implementation of the value instead. Like with
+/// [display()][crate::tokens::display()] the result is a literal, so the
+/// output is not escaped or quoted according to the target language.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let values = vec![1u32, 2, 3];
+///
+/// let tokens: rust::Tokens = quote! {
+///     let values = vec!$(debug(&values));
+/// };
+///
+/// assert_eq!("let values = vec![1, 2, 3];", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn debug<T>(inner: T) -> Debug<T>
+where
+    T: fmt::Debug,
+{
+    Debug { inner }
+}
+
+/// Struct containing a type that implements [Debug][fmt::Debug] and can be
+/// tokenized into a stream.
+///
+/// This is constructed with the [debug()] function.
+#[derive(Clone, Copy)]
+pub struct Debug<T> {
+    inner: T,
+}
+
+impl<T, L> FormatInto<L> for Debug<T>
+where
+    L: Lang,
+    T: fmt::Debug,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        tokens.item(Item::Literal(ItemStr::from_fmt(format_args!(
+            "{:?}",
+            self.inner
+        ))));
+    }
+}
@@ -0,0 +1,62 @@
+use crate::lang::Lang;
+use crate::tokens::FormatInto;
+use crate::Tokens;
+
+/// Construct a block between an opening and a closing delimiter, with an
+/// indented body.
+///
+/// This is like [block()] but with arbitrary delimiters. The body is placed
+/// indented on its own lines, and the closing delimiter on its own line. An
+/// empty body renders as just the delimiters.
+///
+/// [block()]: crate::tokens::block()
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::delimited;
+///
+/// let tokens: rust::Tokens = quote! {
+///     let a = $(delimited("[", quote!(1, 2), "]"));
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "let a = [",
+///         "    1, 2",
+///         "];",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn delimited<O, T, C>(open: O, body: T, close: C) -> Delimited<O, T, C> {
+    Delimited { open, body, close }
+}
+
+/// A block between delimiters.
+///
+/// This is constructed with the [delimited()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct Delimited<O, T, C> {
+    open: O,
+    body: T,
+    close: C,
+}
+
+impl<L, O, T, C> FormatInto<L> for Delimited<O, T, C>
+where
+    L: Lang,
+    O: FormatInto<L>,
+    T: FormatInto<L>,
+    C: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        tokens.append(self.open);
+        tokens.indent();
+        tokens.append(self.body);
+        tokens.unindent();
+        tokens.append(self.close);
+    }
+}
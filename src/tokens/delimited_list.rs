@@ -0,0 +1,97 @@
+use crate::lang::Lang;
+use crate::tokens::{static_literal, FormatInto, Item};
+use crate::Tokens;
+
+/// Construct a multi-line list of comma-separated items between an opening
+/// and a closing delimiter.
+///
+/// Each item is placed on its own indented line. Whether a comma is added
+/// after the last item is decided when formatting, through
+/// [fmt::Config::with_trailing_comma]. An empty list is rendered as just the
+/// delimiters.
+///
+/// [fmt::Config::with_trailing_comma]: crate::fmt::Config::with_trailing_comma
+///
+/// # Examples
+///
+/// ```
+/// use genco::fmt;
+/// use genco::prelude::*;
+/// use genco::tokens::delimited_list;
+///
+/// let tokens: rust::Tokens = quote! {
+///     let values = vec!$(delimited_list("[", ["1", "2"], "]"));
+/// };
+///
+/// let mut w = fmt::VecWriter::new();
+/// let fmt = fmt::Config::from_lang::<Rust>().with_trailing_comma(true);
+/// tokens.format_file(&mut w.as_formatter(&fmt), &rust::Config::default())?;
+///
+/// assert_eq!(
+///     vec![
+///         "let values = vec![",
+///         "    1,",
+///         "    2,",
+///         "];",
+///     ],
+///     w.into_vec()
+/// );
+///
+/// assert_eq!(
+///     vec![
+///         "let values = vec![",
+///         "    1,",
+///         "    2",
+///         "];",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn delimited_list<O, I, C>(open: O, items: I, close: C) -> DelimitedList<O, I, C>
+where
+    I: IntoIterator,
+{
+    DelimitedList { open, items, close }
+}
+
+/// A multi-line list of items between delimiters.
+///
+/// This is constructed with the [delimited_list()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct DelimitedList<O, I, C> {
+    open: O,
+    items: I,
+    close: C,
+}
+
+impl<L, O, I, C> FormatInto<L> for DelimitedList<O, I, C>
+where
+    L: Lang,
+    O: FormatInto<L>,
+    I: IntoIterator,
+    I::Item: FormatInto<L>,
+    C: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        tokens.append(self.open);
+        tokens.indent();
+
+        let mut it = self.items.into_iter();
+
+        if let Some(first) = it.next() {
+            tokens.append(first);
+
+            for item in it {
+                tokens.append(static_literal(","));
+                tokens.push();
+                tokens.append(item);
+            }
+
+            tokens.item(Item::TrailingComma);
+        }
+
+        tokens.unindent();
+        tokens.append(self.close);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::lang::Lang;
-use crate::tokens::{FormatInto, Item};
+use crate::tokens::{FormatInto, Item, ItemStr};
 use crate::Tokens;
-use std::fmt;
+use core::fmt;
 
 /// Function to build a string literal.
 ///
@@ -12,6 +12,14 @@ use std::fmt;
 /// On the other hand, things implementing [tokens::FormatInto] have access to the
 /// full range of the [Tokens] api, allowing it to work more efficiently.
 ///
+/// The value is formatted once when it's interpolated, and the result is
+/// stored as a literal in the token stream. The output is written straight
+/// into the stored literal, so unlike calling `to_string()` no intermediate
+/// [String][alloc::string::String] is allocated. Since it's a literal it's
+/// written as-is, unlike [quoted()] which produces a string quoted and escaped
+/// according to the target language.
+///
+/// [quoted()]: crate::tokens::quoted()
 /// [tokens::FormatInto]: crate::tokens::FormatInto
 /// [Tokens]: crate::Tokens
 ///
@@ -73,8 +81,9 @@ where
     T: fmt::Display,
 {
     fn format_into(self, tokens: &mut Tokens<L>) {
-        tokens.item(Item::Literal(
-            self.inner.to_string().into_boxed_str().into(),
-        ));
+        tokens.item(Item::Literal(ItemStr::from_fmt(format_args!(
+            "{}",
+            self.inner
+        ))));
     }
 }
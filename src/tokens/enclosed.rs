@@ -0,0 +1,86 @@
+use crate::lang::Lang;
+use crate::tokens::{static_literal, FormatInto};
+use crate::Tokens;
+
+/// Enclose the given tokens in parenthesis, like `(inner)`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::parens;
+///
+/// let tokens: rust::Tokens = quote!(let a = 2 * $(parens(quote!(1 + 2))););
+/// assert_eq!("let a = 2 * (1 + 2);", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn parens<T>(inner: T) -> Enclosed<T> {
+    Enclosed {
+        open: "(",
+        inner,
+        close: ")",
+    }
+}
+
+/// Enclose the given tokens in brackets, like `[inner]`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::brackets;
+///
+/// let tokens: rust::Tokens = quote!(let a = $(brackets(quote!(1, 2))););
+/// assert_eq!("let a = [1, 2];", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn brackets<T>(inner: T) -> Enclosed<T> {
+    Enclosed {
+        open: "[",
+        inner,
+        close: "]",
+    }
+}
+
+/// Enclose the given tokens in angle brackets, like `<inner>`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::angled;
+///
+/// let tokens: rust::Tokens = quote!(let a = Vec::$(angled("u32"))::new(););
+/// assert_eq!("let a = Vec::<u32>::new();", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn angled<T>(inner: T) -> Enclosed<T> {
+    Enclosed {
+        open: "<",
+        inner,
+        close: ">",
+    }
+}
+
+/// Tokens enclosed on a single line by an opening and a closing delimiter.
+///
+/// This is constructed with the [parens()], [brackets()], or [angled()]
+/// functions.
+#[derive(Debug, Clone, Copy)]
+pub struct Enclosed<T> {
+    open: &'static str,
+    inner: T,
+    close: &'static str,
+}
+
+impl<L, T> FormatInto<L> for Enclosed<T>
+where
+    L: Lang,
+    T: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        tokens.append(static_literal(self.open));
+        tokens.append(self.inner);
+        tokens.append(static_literal(self.close));
+    }
+}
@@ -0,0 +1,77 @@
+use crate::lang::Lang;
+use crate::tokens::{join, FormatInto};
+use crate::Tokens;
+
+/// Format the entries of a map, like a [BTreeMap] or a [HashMap], inserting
+/// the given separator between each of them.
+///
+/// Each entry is converted into tokens through the provided function, which
+/// receives the key and the value of the entry. Entries are formatted in the
+/// order the map iterates over them, so a [BTreeMap] produces deterministic
+/// output sorted by key while a [HashMap] does not.
+///
+/// [BTreeMap]: alloc::collections::BTreeMap
+/// [HashMap]: https://doc.rust-lang.org/std/collections/struct.HashMap.html
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use genco::prelude::*;
+/// use genco::tokens::entries;
+///
+/// let mut map = BTreeMap::new();
+/// map.insert("port", "8080");
+/// map.insert("host", "localhost");
+///
+/// let tokens: python::Tokens = quote! {
+///     config = {
+///         $(entries(&map, quote_fn!(,$['\r']), |key, value| quote!($(quoted(*key)): $(quoted(*value)))))
+///     }
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "config = {",
+///         "    \"host\": \"localhost\",",
+///         "    \"port\": \"8080\"",
+///         "}",
+///     ],
+///     tokens.to_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn entries<M, K, V, S, F, T>(map: M, separator: S, f: F) -> Entries<M, S, F>
+where
+    M: IntoIterator<Item = (K, V)>,
+    S: Clone,
+    F: FnMut(K, V) -> T,
+{
+    Entries { map, separator, f }
+}
+
+/// The entries of a map joined by a separator.
+///
+/// This is constructed with the [entries()] function.
+#[derive(Clone, Copy)]
+pub struct Entries<M, S, F> {
+    map: M,
+    separator: S,
+    f: F,
+}
+
+impl<L, M, K, V, S, F, T> FormatInto<L> for Entries<M, S, F>
+where
+    L: Lang,
+    M: IntoIterator<Item = (K, V)>,
+    S: FormatInto<L> + Clone,
+    F: FnMut(K, V) -> T,
+    T: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        let mut f = self.f;
+        let it = self.map.into_iter().map(|(key, value)| f(key, value));
+        tokens.append(join(it, self.separator));
+    }
+}
@@ -1,7 +1,11 @@
 use crate::lang::Lang;
 use crate::tokens::{Item, ItemStr, Tokens};
-use std::fmt::Arguments;
-use std::rc::Rc;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::Arguments;
 
 /// Trait for types that can be formatted in-place into a token stream.
 ///
@@ -51,6 +55,13 @@ where
     L: Lang,
 {
     fn format_into(self, tokens: &mut Self) {
+        #[cfg(feature = "locations")]
+        {
+            let locations = self.locations().to_vec();
+            tokens.extend_located(self, &locations);
+        }
+
+        #[cfg(not(feature = "locations"))]
         tokens.extend(self);
     }
 }
@@ -70,15 +81,59 @@ where
 /// assert_eq!("foo bar baz", result.to_string()?);
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
-impl<'a, L> FormatInto<L> for &'a Tokens<L>
+impl<L> FormatInto<L> for &Tokens<L>
 where
     L: Lang,
 {
     fn format_into(self, tokens: &mut Tokens<L>) {
+        #[cfg(feature = "locations")]
+        tokens.extend_located(self.iter().cloned(), self.locations());
+
+        #[cfg(not(feature = "locations"))]
         tokens.extend(self.iter().cloned());
     }
 }
 
+/// Formatting a shared token stream appends it without copying its items.
+///
+/// See [Tokens::append_shared].
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use genco::prelude::*;
+///
+/// let a: Arc<Tokens> = Arc::new(quote!(foo bar));
+///
+/// let result = quote!($(a.clone()) baz);
+///
+/// assert_eq!("foo bar baz", result.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+impl<L> FormatInto<L> for Arc<Tokens<L>>
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        tokens.append_shared(self);
+    }
+}
+
+/// Formatting a reference to a shared token stream appends a new reference
+/// to it without copying its items.
+///
+/// See [Tokens::append_shared].
+impl<L> FormatInto<L> for &Arc<Tokens<L>>
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        tokens.append_shared(self.clone());
+    }
+}
+
 /// Formatting a vector of token streams is like formatting each, one after
 /// another.
 ///
@@ -126,7 +181,7 @@ where
 /// assert_eq!("foo bar baz", result.to_string()?);
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
-impl<'a, L, T> FormatInto<L> for &'a [T]
+impl<L, T> FormatInto<L> for &[T]
 where
     L: Lang,
     T: Clone + FormatInto<L>,
@@ -153,7 +208,7 @@ where
 /// assert_eq!("foo bar baz", result.to_string()?);
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
-impl<'a, L> FormatInto<L> for &'a str
+impl<L> FormatInto<L> for &str
 where
     L: Lang,
 {
@@ -177,7 +232,7 @@ where
 /// assert_eq!("foo bar baz", result.to_string()?);
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
-impl<'a, L> FormatInto<L> for &'a String
+impl<L> FormatInto<L> for &String
 where
     L: Lang,
 {
@@ -253,7 +308,7 @@ where
 /// assert_eq!("foo bar baz", result.to_string()?);
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
-impl<'a, L> FormatInto<L> for &'a Rc<String>
+impl<L> FormatInto<L> for &Rc<String>
 where
     L: Lang,
 {
@@ -262,6 +317,36 @@ where
     }
 }
 
+/// Formatting a character appends it as a literal.
+///
+/// Note that this applies to whitespace characters as well, so `' '` is
+/// written as a literal space rather than being treated as spacing between
+/// tokens. Use [Tokens::space] for that.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::{Item, ItemStr};
+///
+/// let result: Tokens = quote!(a $('+') b);
+/// assert_eq!("a + b", result.to_string()?);
+///
+/// let result: Tokens = quote!(a$(' ')b);
+/// assert_eq!(Some(&Item::Literal(ItemStr::from(" "))), result.get(1));
+/// assert_eq!("a b", result.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+impl<L> FormatInto<L> for char
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        let mut buf = [0; 4];
+        tokens.item(Item::Literal(ItemStr::from(&*self.encode_utf8(&mut buf))));
+    }
+}
+
 /// Implementation for [Arguments] which allows for arbitrary and efficient
 /// literal formatting.
 ///
@@ -281,7 +366,7 @@ where
     L: Lang,
 {
     fn format_into(self, tokens: &mut Tokens<L>) {
-        tokens.item(Item::Literal(ItemStr::from(self.to_string())));
+        tokens.item(Item::Literal(ItemStr::from_fmt(self)));
     }
 }
 
@@ -314,6 +399,59 @@ where
     }
 }
 
+/// Formatting a reference to an optional item formats a reference to the item
+/// if it is present.
+///
+/// This allows an optional token stream to be interpolated more than once
+/// without cloning it.
+///
+/// There is no implementation for [Result], since an error can't be
+/// reported during interpolation. Use `?` inside of the interpolation instead
+/// to propagate the error from the surrounding function.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let derive: Option<rust::Tokens> = Some(quote!(#[derive(Debug)]));
+/// let missing: Option<rust::Tokens> = None;
+///
+/// let field = "4".parse::<u32>().map_err(|_| std::fmt::Error);
+///
+/// let result: rust::Tokens = quote! {
+///     $(&derive)
+///     $(&missing)
+///     struct A($(field?));
+///
+///     $(&derive)
+///     struct B;
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         "#[derive(Debug)]",
+///         "struct A(4);",
+///         "",
+///         "#[derive(Debug)]",
+///         "struct B;",
+///     ],
+///     result.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+impl<'a, L, T> FormatInto<L> for &'a Option<T>
+where
+    L: Lang,
+    &'a T: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        if let Some(inner) = self {
+            inner.format_into(tokens);
+        }
+    }
+}
+
 macro_rules! impl_display {
     ($($ty:ty),*) => {
         $(
@@ -23,6 +23,40 @@ use crate::Tokens;
 /// let _: Tokens<Rust> = quote!($c $['\n'] $c);
 /// # Ok::<_, genco::fmt::Error>(())
 /// ```
+///
+/// The constructed value implements [Clone] and [Copy] if the function does,
+/// so it can be interpolated many times.
+///
+/// # Registering imports
+///
+/// The function has full access to the token stream it's being formatted
+/// into, so it can [register][Tokens::register] imports without rendering
+/// them. These are collected like any other import when the file is
+/// formatted.
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::from_fn;
+///
+/// let display = rust::import("std::fmt", "Display");
+///
+/// let impl_display = from_fn(move |t: &mut rust::Tokens| {
+///     t.register(display);
+///     t.append("impl fmt::Display for Foo {}");
+/// });
+///
+/// let tokens: rust::Tokens = quote!($impl_display);
+///
+/// assert_eq!(
+///     vec![
+///         "use std::fmt::Display;",
+///         "",
+///         "impl fmt::Display for Foo {}",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
 pub fn from_fn<F, L>(f: F) -> FromFn<F>
 where
     F: FnOnce(&mut Tokens<L>),
@@ -48,3 +82,47 @@ where
         (self.f)(tokens);
     }
 }
+
+/// Construct tokens from a fallible function.
+///
+/// Unlike [from_fn()] the function is called immediately, since the error has
+/// to be handled before the tokens can be used. The tokens are only returned
+/// if the function succeeds.
+///
+/// # Examples
+///
+/// ```
+/// use genco::fmt;
+/// use genco::prelude::*;
+/// use genco::tokens::try_from_fn;
+///
+/// fn field(name: &str) -> fmt::Result<rust::Tokens> {
+///     try_from_fn(|t| {
+///         if name.is_empty() {
+///             return Err(std::fmt::Error);
+///         }
+///
+///         quote_in!(*t => $name: u32,);
+///         Ok(())
+///     })
+/// }
+///
+/// let tokens: rust::Tokens = quote! {
+///     struct Foo {
+///         $(field("a")?)
+///     }
+/// };
+///
+/// assert_eq!(vec!["struct Foo {", "    a: u32,", "}"], tokens.to_file_vec()?);
+/// assert!(field("").is_err());
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn try_from_fn<F, L, E>(f: F) -> Result<Tokens<L>, E>
+where
+    F: FnOnce(&mut Tokens<L>) -> Result<(), E>,
+    L: Lang,
+{
+    let mut tokens = Tokens::new();
+    f(&mut tokens)?;
+    Ok(tokens)
+}
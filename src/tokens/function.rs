@@ -0,0 +1,135 @@
+use crate::lang::{Lang, LangFunction};
+use crate::tokens::{FormatInto, ItemStr};
+use crate::Tokens;
+use alloc::vec::Vec;
+
+/// A function assembled from a name, parameters, a return type and a body.
+///
+/// The function is rendered in the syntax of the language it's formatted
+/// into, as defined by its implementation of [LangFunction].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::Function;
+///
+/// let function = Function::<Go>::new("add")
+///     .param("a", "int")
+///     .param("b", "int")
+///     .returns("int")
+///     .body(quote!(return a + b));
+///
+/// let toks: go::Tokens = quote!($function);
+///
+/// assert_eq!(
+///     vec![
+///         "func add(a int, b int) int {",
+///         "\treturn a + b",
+///         "}",
+///     ],
+///     toks.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub struct Function<L>
+where
+    L: Lang,
+{
+    name: ItemStr,
+    params: Vec<(ItemStr, Tokens<L>)>,
+    returns: Option<Tokens<L>>,
+    body: Option<Tokens<L>>,
+}
+
+impl<L> Function<L>
+where
+    L: Lang,
+{
+    /// Construct a new function with the given name.
+    pub fn new<N>(name: N) -> Self
+    where
+        N: Into<ItemStr>,
+    {
+        Self {
+            name: name.into(),
+            params: Vec::new(),
+            returns: None,
+            body: None,
+        }
+    }
+
+    /// Add a parameter with the given name and type.
+    pub fn param<N, T>(mut self, name: N, ty: T) -> Self
+    where
+        N: Into<ItemStr>,
+        T: FormatInto<L>,
+    {
+        self.params.push((name.into(), into_tokens(ty)));
+        self
+    }
+
+    /// Set the return type of the function.
+    pub fn returns<T>(self, ty: T) -> Self
+    where
+        T: FormatInto<L>,
+    {
+        Self {
+            returns: Some(into_tokens(ty)),
+            ..self
+        }
+    }
+
+    /// Set the body of the function.
+    ///
+    /// A function without a body is rendered as a declaration, if the
+    /// language supports it.
+    pub fn body<T>(self, body: T) -> Self
+    where
+        T: FormatInto<L>,
+    {
+        Self {
+            body: Some(into_tokens(body)),
+            ..self
+        }
+    }
+
+    /// The name of the function.
+    pub fn name(&self) -> &ItemStr {
+        &self.name
+    }
+
+    /// The parameters of the function, as pairs of name and type.
+    pub fn params(&self) -> &[(ItemStr, Tokens<L>)] {
+        &self.params
+    }
+
+    /// The return type of the function, if any.
+    pub fn return_type(&self) -> Option<&Tokens<L>> {
+        self.returns.as_ref()
+    }
+
+    /// The body of the function, if any.
+    pub fn block(&self) -> Option<&Tokens<L>> {
+        self.body.as_ref()
+    }
+}
+
+impl<L> FormatInto<L> for Function<L>
+where
+    L: LangFunction,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        L::format_function(&self, tokens);
+    }
+}
+
+fn into_tokens<L, T>(value: T) -> Tokens<L>
+where
+    L: Lang,
+    T: FormatInto<L>,
+{
+    let mut tokens = Tokens::new();
+    tokens.append(value);
+    tokens
+}
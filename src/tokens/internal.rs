@@ -1,17 +1,18 @@
 use crate::lang::Lang;
 use crate::tokens::{from_fn, FormatInto};
+use alloc::boxed::Box;
 
 /// Add a language item directly.
 ///
 /// This must only be used by the [impl_lang!] macro.
 ///
 /// [impl_lang!]: crate::impl_lang!
-pub fn __lang_item<L>(item: Box<L::Item>) -> impl FormatInto<L>
+pub fn __lang_item<L>(item: L::Item) -> impl FormatInto<L>
 where
     L: Lang,
 {
     from_fn(|t| {
-        t.lang_item(item);
+        t.lang_item(Box::new(item));
     })
 }
 
@@ -20,11 +21,11 @@ where
 /// This must only be used by the [impl_lang!] macro.
 ///
 /// [impl_lang!]: crate::impl_lang!
-pub fn __lang_item_register<L>(item: Box<L::Item>) -> impl FormatInto<L>
+pub fn __lang_item_register<L>(item: L::Item) -> impl FormatInto<L>
 where
     L: Lang,
 {
     from_fn(|t| {
-        t.lang_item_register(item);
+        t.lang_item_register(Box::new(item));
     })
 }
@@ -2,9 +2,15 @@
 
 use crate::lang::Lang;
 use crate::tokens::{FormatInto, ItemStr, Tokens};
+use alloc::boxed::Box;
+use alloc::sync::Arc;
 
 /// A single item in a stream of tokens.
+///
+/// New kinds of items might be added in the future, so matching on items
+/// requires a wildcard arm.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
 pub enum Item<L>
 where
     L: Lang,
@@ -16,6 +22,12 @@ where
     Lang(usize, Box<L::Item>),
     /// A language-specific item that is not rendered.
     Register(usize, Box<L::Item>),
+    /// A shared token stream which is formatted in place, as a
+    /// self-contained stream.
+    ///
+    /// This is added through [Tokens::append_shared], which avoids cloning
+    /// the items of fragments which are reused in many places.
+    Shared(usize, Arc<Tokens<L>>),
     /// Push a new line unless the current line is empty. Will be flushed on
     /// indentation changes.
     Push,
@@ -40,10 +52,36 @@ where
     OpenQuote(bool),
     /// Close the current quote.
     CloseQuote,
+    /// A literal which has already been escaped, and is written as-is even
+    /// when inside of a quote.
+    ///
+    /// This is produced by [quoted_with()][crate::tokens::quoted_with()].
+    Escaped(ItemStr),
     /// Switch on evaluation. Only valid during string handling.
     OpenEval,
     /// Close evaluation.
     CloseEval,
+    /// A comma which is only written if trailing commas are enabled through
+    /// [fmt::Config::with_trailing_comma].
+    ///
+    /// [fmt::Config::with_trailing_comma]: crate::fmt::Config::with_trailing_comma
+    TrailingComma,
+    /// A space around an operator, which is only written if operator spacing
+    /// is enabled through [fmt::Config::with_operator_spacing].
+    ///
+    /// This is produced by [binop()][crate::tokens::binop()].
+    ///
+    /// [fmt::Config::with_operator_spacing]: crate::fmt::Config::with_operator_spacing
+    OperatorSpace,
+    /// A space at which the line may be broken if it would otherwise exceed
+    /// the width configured through [fmt::Config::with_max_width].
+    ///
+    /// This is produced by [Tokens::soft_break] or `$[soft]` in [quote!].
+    ///
+    /// [fmt::Config::with_max_width]: crate::fmt::Config::with_max_width
+    /// [Tokens::soft_break]: crate::Tokens::soft_break
+    /// [quote!]: crate::quote
+    SoftBreak,
 }
 
 /// Formatting an item is the same as simply adding that item to the token
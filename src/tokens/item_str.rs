@@ -2,10 +2,14 @@
 
 use crate::lang::Lang;
 use crate::tokens::{FormatInto, Item, Tokens};
-use std::borrow::Cow;
-use std::fmt;
-use std::ops::Deref;
-use std::rc::Rc;
+use alloc::borrow::Cow;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::String;
+use core::fmt;
+use core::ops::Deref;
+use smallvec::SmallVec;
 
 /// A managed string that permits immutable borrowing.
 #[derive(Debug, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
@@ -16,6 +20,67 @@ pub enum ItemStr {
     Static(&'static str),
 }
 
+impl ItemStr {
+    /// Access the underlying string slice.
+    ///
+    /// `ItemStr` also dereferences to `str`, so it can be passed wherever a
+    /// `&str` is expected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::tokens::ItemStr;
+    ///
+    /// fn shout(s: &str) -> String {
+    ///     s.to_uppercase()
+    /// }
+    ///
+    /// let a = ItemStr::Static("hello");
+    /// let b = ItemStr::from(String::from("world"));
+    ///
+    /// assert_eq!("hello", a.as_str());
+    /// assert_eq!("WORLD", shout(&b));
+    /// assert!(b.starts_with("wor"));
+    /// ```
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Box(b) => b,
+            Self::Static(s) => s,
+        }
+    }
+
+    /// Construct a string from format arguments.
+    ///
+    /// Output which fits on the stack is written there first, so that the only
+    /// allocation is the exactly sized boxed string. Arguments without any
+    /// formatting are stored as a static string without allocating.
+    pub(crate) fn from_fmt(args: fmt::Arguments<'_>) -> Self {
+        use core::fmt::Write as _;
+
+        struct Buf(SmallVec<[u8; 128]>);
+
+        impl fmt::Write for Buf {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0.extend_from_slice(s.as_bytes());
+                Ok(())
+            }
+        }
+
+        if let Some(s) = args.as_str() {
+            return Self::Static(s);
+        }
+
+        let mut buf = Buf(SmallVec::new());
+
+        buf.write_fmt(args)
+            .expect("a formatting trait implementation returned an error");
+
+        // NB: only complete string slices are written to the buffer.
+        let s = core::str::from_utf8(&buf.0).expect("buffer is valid utf-8");
+        Self::Box(s.into())
+    }
+}
+
 /// Convert stringy things.
 impl<L> FormatInto<L> for ItemStr
 where
@@ -26,7 +91,7 @@ where
     }
 }
 
-impl<'a, L> FormatInto<L> for &'a ItemStr
+impl<L> FormatInto<L> for &ItemStr
 where
     L: Lang,
 {
@@ -37,10 +102,7 @@ where
 
 impl AsRef<str> for ItemStr {
     fn as_ref(&self) -> &str {
-        match self {
-            Self::Box(b) => b,
-            Self::Static(s) => s,
-        }
+        self.as_str()
     }
 }
 
@@ -48,10 +110,7 @@ impl Deref for ItemStr {
     type Target = str;
 
     fn deref(&self) -> &str {
-        match self {
-            Self::Box(b) => b,
-            Self::Static(s) => s,
-        }
+        self.as_str()
     }
 }
 
@@ -0,0 +1,76 @@
+use crate::lang::Lang;
+use crate::tokens::FormatInto;
+use crate::Tokens;
+
+/// Join the items of an iterator, inserting the given separator between each
+/// of them.
+///
+/// This is the programmatic equivalent of using `join` in a [quote!] loop.
+/// The separator is appended between items but not after the last one, and
+/// an empty iterator appends nothing at all.
+///
+/// Since the separator is appended more than once it needs to implement
+/// [Clone], which is the case for string literals as well as most functions
+/// constructed through [quote_fn!].
+///
+/// [quote!]: crate::quote
+/// [quote_fn!]: crate::quote_fn
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens;
+///
+/// let args = ["a", "b", "c"];
+///
+/// let tokens: rust::Tokens = quote! {
+///     foo($(tokens::join(args, ", ")));
+/// };
+///
+/// assert_eq!("foo(a, b, c);", tokens.to_string()?);
+///
+/// let lines: rust::Tokens = quote! {
+///     $(tokens::join(args, quote_fn!(;$['\r'])));
+/// };
+///
+/// assert_eq!(vec!["a;", "b;", "c;"], lines.to_vec()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn join<I, S>(iter: I, separator: S) -> Join<I, S>
+where
+    I: IntoIterator,
+    S: Clone,
+{
+    Join { iter, separator }
+}
+
+/// An iterator joined by a separator.
+///
+/// This is constructed with the [join()] function.
+#[derive(Clone, Copy)]
+pub struct Join<I, S> {
+    iter: I,
+    separator: S,
+}
+
+impl<L, I, S> FormatInto<L> for Join<I, S>
+where
+    L: Lang,
+    I: IntoIterator,
+    I::Item: FormatInto<L>,
+    S: FormatInto<L> + Clone,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        let mut it = self.iter.into_iter();
+
+        if let Some(first) = it.next() {
+            tokens.append(first);
+
+            for item in it {
+                tokens.append(self.separator.clone());
+                tokens.append(item);
+            }
+        }
+    }
+}
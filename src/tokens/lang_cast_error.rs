@@ -0,0 +1,35 @@
+use core::fmt;
+
+/// Error raised when a token stream can't be cast to another language through
+/// [Tokens::lang_cast][crate::Tokens::lang_cast].
+///
+/// This happens when the stream contains language items, since those are
+/// specific to the language they were created for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LangCastError {
+    index: usize,
+}
+
+impl LangCastError {
+    pub(crate) fn new(index: usize) -> Self {
+        Self { index }
+    }
+
+    /// The index of the first language item in the token stream.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl fmt::Display for LangCastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "language item at index {} can't be cast to another language",
+            self.index
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LangCastError {}
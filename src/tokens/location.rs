@@ -0,0 +1,77 @@
+use alloc::vec::Vec;
+use core::cmp;
+use core::hash;
+
+/// The location in Rust source at which an item was produced by the
+/// [quote!] macro.
+///
+/// Locations are recorded by token streams when the `locations` feature is
+/// enabled, and can be accessed through [Tokens::locations].
+///
+/// [quote!]: crate::quote
+/// [Tokens::locations]: crate::Tokens::locations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    file: &'static str,
+    line: usize,
+    column: usize,
+}
+
+impl Location {
+    /// The file in which the item was produced.
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// The line at which the item was produced, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column at which the item was produced, starting at 0.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+/// Locations recorded for items in a token stream, as pairs of item index and
+/// location.
+///
+/// Locations are diagnostic only, so they do not participate in the
+/// comparison or hashing of token streams.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Locations {
+    pub(crate) locations: Vec<(usize, Location)>,
+}
+
+impl Locations {
+    /// Record a location for the item at the given index.
+    pub(crate) fn push(&mut self, index: usize, file: &'static str, line: usize, column: usize) {
+        self.locations
+            .push((index, Location { file, line, column }));
+    }
+}
+
+impl PartialEq for Locations {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Locations {}
+
+impl PartialOrd for Locations {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Locations {
+    fn cmp(&self, _: &Self) -> cmp::Ordering {
+        cmp::Ordering::Equal
+    }
+}
+
+impl hash::Hash for Locations {
+    fn hash<H: hash::Hasher>(&self, _: &mut H) {}
+}
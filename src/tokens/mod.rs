@@ -56,27 +56,70 @@
 //! # }
 //! ```
 
+mod assert;
+mod binop;
+mod block;
+mod boolean;
+mod comment;
+mod debug;
+mod delimited;
+mod delimited_list;
 mod display;
+mod enclosed;
+mod entries;
 mod format_into;
 mod from_fn;
+mod function;
 mod internal;
 mod item;
 mod item_str;
+mod join;
+mod lang_cast_error;
+#[cfg(feature = "locations")]
+mod location;
+mod neutral;
+mod null;
+mod quote_target;
 mod quoted;
 mod register;
 mod static_literal;
 mod tokens;
+mod validation_error;
+pub(crate) mod validator;
+pub mod visit;
+mod when;
 
+pub use self::binop::{binop, Binop};
+pub use self::block::{block, Block, BraceStyle};
+pub use self::boolean::{boolean, Boolean};
+pub use self::comment::{comment, Comment};
+pub use self::debug::{debug, Debug};
+pub use self::delimited::{delimited, Delimited};
+pub use self::delimited_list::{delimited_list, DelimitedList};
 pub use self::display::{display, Display};
+pub use self::enclosed::{angled, brackets, parens, Enclosed};
+pub use self::entries::{entries, Entries};
 pub use self::format_into::FormatInto;
-pub use self::from_fn::{from_fn, FromFn};
+pub use self::from_fn::{from_fn, try_from_fn, FromFn};
+pub use self::function::Function;
 pub use self::item::Item;
 pub use self::item_str::ItemStr;
-pub use self::quoted::{quoted, QuotedFn};
+pub use self::join::{join, Join};
+pub use self::lang_cast_error::LangCastError;
+#[cfg(feature = "locations")]
+pub use self::location::Location;
+pub use self::neutral::{neutral, Neutral};
+pub use self::null::{null, Null};
+pub use self::quote_target::{QuoteTarget, VecTarget};
+pub use self::quoted::{quoted, quoted_with, QuotedFn, QuotedWith};
 pub use self::register::{register, Register, RegisterFn};
 pub use self::static_literal::static_literal;
 pub use self::tokens::Tokens;
+pub use self::validation_error::ValidationError;
+pub use self::when::{or_else, when, OrElse, When};
 
+#[doc(hidden)]
+pub use self::assert::{__AssertTokens, __assert_tokens_eq};
 #[doc(hidden)]
 pub use self::internal::__lang_item;
 #[doc(hidden)]
@@ -0,0 +1,46 @@
+use crate::lang::Lang;
+use crate::tokens::FormatInto;
+use crate::Tokens;
+
+/// Interpolate a language-neutral token stream into a token stream of any
+/// language.
+///
+/// Unlike [Tokens::lang_cast] this can't fail. Any language items in the
+/// neutral token stream are skipped, since language items for `()` render
+/// nothing.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::neutral;
+///
+/// fn answer() -> Tokens<()> {
+///     quote!(42)
+/// }
+///
+/// let rust: rust::Tokens = quote!(let answer = $(neutral(answer())););
+/// assert_eq!("let answer = 42;", rust.to_string()?);
+///
+/// let java: java::Tokens = quote!(int answer = $(neutral(answer())););
+/// assert_eq!("int answer = 42;", java.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn neutral(tokens: Tokens<()>) -> Neutral {
+    Neutral(tokens)
+}
+
+/// A language-neutral token stream.
+///
+/// This is constructed with the [neutral()] function.
+#[derive(Debug, Clone)]
+pub struct Neutral(Tokens<()>);
+
+impl<L> FormatInto<L> for Neutral
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        tokens.append(self.0.lang_cast_lossy::<L>());
+    }
+}
@@ -0,0 +1,44 @@
+use crate::lang::Lang;
+use crate::tokens::{static_literal, FormatInto};
+use crate::Tokens;
+
+/// Format the null literal of the target language, as provided by
+/// [Lang::null_literal].
+///
+/// This allows templates which are shared between languages to render the
+/// appropriate literal, like `null` in Java, `None` in Rust, or `nil` in Go.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::null;
+///
+/// let java: java::Tokens = quote!(String name = $(null()););
+/// assert_eq!("String name = null;", java.to_string()?);
+///
+/// let rust: rust::Tokens = quote!(let name: Option<String> = $(null()););
+/// assert_eq!("let name: Option<String> = None;", rust.to_string()?);
+///
+/// let go: go::Tokens = quote!(var name *string = $(null()));
+/// assert_eq!("var name *string = nil", go.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn null() -> Null {
+    Null(())
+}
+
+/// The null literal of the target language.
+///
+/// This is constructed with the [null()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct Null(());
+
+impl<L> FormatInto<L> for Null
+where
+    L: Lang,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        tokens.append(static_literal(L::null_literal()));
+    }
+}
@@ -0,0 +1,116 @@
+use alloc::vec::Vec;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+
+use crate::lang::Lang;
+use crate::tokens::Item;
+use crate::Tokens;
+
+/// Trait for things which can be the target of the [quote_in!] macro.
+///
+/// This is implemented for [Tokens] and for a raw `Vec<Item<L>>`.
+///
+/// [quote_in!]: crate::quote_in
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::{Item, ItemStr};
+///
+/// let mut items = Vec::<Item<()>>::new();
+///
+/// quote_in!(items => foo bar);
+///
+/// assert_eq!(
+///     vec![
+///         Item::Literal(ItemStr::Static("foo")),
+///         Item::Space,
+///         Item::Literal(ItemStr::Static("bar")),
+///     ],
+///     items
+/// );
+/// ```
+pub trait QuoteTarget<L>
+where
+    L: Lang,
+{
+    /// The token stream being quoted into, which is flushed into the target
+    /// when dropped.
+    type Target<'a>: DerefMut<Target = Tokens<L>>
+    where
+        Self: 'a;
+
+    /// Access the token stream to quote into.
+    fn quote_target(&mut self) -> Self::Target<'_>;
+}
+
+impl<L> QuoteTarget<L> for Tokens<L>
+where
+    L: Lang,
+{
+    type Target<'a> = &'a mut Tokens<L>;
+
+    #[inline]
+    fn quote_target(&mut self) -> Self::Target<'_> {
+        self
+    }
+}
+
+impl<L> QuoteTarget<L> for Vec<Item<L>>
+where
+    L: Lang,
+{
+    type Target<'a> = VecTarget<'a, L>;
+
+    #[inline]
+    fn quote_target(&mut self) -> Self::Target<'_> {
+        VecTarget {
+            target: self,
+            tokens: Tokens::new(),
+        }
+    }
+}
+
+/// A token stream which is appended to a vector of items when dropped.
+///
+/// This is created through the [QuoteTarget] implementation for
+/// `Vec<Item<L>>`.
+pub struct VecTarget<'a, L>
+where
+    L: Lang,
+{
+    target: &'a mut Vec<Item<L>>,
+    tokens: Tokens<L>,
+}
+
+impl<L> Deref for VecTarget<'_, L>
+where
+    L: Lang,
+{
+    type Target = Tokens<L>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.tokens
+    }
+}
+
+impl<L> DerefMut for VecTarget<'_, L>
+where
+    L: Lang,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.tokens
+    }
+}
+
+impl<L> Drop for VecTarget<'_, L>
+where
+    L: Lang,
+{
+    fn drop(&mut self) {
+        self.target.extend(mem::take(&mut self.tokens));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::lang::Lang;
 use crate::tokens::{FormatInto, Item, Tokens};
+use alloc::string::String;
 
 /// Function to provide string quoting.
 ///
@@ -74,3 +75,69 @@ where
         t.item(Item::CloseQuote);
     }
 }
+
+/// Function to provide string quoting with custom escaping.
+///
+/// This works like [quoted()], except that each character of the string is
+/// escaped using the provided closure instead of the language-specific
+/// [quoting method]. The closure is called with each character and the
+/// string to write the escaped character to.
+///
+/// Only the quotes themselves are provided by the language.
+///
+/// [quoting method]: crate::lang::Lang::write_quoted
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::quoted_with;
+///
+/// let escape_newlines = |c, out: &mut String| match c {
+///     '\n' => out.push_str("\\n"),
+///     c => out.push(c),
+/// };
+///
+/// let tokens: java::Tokens = quote! {
+///     String s = $(quoted_with("a \"b\"\nc", escape_newlines));
+/// };
+///
+/// assert_eq!("String s = \"a \"b\"\\nc\";", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn quoted_with<S, F>(inner: S, escape: F) -> QuotedWith<S, F>
+where
+    S: AsRef<str>,
+    F: FnMut(char, &mut String),
+{
+    QuotedWith { inner, escape }
+}
+
+/// Struct containing a string that is quoted with custom escaping.
+///
+/// This is constructed with the [quoted_with()] function.
+#[derive(Clone, Copy, Debug)]
+pub struct QuotedWith<S, F> {
+    inner: S,
+    escape: F,
+}
+
+impl<S, F, L> FormatInto<L> for QuotedWith<S, F>
+where
+    L: Lang,
+    S: AsRef<str>,
+    F: FnMut(char, &mut String),
+{
+    fn format_into(mut self, t: &mut Tokens<L>) {
+        let inner = self.inner.as_ref();
+        let mut escaped = String::with_capacity(inner.len());
+
+        for c in inner.chars() {
+            (self.escape)(c, &mut escaped);
+        }
+
+        t.item(Item::OpenQuote(false));
+        t.item(Item::Escaped(escaped.into_boxed_str().into()));
+        t.item(Item::CloseQuote);
+    }
+}
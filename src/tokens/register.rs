@@ -1,6 +1,7 @@
 use crate::lang::Lang;
 use crate::tokens::FormatInto;
 use crate::Tokens;
+use alloc::vec::Vec;
 
 /// Function to provide item registration.
 ///
@@ -111,6 +112,80 @@ where
     fn register(self, tokens: &mut Tokens<L>);
 }
 
+/// Register every item in a vector.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let imports = vec![
+///     rust::import("std::io", "Read").with_alias("_"),
+///     rust::import("std::io", "Write").with_alias("_"),
+/// ];
+///
+/// let mut tokens = rust::Tokens::new();
+/// tokens.register(&imports[..]);
+/// tokens.register(imports);
+///
+/// assert_eq!(
+///     "use std::io::{Read as _, Write as _};\n",
+///     tokens.to_file_string()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+impl<L, T> Register<L> for Vec<T>
+where
+    L: Lang,
+    T: Register<L>,
+{
+    fn register(self, tokens: &mut Tokens<L>) {
+        tokens.register_all(self);
+    }
+}
+
+impl<'a, L, T> Register<L> for &'a Vec<T>
+where
+    L: Lang,
+    &'a T: Register<L>,
+{
+    fn register(self, tokens: &mut Tokens<L>) {
+        tokens.register_all(self);
+    }
+}
+
+impl<'a, L, T> Register<L> for &'a [T]
+where
+    L: Lang,
+    &'a T: Register<L>,
+{
+    fn register(self, tokens: &mut Tokens<L>) {
+        tokens.register_all(self);
+    }
+}
+
+impl<L, T, const N: usize> Register<L> for [T; N]
+where
+    L: Lang,
+    T: Register<L>,
+{
+    fn register(self, tokens: &mut Tokens<L>) {
+        tokens.register_all(self);
+    }
+}
+
+impl<L, T> Register<L> for Option<T>
+where
+    L: Lang,
+    T: Register<L>,
+{
+    fn register(self, tokens: &mut Tokens<L>) {
+        if let Some(item) = self {
+            item.register(tokens);
+        }
+    }
+}
+
 /// Macro to build implementations of `Register<T>` for a tuple.
 macro_rules! impl_register_tuple {
     ($($ty:ident, $var:ident),*) => {
@@ -11,13 +11,25 @@
 #![allow(clippy::module_inception)]
 
 use crate::fmt;
-use crate::lang::{Lang, LangSupportsEval};
-use crate::tokens::{FormatInto, Item, Register};
-use std::cmp;
-use std::iter::FromIterator;
-use std::mem;
-use std::slice;
-use std::vec;
+use crate::lang::{Lang, LangItem, LangSupportsEval};
+#[cfg(feature = "locations")]
+use crate::tokens::location::Locations;
+use crate::tokens::validator::Validator;
+#[cfg(feature = "locations")]
+use crate::tokens::Location;
+use crate::tokens::{FormatInto, Item, ItemStr, Register};
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp;
+use core::iter::{self, FromIterator};
+use core::mem;
+use core::ops;
+use core::slice;
 
 /// A stream of tokens.
 ///
@@ -65,7 +77,7 @@ use std::vec;
 /// [`space`]: Self::space
 /// [`push`]: Self::push
 /// [`line`]: Self::line
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Tokens<L = ()>
 where
     L: Lang,
@@ -79,6 +91,9 @@ where
     /// This makes up a singly-linked list over all language items that you can
     /// follow.
     last_lang_item: usize,
+    /// Source locations recorded for items.
+    #[cfg(feature = "locations")]
+    locations: Locations,
 }
 
 impl<L> Tokens<L>
@@ -100,6 +115,8 @@ where
         Tokens {
             items: Vec::new(),
             last_lang_item: 0,
+            #[cfg(feature = "locations")]
+            locations: Locations::default(),
         }
     }
 
@@ -118,6 +135,8 @@ where
         Tokens {
             items: Vec::with_capacity(cap),
             last_lang_item: 0,
+            #[cfg(feature = "locations")]
+            locations: Locations::default(),
         }
     }
 
@@ -145,39 +164,38 @@ where
         }
     }
 
-    /// Append the given tokens.
-    ///
-    /// This append function takes anything implementing [FormatInto] making the
-    /// argument's behavior customizable. Most primitive types have built-in
-    /// implementations of [FormatInto] treating them as raw tokens.
-    ///
-    /// Most notabley, things implementing [FormatInto] can be used as arguments
-    /// for [interpolation] in the [quote!] macro.
+    /// Borrow the items of the token stream as a slice.
     ///
-    /// [quote!]: macro.quote.html
-    /// [interpolation]: macro.quote.html#interpolation
+    /// This allows for matching over and indexing into the stream without
+    /// going through an iterator.
     ///
     /// # Examples
     ///
     /// ```
     /// use genco::prelude::*;
+    /// use genco::tokens::{ItemStr, Item};
     ///
-    /// let mut tokens = Tokens::<()>::new();
-    /// tokens.append(4u32);
+    /// let tokens: Tokens<()> = quote!(foo bar);
     ///
-    /// assert_eq!(quote!($(4u32)), tokens);
+    /// match tokens.as_slice() {
+    ///     [Item::Literal(first), Item::Space, rest @ ..] => {
+    ///         assert_eq!("foo", first.as_ref());
+    ///         assert_eq!(&[Item::Literal(ItemStr::Static("bar"))], rest);
+    ///     }
+    ///     _ => panic!("unexpected items"),
+    /// }
     /// ```
-    pub fn append<T>(&mut self, tokens: T)
-    where
-        T: FormatInto<L>,
-    {
-        tokens.format_into(self)
+    pub fn as_slice(&self) -> &[Item<L>] {
+        &self.items
     }
 
-    /// Extend with another stream of tokens.
+    /// Construct a mutable iterator over the token stream.
     ///
-    /// This respects the structural requirements of adding one element at a
-    /// time, like you would get by calling [`space`], [`push`], or [`line`].
+    /// This is intended for post-processing passes which rewrite literals in
+    /// place. Every item can be inspected, but only literals can be modified,
+    /// since changing the kind of an item could break the structural
+    /// guarantees of the stream. To remove items use [retain][Self::retain]
+    /// instead.
     ///
     /// # Examples
     ///
@@ -186,332 +204,2377 @@ where
     /// use genco::tokens::{Item, ItemStr};
     ///
     /// let mut tokens: Tokens<()> = quote!(foo bar);
-    /// tokens.extend::<Tokens<()>>(quote!($[' ']baz));
     ///
-    /// assert_eq!(tokens, quote!(foo bar baz));
-    /// ```
+    /// let mut it = tokens.iter_mut();
+    /// assert_eq!(Some(&Item::Space), it.nth(1).as_deref());
     ///
-    /// [`space`]: Self::space
-    /// [`push`]: Self::push
-    /// [`line`]: Self::line
-    pub fn extend<I>(&mut self, it: I)
-    where
-        I: IntoIterator<Item = Item<L>>,
-    {
-        let it = it.into_iter();
-        let (low, high) = it.size_hint();
-        self.items.reserve(high.unwrap_or(low));
-
-        for item in it {
-            self.item(item);
+    /// for mut item in it {
+    ///     if let Some(s) = item.literal_mut() {
+    ///         if &**s == "bar" {
+    ///             *s = ItemStr::Static("baz");
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(quote!(foo baz), tokens);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, L> {
+        IterMut {
+            iter: self.items.iter_mut(),
         }
     }
 
-    /// Walk over all imports.
+    /// Rewrite every literal in the token stream using the given function.
     ///
-    /// The order in which the imports are returned is *not* defined. So if you
-    /// need them in some particular order you need to sort them.
+    /// This includes literals inside of quoted strings, but not language
+    /// items like imports.
     ///
     /// # Examples
     ///
     /// ```
     /// use genco::prelude::*;
     ///
-    /// let debug = rust::import("std::fmt", "Debug");
-    /// let ty = rust::import("std::collections", "HashMap");
+    /// let mut tokens: rust::Tokens = quote! {
+    ///     let foo = $(quoted("foo"));
+    /// };
     ///
-    /// let tokens = quote!(foo $ty<u32, dyn $debug> baz);
+    /// tokens.map_literals(|s| s.replace("foo", "bar").into());
     ///
-    /// for import in tokens.walk_imports() {
-    ///     println!("{:?}", import);
-    /// }
+    /// assert_eq!("let bar = \"bar\";", tokens.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
     /// ```
-    pub fn walk_imports(&self) -> WalkImports<'_, L> {
-        WalkImports {
-            items: &self.items,
-            pos: self.last_lang_item,
+    pub fn map_literals<F>(&mut self, mut f: F)
+    where
+        F: FnMut(ItemStr) -> ItemStr,
+    {
+        for item in &mut self.items {
+            if let Item::Literal(literal) = item {
+                let current = mem::replace(literal, ItemStr::Static(""));
+                *literal = f(current);
+            }
         }
     }
 
-    /// Add an registered custom element that is _not_ rendered.
+    /// Append the given tokens.
     ///
-    /// Registration can be used to generate imports that do not render a
-    /// visible result.
+    /// This append function takes anything implementing [FormatInto] making the
+    /// argument's behavior customizable. Most primitive types have built-in
+    /// implementations of [FormatInto] treating them as raw tokens.
+    ///
+    /// Most notabley, things implementing [FormatInto] can be used as arguments
+    /// for [interpolation] in the [quote!] macro.
+    ///
+    /// [quote!]: macro.quote.html
+    /// [interpolation]: macro.quote.html#interpolation
     ///
     /// # Examples
     ///
     /// ```
     /// use genco::prelude::*;
     ///
-    /// let write_bytes_ext = rust::import("byteorder", "WriteBytesExt").with_alias("_");
-    ///
-    /// let tokens = quote!($(register(write_bytes_ext)));
+    /// let mut tokens = Tokens::<()>::new();
+    /// tokens.append(4u32);
     ///
-    /// assert_eq!("use byteorder::WriteBytesExt as _;\n", tokens.to_file_string()?);
-    /// # Ok::<_, genco::fmt::Error>(())
+    /// assert_eq!(quote!($(4u32)), tokens);
     /// ```
-    ///
-    /// [quote!]: macro.quote.html
-    pub fn register<T>(&mut self, tokens: T)
+    pub fn append<T>(&mut self, tokens: T)
     where
-        T: Register<L>,
+        T: FormatInto<L>,
     {
-        tokens.register(self);
+        tokens.format_into(self)
     }
 
-    /// Check if tokens contain no items.
+    /// Append the items of an iterator, separated by the given separator.
+    ///
+    /// This is a shorthand for appending [tokens::join()]. The separator is
+    /// only appended between items, so an empty iterator appends nothing.
+    ///
+    /// [tokens::join()]: crate::tokens::join()
+    ///
+    /// # Examples
     ///
     /// ```
     /// use genco::prelude::*;
     ///
-    /// let tokens: Tokens<()> = quote!();
+    /// let mut tokens = rust::Tokens::new();
+    /// tokens.append("foo(");
+    /// tokens.append_separated(["a", "b"], ", ");
+    /// tokens.append(")");
     ///
-    /// assert!(tokens.is_empty());
+    /// assert_eq!("foo(a, b)", tokens.to_string()?);
+    ///
+    /// let mut empty = rust::Tokens::new();
+    /// empty.append_separated(Vec::<&str>::new(), ", ");
+    /// assert!(empty.is_empty());
+    /// # Ok::<_, genco::fmt::Error>(())
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.items.is_empty()
+    pub fn append_separated<I, S>(&mut self, iter: I, separator: S)
+    where
+        I: IntoIterator,
+        I::Item: FormatInto<L>,
+        S: FormatInto<L> + Clone,
+    {
+        self.append(crate::tokens::join(iter, separator));
     }
 
-    /// Add a single spacing to the token stream.
+    /// Append a shared token stream.
     ///
-    /// Note that due to structural guarantees two consequent spaces may not
-    /// follow each other in the same token stream.
+    /// Unlike [append][Self::append], the items of the shared stream are not
+    /// copied. Instead a single [Item::Shared] is added which the formatter
+    /// descends into, making it cheap to reuse a large fragment in many
+    /// places. Imports in the shared stream are included by
+    /// [walk_imports][Self::walk_imports].
     ///
-    /// A space operation has no effect unless it's followed by a non-whitespace
-    /// token.
+    /// The shared stream is formatted as a self-contained stream, so it can't
+    /// for example close a quote which was opened outside of it.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::sync::Arc;
+    ///
     /// use genco::prelude::*;
     ///
-    /// let mut tokens = Tokens::<()>::new();
+    /// let map = rust::import("std::collections", "HashMap");
     ///
-    /// tokens.space();
-    /// tokens.append("hello");
-    /// tokens.space();
-    /// tokens.space(); // Note: ignored
-    /// tokens.append("world");
-    /// tokens.space();
+    /// let shared: Arc<rust::Tokens> = Arc::new(quote! {
+    ///     let mut m = $map::new();
+    /// });
+    ///
+    /// let mut tokens = rust::Tokens::new();
+    /// tokens.append("fn foo() {");
+    /// tokens.indent();
+    /// tokens.append_shared(shared.clone());
+    /// tokens.unindent();
+    /// tokens.append("}");
     ///
     /// assert_eq!(
     ///     vec![
-    ///         " hello world",
+    ///         "use std::collections::HashMap;",
+    ///         "",
+    ///         "fn foo() {",
+    ///         "    let mut m = HashMap::new();",
+    ///         "}",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    ///
+    /// // Shared streams can also be interpolated directly.
+    /// let tokens: rust::Tokens = quote!(fn bar() { $(&shared) });
+    /// assert_eq!(
+    ///     vec![
+    ///         "use std::collections::HashMap;",
+    ///         "",
+    ///         "fn bar() { let mut m = HashMap::new(); }",
     ///     ],
     ///     tokens.to_file_vec()?
     /// );
     /// # Ok::<_, genco::fmt::Error>(())
     /// ```
-    pub fn space(&mut self) {
-        if let Some(Item::Space) = self.items.last() {
-            return;
-        }
-
-        self.items.push(Item::Space);
+    pub fn append_shared(&mut self, tokens: Arc<Tokens<L>>) {
+        // NB: recorded position needs to be adjusted.
+        self.items.push(Item::Shared(self.last_lang_item, tokens));
+        self.last_lang_item = self.items.len();
     }
 
-    /// Add a single push operation.
-    ///
-    /// Push operations ensure that any following tokens are added to their own
-    /// line.
+    /// Append the items of an iterator, separated by spaces.
     ///
-    /// A push has no effect unless it's *preceeded* or *followed* by
-    /// non-whitespace tokens.
+    /// Unlike [append_separated][Self::append_separated], a space is also
+    /// inserted before the first new item if the stream already has content,
+    /// so that the new items never run into the existing ones.
     ///
     /// # Examples
     ///
     /// ```
     /// use genco::prelude::*;
     ///
-    /// let mut tokens = Tokens::<()>::new();
-    ///
-    /// tokens.push();
-    /// tokens.append("hello");
-    /// tokens.push();
-    /// tokens.append("world");
-    /// tokens.push();
+    /// let mut tokens = rust::Tokens::new();
+    /// tokens.append_all_spaced(["pub", "fn"]);
+    /// tokens.append_all_spaced(["foo()", "{}"]);
     ///
-    /// assert_eq!(
-    ///     vec![
-    ///         "hello",
-    ///         "world"
-    ///     ],
-    ///     tokens.to_file_vec()?
-    /// );
+    /// assert_eq!("pub fn foo() {}", tokens.to_string()?);
     /// # Ok::<_, genco::fmt::Error>(())
     /// ```
-    pub fn push(&mut self) {
-        let item = loop {
-            match self.items.pop() {
-                // NB: never reconfigure a line into a push.
-                Some(Item::Line) => {
-                    self.items.push(Item::Line);
-                    return;
-                }
-                Some(Item::Space | Item::Push) => continue,
-                item => break item,
+    pub fn append_all_spaced<I, T>(&mut self, it: I)
+    where
+        I: IntoIterator<Item = T>,
+        T: FormatInto<L>,
+    {
+        for item in it {
+            if !self.items.is_empty() {
+                self.space();
             }
-        };
 
-        self.items.extend(item);
-        self.items.push(Item::Push);
+            self.append(item);
+        }
     }
 
-    /// Add a single line operation.
+    /// Append the items of an iterator, each on its own line.
     ///
-    /// A line ensures that any following tokens have one line of separation
-    /// between them and the preceeding tokens.
-    ///
-    /// A line has no effect unless it's *preceeded* and *followed* by
-    /// non-whitespace tokens.
+    /// A [push][Self::push] is inserted before each item, except for the
+    /// first item if the stream is empty, so that appending to an empty
+    /// stream doesn't start with a line break.
     ///
     /// # Examples
     ///
     /// ```
     /// use genco::prelude::*;
     ///
-    /// let mut tokens = Tokens::<()>::new();
-    ///
-    /// tokens.line();
-    /// tokens.append("hello");
-    /// tokens.line();
-    /// tokens.append("world");
-    /// tokens.line();
+    /// let mut tokens = rust::Tokens::new();
+    /// tokens.append_lines(["let a = 1;", "let b = 2;"]);
     ///
-    /// assert_eq!(
-    ///     vec![
-    ///         "hello",
-    ///         "",
-    ///         "world"
-    ///     ],
-    ///     tokens.to_file_vec()?
-    /// );
+    /// assert_eq!("let a = 1;\nlet b = 2;", tokens.to_string()?);
     /// # Ok::<_, genco::fmt::Error>(())
     /// ```
-    pub fn line(&mut self) {
-        let item = loop {
-            match self.items.pop() {
-                Some(Item::Line) | Some(Item::Push) => continue,
-                item => break item,
+    pub fn append_lines<I, T>(&mut self, it: I)
+    where
+        I: IntoIterator<Item = T>,
+        T: FormatInto<L>,
+    {
+        for item in it {
+            if !self.items.is_empty() {
+                self.push();
             }
-        };
 
-        self.items.extend(item);
-        self.items.push(Item::Line);
+            self.append(item);
+        }
     }
 
-    /// Increase the indentation of the token stream.
-    ///
-    /// An indentation is a language-specific operation which adds whitespace to
-    /// the beginning of a line preceeding any non-whitespace tokens.
-    ///
-    /// An indentation has no effect unless it's *followed* by non-whitespace
-    /// tokens. It also acts like a [`push`], in that it will shift any tokens to
-    /// a new line.
+    /// Prepend the given tokens to the front of the stream.
     ///
-    /// [`push`]: Self::push
+    /// The existing items are appended after the new ones, which upholds the
+    /// structural guarantees of the stream at the seam between them. So if
+    /// the prepended tokens end with a space and the stream starts with a
+    /// push, only the push is kept.
     ///
     /// # Examples
     ///
     /// ```
     /// use genco::prelude::*;
     ///
-    /// let mut tokens = Tokens::<()>::new();
+    /// let mut tokens: rust::Tokens = quote! {
+    ///     fn main() {}
+    /// };
     ///
-    /// tokens.indent();
-    /// tokens.append("hello");
-    /// tokens.indent();
-    /// tokens.append("world");
-    /// tokens.indent();
-    /// tokens.append("😀");
+    /// tokens.prepend(quote!(#[allow(dead_code)]$['\r']));
+    /// tokens.prepend(quote!($("// Generated file.")$['\n']));
     ///
     /// assert_eq!(
     ///     vec![
-    ///         "    hello",
-    ///         "        world",
-    ///         "            😀",
+    ///         "// Generated file.",
+    ///         "",
+    ///         "#[allow(dead_code)]",
+    ///         "fn main() {}",
     ///     ],
     ///     tokens.to_file_vec()?
     /// );
     /// # Ok::<_, genco::fmt::Error>(())
     /// ```
-    pub fn indent(&mut self) {
-        self.indentation(1);
+    pub fn prepend<T>(&mut self, tokens: T)
+    where
+        T: FormatInto<L>,
+    {
+        let mut head = Tokens::new();
+        head.append(tokens);
+        let tail = mem::replace(self, head);
+        self.append(tail);
     }
 
-    /// Decrease the indentation of the token stream.
+    /// Insert a single item at the given index in the stream.
     ///
-    /// An indentation is a language-specific operation which adds whitespace to
-    /// the beginning of a line preceeding any non-whitespace tokens.
-    ///
-    /// An indentation has no effect unless it's *followed* by non-whitespace
-    /// tokens. It also acts like a [`push`], in that it will shift any tokens to
-    /// a new line.
+    /// The stream is rebuilt around the inserted item, so the structural
+    /// guarantees of the stream are upheld. Inserting whitespace next to
+    /// other whitespace might therefore cause items to be merged, which
+    /// changes the index of subsequent items.
     ///
-    /// Indentation can never go below zero, and will just be ignored if that
-    /// were to happen. However, negative indentation is stored in the token
-    /// stream, so any negative indentation in place will have to be countered
-    /// before indentation starts again.
+    /// # Panics
     ///
-    /// [`push`]: Self::push
+    /// Panics if `index > len`, where `len` is the number of items in the
+    /// stream.
     ///
     /// # Examples
     ///
     /// ```
     /// use genco::prelude::*;
+    /// use genco::tokens::Item;
     ///
-    /// let mut tokens = Tokens::<()>::new();
+    /// let mut tokens: rust::Tokens = quote!(a c);
+    /// tokens.insert(2, Item::Literal("b".into()));
+    /// tokens.insert(3, Item::Space);
     ///
-    /// tokens.indent();
-    /// tokens.append("hello");
-    /// tokens.unindent();
-    /// tokens.append("world");
-    /// tokens.unindent();
-    /// tokens.append("😀");
-    /// tokens.indent();
-    /// tokens.append("😁");
+    /// assert_eq!("a b c", tokens.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn insert(&mut self, index: usize, item: Item<L>) {
+        let len = self.items.len();
+
+        assert!(
+            index <= len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            len
+        );
+
+        let items = mem::take(&mut self.items);
+        self.last_lang_item = 0;
+
+        // New index of each existing item.
+        #[cfg(feature = "locations")]
+        let mut indexes = Vec::with_capacity(items.len());
+
+        let mut item = Some(item);
+
+        for (n, current) in items.into_iter().enumerate() {
+            if n == index {
+                self.item(item.take().expect("item already inserted"));
+            }
+
+            self.item(current);
+
+            #[cfg(feature = "locations")]
+            indexes.push(self.items.len().checked_sub(1));
+        }
+
+        if let Some(item) = item {
+            self.item(item);
+        }
+
+        #[cfg(feature = "locations")]
+        self.locations.locations.retain_mut(|(index, _)| {
+            match indexes.get(*index).copied().flatten() {
+                Some(new_index) => {
+                    *index = new_index;
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+
+    /// Remove and return the last item in the token stream, or `None` if it's
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::tokens::{Item, ItemStr};
+    ///
+    /// let mut tokens: rust::Tokens = quote!(foo bar);
+    ///
+    /// assert_eq!(Some(Item::Literal(ItemStr::Static("bar"))), tokens.pop());
+    /// assert_eq!(Some(Item::Space), tokens.pop());
+    /// assert_eq!("foo", tokens.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn pop(&mut self) -> Option<Item<L>> {
+        let item = self.items.pop()?;
+
+        if let Item::Lang(prev, _) | Item::Register(prev, _) | Item::Shared(prev, _) = &item {
+            self.last_lang_item = *prev;
+        }
+
+        #[cfg(feature = "locations")]
+        {
+            let len = self.items.len();
+            self.locations.locations.retain(|(index, _)| *index < len);
+        }
+
+        Some(item)
+    }
+
+    /// Split the token stream into two at the given index.
+    ///
+    /// Returns a token stream containing the items from `index` onwards,
+    /// leaving the items before it in `self`. Whitespace which would be left
+    /// dangling at the cut, like trailing spaces and pushes in `self` and
+    /// leading ones in the returned stream, is removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than [len()][Self::len].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens: rust::Tokens = quote!(fn foo() {});
+    /// let index = tokens.len();
+    ///
+    /// tokens.push();
+    /// tokens.append(quote!(fn bar() {}));
+    ///
+    /// let rest = tokens.split_off(index);
+    ///
+    /// assert_eq!("fn foo() {}", tokens.to_string()?);
+    /// assert_eq!("fn bar() {}", rest.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn split_off(&mut self, index: usize) -> Self {
+        let tail = self.items.split_off(index);
+
+        #[cfg(feature = "locations")]
+        let tail_locations = {
+            let (head, tail): (Vec<_>, Vec<_>) = mem::take(&mut self.locations.locations)
+                .into_iter()
+                .partition(|(i, _)| *i < index);
+            self.locations.locations = head;
+            tail
+        };
+
+        self.last_lang_item = self
+            .items
+            .iter()
+            .rposition(|item| {
+                matches!(item, Item::Lang(..) | Item::Register(..) | Item::Shared(..))
+            })
+            .map_or(0, |n| n + 1);
+
+        while let Some(Item::Space | Item::SoftBreak | Item::Push | Item::Line) = self.items.last()
+        {
+            self.items.pop();
+        }
+
+        #[cfg(feature = "locations")]
+        {
+            let len = self.items.len();
+            self.locations.locations.retain(|(i, _)| *i < len);
+        }
+
+        let skip = tail
+            .iter()
+            .take_while(|item| {
+                matches!(
+                    item,
+                    Item::Space | Item::SoftBreak | Item::Push | Item::Line
+                )
+            })
+            .count();
+
+        let mut out = Self::new();
+
+        #[cfg(feature = "locations")]
+        {
+            let locations = tail_locations
+                .into_iter()
+                .filter(|(i, _)| *i >= index + skip)
+                .map(|(i, location)| (i - index - skip, location))
+                .collect::<Vec<_>>();
+
+            out.extend_located(tail.into_iter().skip(skip), &locations);
+        }
+
+        #[cfg(not(feature = "locations"))]
+        out.extend(tail.into_iter().skip(skip));
+
+        out
+    }
+
+    /// Split the token stream around the first literal which is equal to
+    /// `marker`.
+    ///
+    /// Returns the items before and after the marker, or `None` if the marker
+    /// is not present. Like with [split_off()][Self::split_off], whitespace
+    /// around the marker is removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let template: rust::Tokens = quote! {
+    ///     fn main() {
+    ///         __BODY__
+    ///     }
+    /// };
+    ///
+    /// let (head, tail) = template.split_once("__BODY__").unwrap();
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     $head
+    ///     println!("Hello World");
+    ///     $tail
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "fn main() {",
+    ///         "    println!(\"Hello World\");",
+    ///         "}",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    ///
+    /// assert!(template.split_once("__MISSING__").is_none());
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn split_once(&self, marker: &str) -> Option<(Self, Self)> {
+        let index = self.items.iter().position(
+            |item| matches!(item, Item::Literal(literal) if literal.as_str() == marker),
+        )?;
+
+        let mut head = self.clone();
+        let mut tail = head.split_off(index);
+        let tail = tail.split_off(1);
+        Some((head, tail))
+    }
+
+    /// Extend with another stream of tokens.
+    ///
+    /// This respects the structural requirements of adding one element at a
+    /// time, like you would get by calling [`space`], [`push`], or [`line`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::tokens::{Item, ItemStr};
+    ///
+    /// let mut tokens: Tokens<()> = quote!(foo bar);
+    /// tokens.extend::<Tokens<()>>(quote!($[' ']baz));
+    ///
+    /// assert_eq!(tokens, quote!(foo bar baz));
+    /// ```
+    ///
+    /// [`space`]: Self::space
+    /// [`push`]: Self::push
+    /// [`line`]: Self::line
+    pub fn extend<I>(&mut self, it: I)
+    where
+        I: IntoIterator<Item = Item<L>>,
+    {
+        let it = it.into_iter();
+        let (low, high) = it.size_hint();
+        self.items.reserve(high.unwrap_or(low));
+
+        for item in it {
+            self.item(item);
+        }
+    }
+
+    /// Run the given closure with mutable access to the token stream.
+    ///
+    /// This is the method equivalent of a [scope] in the [quote!] macro, like
+    /// `$(ref t { ... })`, which allows imperative helpers to read naturally.
+    ///
+    /// [scope]: macro.quote.html#scopes
+    /// [quote!]: macro.quote.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let lastname = Some("Doe");
+    ///
+    /// let mut tokens: rust::Tokens = quote!(Hello John);
+    ///
+    /// tokens.scope(|t| {
+    ///     if let Some(lastname) = lastname {
+    ///         t.space();
+    ///         t.append(lastname);
+    ///     }
+    /// });
+    ///
+    /// assert_eq!("Hello John Doe", tokens.to_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn scope<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        f(self)
+    }
+
+    /// Run the given closure with mutable access to the token stream, making
+    /// sure that any indentation and quotes opened by it are closed
+    /// afterwards.
+    ///
+    /// This guards against a forgotten [unindent][Self::unindent] or an
+    /// unclosed quote corrupting the rest of the stream. Whatever the closure
+    /// returns is returned from this function, so it can be used with
+    /// fallible closures which return early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::tokens::Item;
+    ///
+    /// let mut tokens = rust::Tokens::new();
+    ///
+    /// let result = tokens.edit(|t| {
+    ///     t.append("fn foo() {");
+    ///     t.indent();
+    ///     t.append("bar();");
+    ///     t.push();
+    ///     t.append("let s =");
+    ///     t.space();
+    ///     t.append(Item::OpenQuote(false));
+    ///     t.append("baz");
+    ///     let n = "nan".parse::<u32>()?;
+    ///     t.append(n);
+    ///     Ok::<_, std::num::ParseIntError>(())
+    /// });
+    ///
+    /// assert!(result.is_err());
+    ///
+    /// tokens.push();
+    /// tokens.append("}");
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "fn foo() {",
+    ///         "    bar();",
+    ///         "    let s = \"baz\"",
+    ///         "}",
+    ///     ],
+    ///     tokens.to_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn edit<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let start = self.edit_start();
+        let (indent, quotes) = balance(&self.items[start..]);
+        let output = f(self);
+
+        let start = start.min(self.items.len());
+        let (new_indent, new_quotes) = balance(&self.items[start..]);
+
+        for _ in quotes..new_quotes {
+            self.item(Item::CloseQuote);
+        }
+
+        // NB: indentation opened before the edit may be closed by it.
+        let n = new_indent.saturating_sub(indent);
+
+        if n > 0 {
+            self.indentation(-n);
+        }
+
+        return output;
+
+        /// Count the net indentation and number of open quotes in the given
+        /// items.
+        fn balance<L>(items: &[Item<L>]) -> (i16, usize)
+        where
+            L: Lang,
+        {
+            let mut indent = 0i16;
+            let mut quotes = 0usize;
+
+            for item in items {
+                match item {
+                    Item::Indentation(n) => indent = indent.saturating_add(*n),
+                    Item::OpenQuote(..) => quotes += 1,
+                    Item::CloseQuote => quotes = quotes.saturating_sub(1),
+                    _ => {}
+                }
+            }
+
+            (indent, quotes)
+        }
+    }
+
+    /// Find the first item which can be modified by appending to the stream,
+    /// since an indentation is merged with a preceeding one once trailing
+    /// whitespace is flushed.
+    fn edit_start(&self) -> usize {
+        let mut start = self.items.len();
+
+        while start > 0 && is_whitespace(&self.items[start - 1]) {
+            start -= 1;
+        }
+
+        if start > 0 && matches!(self.items[start - 1], Item::Indentation(..)) {
+            start -= 1;
+        }
+
+        return start;
+
+        fn is_whitespace<L>(item: &Item<L>) -> bool
+        where
+            L: Lang,
+        {
+            matches!(
+                item,
+                Item::Push | Item::Line | Item::Space | Item::SoftBreak
+            )
+        }
+    }
+
+    /// Run the given closure in an indented scope.
+    ///
+    /// This is like calling [indent][Self::indent] before and
+    /// [unindent][Self::unindent] after the closure, except that the
+    /// unindentation can't be forgotten. Whatever the closure returns is
+    /// returned from this function, so it can be used with fallible
+    /// closures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = rust::Tokens::new();
+    /// tokens.append("fn foo() {");
+    ///
+    /// let count = tokens.indented(|t| {
+    ///     t.append("bar();");
+    ///     t.push();
+    ///     t.append("baz();");
+    ///     2
+    /// });
+    ///
+    /// tokens.push();
+    /// tokens.append("}");
+    ///
+    /// assert_eq!(2, count);
+    /// assert_eq!(
+    ///     vec![
+    ///         "fn foo() {",
+    ///         "    bar();",
+    ///         "    baz();",
+    ///         "}",
+    ///     ],
+    ///     tokens.to_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn indented<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        self.indent();
+        let output = f(self);
+        self.unindent();
+        output
+    }
+
+    /// Run the given closure in a quoted scope, producing a string literal
+    /// from everything appended by it.
+    ///
+    /// This is the programmatic equivalent of [quoted()], and the quote is
+    /// always closed after the closure has run. Literals appended inside of
+    /// the scope are escaped according to the target language, like with
+    /// [Lang::write_quoted].
+    ///
+    /// [quoted()]: crate::tokens::quoted()
+    /// [Lang::write_quoted]: crate::lang::Lang::write_quoted
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = java::Tokens::new();
+    /// tokens.append("String message =");
+    /// tokens.space();
+    ///
+    /// tokens.quoted_scope(|t| {
+    ///     t.append("Hello");
+    ///     t.space();
+    ///     t.append("\"World\"");
+    /// });
+    ///
+    /// tokens.append(";");
+    ///
+    /// assert_eq!(
+    ///     "String message = \"Hello \\\"World\\\"\";",
+    ///     tokens.to_string()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn quoted_scope<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        self.item(Item::OpenQuote(false));
+        let output = f(self);
+        self.item(Item::CloseQuote);
+        output
+    }
+
+    /// Walk over all imports.
+    ///
+    /// The order in which the imports are returned is *not* defined. So if you
+    /// need them in some particular order you need to sort them.
+    ///
+    /// This doesn't descend into [scoped token
+    /// streams][crate::lang::LangItem::scoped_tokens], like the body of a
+    /// [rust::module][crate::lang::rust::module()], since their imports are
+    /// rendered by the item which holds them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let debug = rust::import("std::fmt", "Debug");
+    /// let ty = rust::import("std::collections", "HashMap");
+    ///
+    /// let tokens = quote!(foo $ty<u32, dyn $debug> baz);
+    ///
+    /// for import in tokens.walk_imports() {
+    ///     println!("{:?}", import);
+    /// }
+    /// ```
+    pub fn walk_imports(&self) -> WalkImports<'_, L> {
+        WalkImports {
+            items: &self.items,
+            pos: self.last_lang_item,
+            stack: Vec::new(),
+            registered: None,
+        }
+    }
+
+    /// Walk over all imports like [walk_imports()][Self::walk_imports], but
+    /// skip registrations of imports which have already been registered.
+    ///
+    /// This is used by the built-in languages when formatting files, so that
+    /// resolving an import which is registered many times only costs as much
+    /// as resolving it once.
+    pub(crate) fn walk_imports_dedup(&self) -> WalkImports<'_, L> {
+        WalkImports {
+            registered: Some(BTreeSet::new()),
+            ..self.walk_imports()
+        }
+    }
+
+    /// Collect the unique imports in the token stream, in sorted order.
+    ///
+    /// Like [walk_imports()][Self::walk_imports], this includes imports that
+    /// are only [registered][Self::register]. Unlike it, this also includes
+    /// imports in [scoped token
+    /// streams][crate::lang::LangItem::scoped_tokens], like the body of a
+    /// [rust::module][crate::lang::rust::module()].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    /// let debug = rust::import("std::fmt", "Debug");
+    /// let error = rust::import("std::error", "Error");
+    ///
+    /// let mut tokens: rust::Tokens = quote! {
+    ///     let a = $(&map)::<u32, u32>::new();
+    ///     let b = $(&map)::<u32, u32>::new();
+    ///     let c: &dyn $(&debug) = &a;
+    /// };
+    ///
+    /// tokens.register(&error);
+    ///
+    /// let expected: Vec<rust::Any> = vec![
+    ///     map.into(),
+    ///     error.into(),
+    ///     debug.into(),
+    /// ];
+    ///
+    /// assert_eq!(expected.iter().collect::<Vec<_>>(), tokens.imports());
+    /// ```
+    pub fn imports(&self) -> Vec<&L::Item> {
+        let mut imports = Vec::new();
+        self.collect_imports(&mut imports);
+        imports.sort();
+        imports.dedup();
+        imports
+    }
+
+    /// Collect all imports, including the ones in scoped token streams.
+    fn collect_imports<'a>(&'a self, imports: &mut Vec<&'a L::Item>) {
+        for import in self.walk_imports() {
+            imports.push(import);
+
+            if let Some(tokens) = import.scoped_tokens() {
+                tokens.collect_imports(imports);
+            }
+        }
+    }
+
+    /// Remove registered imports which have already been registered earlier
+    /// in the token stream.
+    ///
+    /// Helpers which [register][Self::register] the imports they depend on
+    /// can end up registering the same import many times, each of which is
+    /// stored in the stream and visited when walking imports. Since
+    /// registered items are not rendered, only the first registration of
+    /// each import is needed.
+    ///
+    /// Registrations in [shared][Self::append_shared] streams are left as-is
+    /// since the streams might be used elsewhere, but they do count as
+    /// earlier registrations.
+    ///
+    /// Formatting a file with one of the built-in languages skips repeated
+    /// registrations while resolving imports without modifying the stream,
+    /// but still has to step over them. Deduplicating costs about as much as
+    /// formatting the stream once, so it pays off for streams which are kept
+    /// around and appended to, walked or formatted several times. See the
+    /// `dedup_registered` benchmark for measurements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let debug = rust::import("std::fmt", "Debug");
+    /// let hash = rust::import("std::hash", "Hash");
+    ///
+    /// let mut tokens = rust::Tokens::new();
+    ///
+    /// for _ in 0..100 {
+    ///     tokens.register(&debug);
+    ///     tokens.register(&hash);
+    /// }
+    ///
+    /// quote_in!(tokens => fn foo<T: $(&debug)>(value: T) {});
+    ///
+    /// assert_eq!(tokens.walk_imports().count(), 201);
+    ///
+    /// tokens.dedup_registered();
+    ///
+    /// assert_eq!(tokens.walk_imports().count(), 3);
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "use std::fmt::Debug;",
+    ///         "use std::hash::Hash;",
+    ///         "",
+    ///         "fn foo<T: Debug>(value: T) {}",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn dedup_registered(&mut self) {
+        let mut seen = BTreeSet::new();
+
+        let keep = self
+            .items
+            .iter()
+            .map(|item| match item {
+                Item::Register(_, item) => seen.insert(&**item),
+                Item::Shared(_, tokens) => {
+                    tokens.collect_registered(&mut seen);
+                    true
+                }
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+
+        if keep.iter().all(|keep| *keep) {
+            return;
+        }
+
+        let items = mem::take(&mut self.items);
+        self.items.reserve(items.len());
+        self.last_lang_item = 0;
+
+        let items = items
+            .into_iter()
+            .zip(keep.iter())
+            .filter_map(|(item, keep)| keep.then_some(item));
+
+        #[cfg(feature = "locations")]
+        {
+            let mut removed = 0;
+
+            let index = keep
+                .iter()
+                .map(|keep| {
+                    removed += usize::from(!*keep);
+                    keep.then_some(removed)
+                })
+                .collect::<Vec<_>>();
+
+            let locations = mem::take(&mut self.locations.locations)
+                .into_iter()
+                .filter_map(|(i, location)| Some((i - index.get(i).copied()??, location)))
+                .collect::<Vec<_>>();
+
+            self.extend_located(items, &locations);
+        }
+
+        #[cfg(not(feature = "locations"))]
+        for item in items {
+            self.item(item);
+        }
+    }
+
+    /// Collect registered imports, including the ones in shared streams.
+    fn collect_registered<'a>(&'a self, seen: &mut BTreeSet<&'a L::Item>) {
+        for item in &self.items {
+            match item {
+                Item::Register(_, item) => {
+                    seen.insert(&**item);
+                }
+                Item::Shared(_, tokens) => tokens.collect_registered(seen),
+                _ => {}
+            }
+        }
+    }
+
+    /// Test if the token stream contains the given import, either through an
+    /// interpolation or through [registering][Self::register] it.
+    ///
+    /// Like [imports()][Self::imports], this includes imports in [scoped token
+    /// streams][crate::lang::LangItem::scoped_tokens].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    /// let set = rust::import("std::collections", "HashSet");
+    ///
+    /// let tokens: rust::Tokens = quote!(let a = $(&map)::<u32, u32>::new(););
+    ///
+    /// assert!(tokens.has_import(&map));
+    /// assert!(!tokens.has_import(&set));
+    /// ```
+    pub fn has_import<T>(&self, import: &T) -> bool
+    where
+        T: Clone + Into<L::Item>,
+    {
+        self.contains_import(&import.clone().into())
+    }
+
+    fn contains_import(&self, import: &L::Item) -> bool {
+        self.walk_imports().any(|item| {
+            item == import
+                || item
+                    .scoped_tokens()
+                    .map_or(false, |tokens| tokens.contains_import(import))
+        })
+    }
+
+    /// Validate the token stream, checking that it would produce valid code
+    /// in the target language.
+    ///
+    /// This checks that:
+    /// * The stream upholds the structural guarantees documented on
+    ///   [Tokens].
+    /// * Quotes and evaluations are balanced, and evaluations only occur
+    ///   inside of quotes.
+    /// * No imported names collide with reserved words in the language, since
+    ///   such imports would render as keywords. Reserved words which the
+    ///   language escapes when formatting, like Rust keywords which are
+    ///   written as raw identifiers, are not reported, see
+    ///   [LangItem::reserved_name][crate::lang::LangItem::reserved_name].
+    ///
+    /// [Shared][Self::append_shared] token streams and [scoped token
+    /// streams][crate::lang::LangItem::scoped_tokens], like the body of a
+    /// [rust::module][crate::lang::rust::module()], are validated as well.
+    ///
+    /// # Errors
+    ///
+    /// Errors with the index and kind of the first violation. Violations in a
+    /// shared or scoped token stream are reported at the index of the item
+    /// holding it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::tokens::ValidationError;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    /// let tokens: rust::Tokens = quote!(let m = $map::new(););
+    /// assert!(tokens.validate().is_ok());
+    ///
+    /// let f = rust::import("foo", "super");
+    /// let tokens: rust::Tokens = quote!(let m = $f(););
+    ///
+    /// assert_eq!(
+    ///     tokens.validate(),
+    ///     Err(ValidationError::ReservedName {
+    ///         index: 6,
+    ///         name: String::from("super"),
+    ///     })
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), crate::tokens::ValidationError> {
+        let mut validator = Validator::default();
+
+        for (index, item) in self.items.iter().enumerate() {
+            validator.check(item)?;
+
+            let item = match item {
+                Item::Lang(_, item) | Item::Register(_, item) => item,
+                Item::Shared(_, tokens) => {
+                    tokens.validate().map_err(|e| e.with_index(index))?;
+                    continue;
+                }
+                _ => continue,
+            };
+
+            if let Some(name) = item.reserved_name() {
+                return Err(crate::tokens::ValidationError::ReservedName {
+                    index,
+                    name: name.to_owned(),
+                });
+            }
+
+            if let Some(tokens) = item.scoped_tokens() {
+                tokens.validate().map_err(|e| e.with_index(index))?;
+            }
+        }
+
+        validator.finish()
+    }
+
+    /// Visit every item in the token stream with the given [Visitor].
+    ///
+    /// See the [visit module][crate::tokens::visit] for details.
+    ///
+    /// [Visitor]: crate::tokens::visit::Visitor
+    pub fn visit<V>(&self, visitor: &mut V)
+    where
+        V: crate::tokens::visit::Visitor<L>,
+    {
+        for item in &self.items {
+            visitor.visit_item(item);
+        }
+    }
+
+    /// Rewrite the token stream into a new stream with the given [Fold].
+    ///
+    /// See the [visit module][crate::tokens::visit] for details.
+    ///
+    /// [Fold]: crate::tokens::visit::Fold
+    pub fn fold<F>(self, fold: &mut F) -> Tokens<L>
+    where
+        F: crate::tokens::visit::Fold<L>,
+    {
+        let mut out = Tokens::new();
+
+        for item in self.items {
+            fold.fold_item(item, &mut out);
+        }
+
+        out
+    }
+
+    /// Access the source locations recorded for items in the token stream.
+    ///
+    /// Each location is paired with the index of the item it was recorded
+    /// for, as yielded by [iter()][Self::iter]. Locations are recorded for
+    /// literals produced by the [quote!] macro, and are preserved when a token
+    /// stream is interpolated into another.
+    ///
+    /// This requires the `locations` feature to be enabled.
+    ///
+    /// [quote!]: macro.quote.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: Tokens<()> = quote!(foo bar);
+    ///
+    /// for (index, location) in tokens.locations() {
+    ///     println!("{}: {}:{}", index, location.line(), location.column());
+    /// }
+    /// ```
+    #[cfg(feature = "locations")]
+    pub fn locations(&self) -> &[(usize, Location)] {
+        &self.locations.locations
+    }
+
+    /// Record the source location of the last item in the stream.
+    ///
+    /// This must only be used by the [quote!] macro.
+    ///
+    /// [quote!]: macro.quote.html
+    #[doc(hidden)]
+    #[cfg(feature = "locations")]
+    pub fn __location(&mut self, file: &'static str, line: usize, column: usize) {
+        if let Some(index) = self.items.len().checked_sub(1) {
+            self.locations.push(index, file, line, column);
+        }
+    }
+
+    /// Extend with another stream of tokens, while preserving the locations
+    /// recorded for its items.
+    #[cfg(feature = "locations")]
+    pub(crate) fn extend_located<I>(&mut self, it: I, locations: &[(usize, Location)])
+    where
+        I: IntoIterator<Item = Item<L>>,
+    {
+        let mut locations = locations.iter().peekable();
+
+        for (index, item) in it.into_iter().enumerate() {
+            self.item(item);
+
+            while let Some((_, location)) = locations.next_if(|(i, _)| *i == index) {
+                if let Some(new_index) = self.items.len().checked_sub(1) {
+                    self.locations.locations.push((new_index, *location));
+                }
+            }
+        }
+    }
+
+    /// Add an registered custom element that is _not_ rendered.
+    ///
+    /// Registration can be used to generate imports that do not render a
+    /// visible result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let write_bytes_ext = rust::import("byteorder", "WriteBytesExt").with_alias("_");
+    ///
+    /// let tokens = quote!($(register(write_bytes_ext)));
+    ///
+    /// assert_eq!("use byteorder::WriteBytesExt as _;\n", tokens.to_file_string()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    ///
+    /// [quote!]: macro.quote.html
+    pub fn register<T>(&mut self, tokens: T)
+    where
+        T: Register<L>,
+    {
+        tokens.register(self);
+    }
+
+    /// Register every item produced by the given iterator.
+    ///
+    /// This is useful when the set of items to register is computed
+    /// dynamically. Collections like [Vec] and slices can also be passed
+    /// directly to [register()][Self::register].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let traits = ["Read", "Write", "Seek"]
+    ///     .into_iter()
+    ///     .map(|name| rust::import("std::io", name).with_alias("_"));
+    ///
+    /// let mut tokens = rust::Tokens::new();
+    /// tokens.register_all(traits);
+    ///
+    /// assert_eq!(
+    ///     "use std::io::{Read as _, Seek as _, Write as _};\n",
+    ///     tokens.to_file_string()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn register_all<I>(&mut self, iter: I)
+    where
+        I: IntoIterator,
+        I::Item: Register<L>,
+    {
+        for item in iter {
+            item.register(self);
+        }
+    }
+
+    /// Retain only the items matching the given predicate.
+    ///
+    /// The stream is rebuilt from the retained items, which re-establishes the
+    /// structural guarantees of the stream. So removing an item between two
+    /// spaces doesn't leave two spaces in sequence, and whitespace which would
+    /// be leading because a preceeding item was removed is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::tokens::Item;
+    ///
+    /// let mut tokens: rust::Tokens = quote! {
+    ///     debug!("starting");
+    ///     let a = 1;
+    ///     debug!("done");
+    /// };
+    ///
+    /// let mut debug = false;
+    ///
+    /// tokens.retain(|item| {
+    ///     match item {
+    ///         Item::Literal(s) if s.starts_with("debug!") => debug = true,
+    ///         Item::Push | Item::Line => debug = false,
+    ///         _ => (),
+    ///     }
+    ///
+    ///     !debug
+    /// });
+    ///
+    /// assert_eq!(vec!["let a = 1;"], tokens.to_file_vec()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Item<L>) -> bool,
+    {
+        let items = mem::take(&mut self.items);
+        self.last_lang_item = 0;
+
+        // New index of each item, if it was retained.
+        #[cfg(feature = "locations")]
+        let mut indexes = Vec::with_capacity(items.len());
+
+        let mut removed = false;
+
+        for item in items {
+            let keep = f(&item);
+
+            // Whitespace which only is leading since preceeding items were
+            // removed.
+            let leading = removed
+                && self.items.is_empty()
+                && matches!(
+                    item,
+                    Item::Push | Item::Line | Item::Space | Item::SoftBreak
+                );
+
+            if keep && !leading {
+                self.item(item);
+            }
+
+            removed |= !keep;
+
+            #[cfg(feature = "locations")]
+            indexes.push(self.items.len().checked_sub(1).filter(|_| keep));
+        }
+
+        #[cfg(feature = "locations")]
+        self.locations.locations.retain_mut(|(index, _)| {
+            match indexes.get(*index).copied().flatten() {
+                Some(new_index) => {
+                    *index = new_index;
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+
+    /// Normalize whitespace in the token stream.
+    ///
+    /// The stream maintains some structural guarantees as it's being built,
+    /// like never containing two spaces in sequence. Normalization removes
+    /// whitespace which is still redundant, which typically occurs when
+    /// conditional sections evaluate to nothing:
+    ///
+    /// * Leading spaces, soft breaks, pushes, and lines.
+    /// * Trailing spaces, soft breaks, pushes, and lines.
+    /// * Spaces and soft breaks directly preceeding a line, or directly
+    ///   following a push, a line or an indentation.
+    /// * Pushes directly following an indentation, since changing the
+    ///   indentation already implies a push.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::tokens::Item;
+    ///
+    /// let header: Option<rust::Tokens> = None;
+    ///
+    /// let mut tokens: rust::Tokens = quote! {
+    ///     $(if let Some(header) = &header {
+    ///         $header
+    ///     })
+    ///
+    ///     fn foo() {}
+    /// };
+    ///
+    /// tokens.append(Item::Space);
+    /// tokens.line();
+    ///
+    /// tokens.normalize();
+    ///
+    /// let expected: rust::Tokens = quote!(fn foo() {});
+    /// assert_eq!(expected, tokens);
+    /// ```
+    pub fn normalize(&mut self) {
+        let items = mem::take(&mut self.items);
+        self.last_lang_item = 0;
+
+        // New index of each item, if it was kept.
+        #[cfg(feature = "locations")]
+        let mut indexes = Vec::with_capacity(items.len());
+
+        for item in items {
+            let keep = !matches!(
+                (&item, self.items.last()),
+                (
+                    Item::Push | Item::Line | Item::Space | Item::SoftBreak,
+                    None
+                ) | (
+                    Item::Space | Item::SoftBreak,
+                    Some(Item::Push | Item::Line | Item::Indentation(_))
+                ) | (Item::Push, Some(Item::Indentation(_)))
+            );
+
+            if keep {
+                if let Item::Line = item {
+                    while let Some(Item::Space | Item::SoftBreak) = self.items.last() {
+                        self.items.pop();
+                    }
+                }
+
+                self.item(item);
+            }
+
+            #[cfg(feature = "locations")]
+            indexes.push(self.items.len().checked_sub(1).filter(|_| keep));
+        }
+
+        while let Some(Item::Push | Item::Line | Item::Space | Item::SoftBreak) = self.items.last()
+        {
+            self.items.pop();
+        }
+
+        #[cfg(feature = "locations")]
+        {
+            let len = self.items.len();
+
+            self.locations.locations.retain_mut(|(index, _)| {
+                match indexes.get(*index).copied().flatten() {
+                    Some(new_index) if new_index < len => {
+                        *index = new_index;
+                        true
+                    }
+                    _ => false,
+                }
+            });
+        }
+    }
+
+    /// Get the number of items in the token stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: Tokens<()> = quote!(foo bar);
+    ///
+    /// assert_eq!(3, tokens.len());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Get the item at the given index, or `None` if it's out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::tokens::{Item, ItemStr};
+    ///
+    /// let tokens: Tokens<()> = quote!(foo bar);
+    ///
+    /// assert_eq!(Some(&Item::Literal(ItemStr::Static("bar"))), tokens.get(2));
+    /// assert_eq!(None, tokens.get(3));
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&Item<L>> {
+        self.items.get(index)
+    }
+
+    /// Check if tokens contain no items.
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: Tokens<()> = quote!();
+    ///
+    /// assert!(tokens.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Check if tokens only contain whitespace, which includes spaces, soft
+    /// breaks, pushes, lines and indentation.
+    ///
+    /// An empty stream is also considered to be whitespace only.
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: Tokens<()> = quote!($['\r']$[' ']);
+    /// assert!(tokens.is_whitespace_only());
+    ///
+    /// let tokens: Tokens<()> = quote!(a);
+    /// assert!(!tokens.is_whitespace_only());
+    /// ```
+    pub fn is_whitespace_only(&self) -> bool {
+        self.items.iter().all(|item| {
+            matches!(
+                item,
+                Item::Space | Item::SoftBreak | Item::Push | Item::Line | Item::Indentation(..)
+            )
+        })
+    }
+
+    /// Add a single spacing to the token stream.
+    ///
+    /// Note that due to structural guarantees two consequent spaces may not
+    /// follow each other in the same token stream.
+    ///
+    /// A space operation has no effect unless it's followed by a non-whitespace
+    /// token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    ///
+    /// tokens.space();
+    /// tokens.append("hello");
+    /// tokens.space();
+    /// tokens.space(); // Note: ignored
+    /// tokens.append("world");
+    /// tokens.space();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         " hello world",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn space(&mut self) {
+        if let Some(Item::Space | Item::SoftBreak) = self.items.last() {
+            return;
+        }
+
+        self.items.push(Item::Space);
+    }
+
+    /// Add a soft line break.
+    ///
+    /// A soft break is written as a space, unless a maximum width has been
+    /// configured through [fmt::Config::with_max_width] and the text following
+    /// it would not fit on the current line. In that case the line is broken
+    /// and continued with one extra level of indentation.
+    ///
+    /// Soft breaks inside of quoted strings are always written as spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::fmt;
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    ///
+    /// tokens.append("call(first,");
+    /// tokens.soft_break();
+    /// tokens.append("second,");
+    /// tokens.soft_break();
+    /// tokens.append("third)");
+    ///
+    /// assert_eq!("call(first, second, third)", tokens.to_string()?);
+    ///
+    /// let mut w = fmt::FmtWriter::new(String::new());
+    /// let fmt = fmt::Config::from_lang::<()>().with_max_width(20);
+    /// tokens.format(&mut w.as_formatter(&fmt), &(), &())?;
+    ///
+    /// assert_eq!("call(first, second,\n    third)", w.into_inner());
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn soft_break(&mut self) {
+        let item = loop {
+            match self.items.pop() {
+                Some(Item::Space) => continue,
+                Some(Item::SoftBreak) => break Some(Item::SoftBreak),
+                // NB: a soft break never replaces a line break.
+                Some(item @ (Item::Push | Item::Line)) => {
+                    self.items.push(item);
+                    return;
+                }
+                item => break item,
+            }
+        };
+
+        let last = matches!(item, Some(Item::SoftBreak));
+        self.items.extend(item);
+
+        if !last {
+            self.items.push(Item::SoftBreak);
+        }
+    }
+
+    /// Add a single push operation.
+    ///
+    /// Push operations ensure that any following tokens are added to their own
+    /// line.
+    ///
+    /// A push has no effect unless it's *preceeded* or *followed* by
+    /// non-whitespace tokens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    ///
+    /// tokens.push();
+    /// tokens.append("hello");
+    /// tokens.push();
+    /// tokens.append("world");
+    /// tokens.push();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "hello",
+    ///         "world"
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn push(&mut self) {
+        let item = loop {
+            match self.items.pop() {
+                // NB: never reconfigure a line into a push.
+                Some(Item::Line) => {
+                    self.items.push(Item::Line);
+                    return;
+                }
+                Some(Item::Space | Item::SoftBreak | Item::Push) => continue,
+                item => break item,
+            }
+        };
+
+        self.items.extend(item);
+        self.items.push(Item::Push);
+    }
+
+    /// Add a single line operation.
+    ///
+    /// A line ensures that any following tokens have one line of separation
+    /// between them and the preceeding tokens.
+    ///
+    /// A line has no effect unless it's *preceeded* and *followed* by
+    /// non-whitespace tokens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    ///
+    /// tokens.line();
+    /// tokens.append("hello");
+    /// tokens.line();
+    /// tokens.append("world");
+    /// tokens.line();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "hello",
+    ///         "",
+    ///         "world"
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn line(&mut self) {
+        let item = loop {
+            match self.items.pop() {
+                Some(Item::Line) | Some(Item::Push) => continue,
+                item => break item,
+            }
+        };
+
+        self.items.extend(item);
+        self.items.push(Item::Line);
+    }
+
+    /// Push each line in a multi-line string as its own literal, with the
+    /// current indentation applied.
+    ///
+    /// Blank lines are added as a [`line`], so consecutive blank lines are
+    /// collapsed into one following the usual whitespace rules.
+    ///
+    /// [`line`]: Self::line
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    ///
+    /// tokens.append("fn main() {");
+    /// tokens.indent();
+    /// tokens.push_str_block("let a = 1;\n\nprintln!(\"{}\", a);\n");
+    /// tokens.unindent();
+    /// tokens.append("}");
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "fn main() {",
+    ///         "    let a = 1;",
+    ///         "",
+    ///         "    println!(\"{}\", a);",
+    ///         "}",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn push_str_block(&mut self, s: &str) {
+        for line in s.lines() {
+            if line.trim().is_empty() {
+                self.line();
+            } else {
+                self.push();
+                self.append(line);
+            }
+        }
+    }
+
+    /// Increase the indentation of the token stream.
+    ///
+    /// An indentation is a language-specific operation which adds whitespace to
+    /// the beginning of a line preceeding any non-whitespace tokens.
+    ///
+    /// An indentation has no effect unless it's *followed* by non-whitespace
+    /// tokens. It also acts like a [`push`], in that it will shift any tokens to
+    /// a new line.
+    ///
+    /// [`push`]: Self::push
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    ///
+    /// tokens.indent();
+    /// tokens.append("hello");
+    /// tokens.indent();
+    /// tokens.append("world");
+    /// tokens.indent();
+    /// tokens.append("😀");
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         "    hello",
+    ///         "        world",
+    ///         "            😀",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn indent(&mut self) {
+        self.indentation(1);
+    }
+
+    /// Decrease the indentation of the token stream.
+    ///
+    /// An indentation is a language-specific operation which adds whitespace to
+    /// the beginning of a line preceeding any non-whitespace tokens.
+    ///
+    /// An indentation has no effect unless it's *followed* by non-whitespace
+    /// tokens. It also acts like a [`push`], in that it will shift any tokens to
+    /// a new line.
+    ///
+    /// Indentation can never go below zero, and will just be ignored if that
+    /// were to happen. However, negative indentation is stored in the token
+    /// stream, so any negative indentation in place will have to be countered
+    /// before indentation starts again.
+    ///
+    /// [`push`]: Self::push
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    ///
+    /// tokens.indent();
+    /// tokens.append("hello");
+    /// tokens.unindent();
+    /// tokens.append("world");
+    /// tokens.unindent();
+    /// tokens.append("😀");
+    /// tokens.indent();
+    /// tokens.append("😁");
     /// tokens.indent();
     /// tokens.append("😂");
     ///
     /// assert_eq!(
-    ///     vec![
-    ///         "    hello",
-    ///         "world",
-    ///         "😀",
-    ///         "😁",
-    ///         "    😂",
-    ///     ],
+    ///     vec![
+    ///         "    hello",
+    ///         "world",
+    ///         "😀",
+    ///         "😁",
+    ///         "    😂",
+    ///     ],
+    ///     tokens.to_file_vec()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn unindent(&mut self) {
+        self.indentation(-1);
+    }
+
+    /// Formatting function for token streams that gives full control over the
+    /// formatting environment.
+    ///
+    /// The configurations and `format` arguments will be provided to all
+    /// registered language items as well, and can be used to customize
+    /// formatting through [LangItem::format()].
+    ///
+    /// The `format` argument is primarily used internally by
+    /// [Lang::format_file] to provide intermediate state that can be affect how
+    /// language items are formatter. So formatting something as a file might
+    /// yield different results than using this raw formatting function.
+    ///
+    /// Available formatters:
+    ///
+    /// * [fmt::VecWriter] - To write result into a vector.
+    /// * [fmt::FmtWriter] - To write the result into something implementing
+    ///   [fmt::Write][std::fmt::Write].
+    /// * [fmt::IoWriter]- To write the result into something implementing
+    ///   [io::Write][std::io::Write].
+    /// * [fmt::Metrics] - To measure the result without storing it.
+    ///
+    /// # Examples
+    ///
+    /// ```,no_run
+    /// # #[cfg(feature = "std")] {
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     let mut m = $map::new();
+    ///     m.insert(1u32, 2u32);
+    /// };
+    ///
+    /// let stdout = std::io::stdout();
+    /// let mut w = fmt::IoWriter::new(stdout.lock());
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>()
+    ///     .with_indentation(fmt::Indentation::Space(2));
+    /// let mut formatter = w.as_formatter(&fmt);
+    /// let config = rust::Config::default();
+    ///
+    /// // Default format state for Rust.
+    /// let format = rust::Format::default();
+    ///
+    /// tokens.format(&mut formatter, &config, &format)?;
+    /// # }
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    ///
+    /// [LangItem::format()]: crate::lang::LangItem::format()
+    pub fn format(
+        &self,
+        out: &mut fmt::Formatter<'_>,
+        config: &L::Config,
+        format: &L::Format,
+    ) -> fmt::Result {
+        out.format_items(
+            &self.items,
+            #[cfg(feature = "locations")]
+            &self.locations.locations,
+            config,
+            format,
+        )
+    }
+
+    /// Push a single item to the stream while checking for structural
+    /// guarantees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::tokens::{Item, ItemStr};
+    ///
+    /// let mut tokens = Tokens::<()>::new();
+    ///
+    /// tokens.append(ItemStr::Static("foo"));
+    /// tokens.space();
+    /// tokens.space(); // Note: second space ignored
+    /// tokens.append(ItemStr::Static("bar"));
+    ///
+    /// assert_eq!(tokens, quote!(foo bar));
+    /// ```
+    pub(crate) fn item(&mut self, item: Item<L>) {
+        match item {
+            Item::Push => self.push(),
+            Item::Line => self.line(),
+            Item::Space => self.space(),
+            Item::SoftBreak => self.soft_break(),
+            Item::Indentation(n) => self.indentation(n),
+            Item::Lang(_, item) => self.lang_item(item),
+            Item::Register(_, item) => self.lang_item_register(item),
+            Item::Shared(_, tokens) => self.append_shared(tokens),
+            other => self.items.push(other),
+        }
+    }
+
+    /// Add a language item directly.
+    pub(crate) fn lang_item(&mut self, item: Box<L::Item>) {
+        // NB: recorded position needs to be adjusted.
+        self.items
+            .push(crate::tokens::Item::Lang(self.last_lang_item, item));
+        self.last_lang_item = self.items.len();
+    }
+
+    /// Register a language item directly.
+    pub(crate) fn lang_item_register(&mut self, item: Box<L::Item>) {
+        // NB: recorded position needs to be adjusted.
+        self.items
+            .push(crate::tokens::Item::Register(self.last_lang_item, item));
+        self.last_lang_item = self.items.len();
+    }
+
+    /// File formatting function for token streams that gives full control over the
+    /// formatting environment.
+    ///
+    /// File formatting will render preambles like namespace declarations and
+    /// imports.
+    ///
+    /// Available formatters:
+    ///
+    /// * [fmt::VecWriter] - To write result into a vector.
+    /// * [fmt::FmtWriter] - To write the result into something implementing
+    ///   [fmt::Write][std::fmt::Write].
+    /// * [fmt::IoWriter]- To write the result into something implementing
+    ///   [io::Write][std::io::Write].
+    /// * [fmt::Metrics] - To measure the result without storing it.
+    ///
+    /// # Examples
+    ///
+    /// ```,no_run
+    /// # #[cfg(feature = "std")] {
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     let mut m = $map::new();
+    ///     m.insert(1u32, 2u32);
+    /// };
+    ///
+    /// let stdout = std::io::stdout();
+    /// let mut w = fmt::IoWriter::new(stdout.lock());
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>()
+    ///     .with_indentation(fmt::Indentation::Space(2));
+    /// let mut formatter = w.as_formatter(&fmt);
+    /// let config = rust::Config::default();
+    ///
+    /// tokens.format_file(&mut formatter, &config)?;
+    /// # }
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn format_file(&self, out: &mut fmt::Formatter<'_>, config: &L::Config) -> fmt::Result {
+        out.preamble(|out| out.write_file_header::<L>(config))?;
+        L::format_file(self, out, config)?;
+        out.write_trailing_line()?;
+        Ok(())
+    }
+
+    /// Measure the token stream as it would be formatted as a file, without
+    /// producing the output.
+    ///
+    /// This formats the stream into a [fmt::Metrics] writer, which counts
+    /// lines, the widest line, and the total number of bytes. This is useful
+    /// for enforcing limits on the size of generated files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     let mut m = $map::new();
+    ///     m.insert(1u32, 2u32);
+    /// };
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    /// let metrics = tokens.measure(&rust::Config::default(), &fmt)?;
+    ///
+    /// assert_eq!(metrics.lines(), 4);
+    /// assert_eq!(metrics.max_width(), 30);
+    /// assert_eq!(
+    ///     metrics.bytes(),
+    ///     tokens.to_file_string_with(&rust::Config::default(), &fmt)?.len()
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn measure(&self, config: &L::Config, fmt: &fmt::Config) -> fmt::Result<fmt::Metrics> {
+        let mut w = fmt::Metrics::new();
+        self.format_file(&mut w.as_formatter(fmt), config)?;
+        Ok(w)
+    }
+
+    /// Test if formatting the token stream as a file using the given
+    /// configuration would produce something other than `existing`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn foo() {}
+    /// };
+    ///
+    /// let config = rust::Config::default();
+    /// let fmt = fmt::Config::from_lang::<Rust>().with_trailing_newline(false);
+    ///
+    /// assert!(!tokens.differs_from_with("fn foo() {}", &config, &fmt)?);
+    /// assert!(tokens.differs_from_with("fn foo() {}\n", &config, &fmt)?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn differs_from_with(
+        &self,
+        existing: &str,
+        config: &L::Config,
+        fmt: &fmt::Config,
+    ) -> fmt::Result<bool> {
+        let mut w = fmt::ComparingWriter::new(existing);
+        self.format_file(&mut w.as_formatter(fmt), config)?;
+        Ok(w.changed())
+    }
+
+    /// Internal function to modify the indentation of the token stream.
+    fn indentation(&mut self, mut n: i16) {
+        let item = loop {
+            // flush all whitespace preceeding the indentation change.
+            match self.items.pop() {
+                Some(Item::Push) => continue,
+                Some(Item::Space | Item::SoftBreak) => continue,
+                Some(Item::Line) => continue,
+                Some(Item::Indentation(u)) => n += u,
+                item => break item,
+            }
+        };
+
+        self.items.extend(item);
+
+        if n != 0 {
+            self.items.push(Item::Indentation(n));
+        }
+    }
+}
+
+impl<L> Default for Tokens<L>
+where
+    L: Lang,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The alternate form, `{:#?}`, prints one item per line where items are
+/// indented according to the [Item::Indentation] markers in the stream.
+/// Items of [shared][Tokens::append_shared] streams are nested under the
+/// item which holds them.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let tokens: Tokens = quote! {
+///     foo {
+///         bar
+///     }
+/// };
+///
+/// let debug = format!("{:#?}", tokens);
+///
+/// // With the `locations` feature enabled, recorded locations follow as the
+/// // last field.
+/// assert_eq!(
+///     vec![
+///         "Tokens {",
+///         "    items: [",
+///         "        Literal(Static(\"foo\")),",
+///         "        Space,",
+///         "        Literal(Static(\"{\")),",
+///         "        Indentation(1),",
+///         "            Literal(Static(\"bar\")),",
+///         "        Indentation(-1),",
+///         "        Literal(Static(\"}\")),",
+///         "    ],",
+///         "    last_lang_item: 0,",
+///     ],
+///     debug.lines().take(11).collect::<Vec<_>>()
+/// );
+/// ```
+impl<L> core::fmt::Debug for Tokens<L>
+where
+    L: Lang,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let alternate = f.alternate();
+        let mut s = f.debug_struct("Tokens");
+
+        if alternate {
+            s.field("items", &IndentedItems(&self.items));
+        } else {
+            s.field("items", &self.items);
+        }
+
+        s.field("last_lang_item", &self.last_lang_item);
+        #[cfg(feature = "locations")]
+        s.field("locations", &self.locations);
+        s.finish()
+    }
+}
+
+/// Items printed one per line, indented according to the
+/// [Item::Indentation] markers they contain.
+struct IndentedItems<'a, L>(&'a [Item<L>])
+where
+    L: Lang;
+
+impl<L> IndentedItems<'_, L>
+where
+    L: Lang,
+{
+    fn fmt_at(&self, f: &mut core::fmt::Formatter<'_>, base: usize) -> core::fmt::Result {
+        writeln!(f, "[")?;
+
+        let mut level = base + 1;
+
+        for item in self.0 {
+            let n = match item {
+                Item::Indentation(n) => *n,
+                _ => 0,
+            };
+
+            if n < 0 {
+                level = level
+                    .saturating_sub(n.unsigned_abs() as usize)
+                    .max(base + 1);
+            }
+
+            write!(f, "{:indent$}", "", indent = level * 4)?;
+
+            match item {
+                Item::Shared(index, tokens) => {
+                    write!(f, "Shared({:?}, ", index)?;
+                    IndentedItems(&tokens.items).fmt_at(f, level)?;
+                    writeln!(f, "),")?;
+                }
+                item => writeln!(f, "{:?},", item)?,
+            }
+
+            if n > 0 {
+                level += n as usize;
+            }
+        }
+
+        write!(f, "{:indent$}]", "", indent = base * 4)
+    }
+}
+
+impl<L> core::fmt::Debug for IndentedItems<'_, L>
+where
+    L: Lang,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_at(f, 0)
+    }
+}
+
+impl Tokens<()> {
+    /// Cast a language-neutral token stream into a token stream for another
+    /// language.
+    ///
+    /// This allows helpers which produce language-neutral snippets to be used
+    /// with any language. The cast is necessary since a `Tokens<()>` can't be
+    /// interpolated directly into token streams of other languages. Use
+    /// [neutral()] to interpolate it while skipping over language items
+    /// instead.
+    ///
+    /// [neutral()]: crate::tokens::neutral()
+    ///
+    /// # Errors
+    ///
+    /// Language items can't be converted between languages, so this errors
+    /// with the index of the first language item in the stream if there are
+    /// any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "std")] {
+    /// use genco::prelude::*;
+    ///
+    /// fn answer() -> Tokens<()> {
+    ///     quote!(42)
+    /// }
+    ///
+    /// let answer = answer().lang_cast::<Rust>()?;
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn answer() -> u32 {
+    ///         $answer
+    ///     }
+    /// };
+    ///
+    /// assert_eq!(
+    ///     vec!["fn answer() -> u32 {", "    42", "}"],
     ///     tokens.to_file_vec()?
     /// );
-    /// # Ok::<_, genco::fmt::Error>(())
+    /// # }
+    /// # Ok::<_, anyhow::Error>(())
     /// ```
-    pub fn unindent(&mut self) {
-        self.indentation(-1);
+    pub fn lang_cast<L>(self) -> Result<Tokens<L>, crate::tokens::LangCastError>
+    where
+        L: Lang,
+    {
+        self.cast(true)
     }
 
-    /// Formatting function for token streams that gives full control over the
-    /// formatting environment.
-    ///
-    /// The configurations and `format` arguments will be provided to all
-    /// registered language items as well, and can be used to customize
-    /// formatting through [LangItem::format()].
-    ///
-    /// The `format` argument is primarily used internally by
-    /// [Lang::format_file] to provide intermediate state that can be affect how
-    /// language items are formatter. So formatting something as a file might
-    /// yield different results than using this raw formatting function.
+    /// Cast into a token stream for another language, skipping over any
+    /// language items since language items for `()` render nothing.
+    pub(crate) fn lang_cast_lossy<L>(self) -> Tokens<L>
+    where
+        L: Lang,
+    {
+        match self.cast(false) {
+            Ok(tokens) => tokens,
+            Err(..) => unreachable!("lossy cast can't fail"),
+        }
+    }
+
+    fn cast<L>(self, strict: bool) -> Result<Tokens<L>, crate::tokens::LangCastError>
+    where
+        L: Lang,
+    {
+        let mut items = Vec::with_capacity(self.items.len());
+        let mut last_lang_item = 0;
+
+        // New index of each existing item.
+        #[cfg(feature = "locations")]
+        let mut indexes = Vec::with_capacity(self.items.len());
+
+        for (index, item) in self.items.into_iter().enumerate() {
+            let item = match item {
+                Item::Literal(literal) => Item::Literal(literal),
+                Item::Push => Item::Push,
+                Item::Line => Item::Line,
+                Item::Space => Item::Space,
+                Item::Indentation(n) => Item::Indentation(n),
+                Item::OpenQuote(has_eval) => Item::OpenQuote(has_eval),
+                Item::CloseQuote => Item::CloseQuote,
+                Item::Escaped(literal) => Item::Escaped(literal),
+                Item::OpenEval => Item::OpenEval,
+                Item::CloseEval => Item::CloseEval,
+                Item::TrailingComma => Item::TrailingComma,
+                Item::OperatorSpace => Item::OperatorSpace,
+                Item::SoftBreak => Item::SoftBreak,
+                Item::Lang(..) | Item::Register(..) => {
+                    if strict {
+                        return Err(crate::tokens::LangCastError::new(index));
+                    }
+
+                    #[cfg(feature = "locations")]
+                    indexes.push(None);
+                    continue;
+                }
+                Item::Shared(_, tokens) => {
+                    let tokens = Tokens::clone(&tokens)
+                        .cast(strict)
+                        .map_err(|_| crate::tokens::LangCastError::new(index))?;
+
+                    // NB: recorded position needs to be adjusted.
+                    let prev = mem::replace(&mut last_lang_item, items.len() + 1);
+                    Item::Shared(prev, Arc::new(tokens))
+                }
+            };
+
+            #[cfg(feature = "locations")]
+            indexes.push(Some(items.len()));
+            items.push(item);
+        }
+
+        #[cfg(feature = "locations")]
+        let mut locations = self.locations;
+
+        #[cfg(feature = "locations")]
+        locations
+            .locations
+            .retain_mut(|(index, _)| match indexes.get(*index).copied().flatten() {
+                Some(new_index) => {
+                    *index = new_index;
+                    true
+                }
+                None => false,
+            });
+
+        Ok(Tokens {
+            items,
+            last_lang_item,
+            #[cfg(feature = "locations")]
+            locations,
+        })
+    }
+}
+
+impl<L> Tokens<L>
+where
+    L: LangSupportsEval,
+{
+    /// Helper function to determine if the token stream supports evaluation at compile time.
+    #[doc(hidden)]
+    #[inline]
+    pub fn lang_supports_eval(&self) {}
+}
+
+impl<L> Tokens<L>
+where
+    L: Lang,
+    L::Config: Default,
+{
+    /// Format the token stream as a file for the given target language to a
+    /// string using the default configuration.
     ///
-    /// Available formatters:
+    /// This is a shorthand to using [FmtWriter][fmt::FmtWriter] directly in
+    /// combination with [format][Self::format_file].
     ///
-    /// * [fmt::VecWriter] - To write result into a vector.
-    /// * [fmt::FmtWriter] - To write the result into something implementing
-    ///   [fmt::Write][std::fmt::Write].
-    /// * [fmt::IoWriter]- To write the result into something implementing
-    ///   [io::Write][std::io::Write].
+    /// This function will render imports.
     ///
     /// # Examples
     ///
-    /// ```,no_run
+    /// ```
     /// use genco::prelude::*;
     /// use genco::fmt;
     ///
@@ -522,173 +2585,357 @@ where
     ///     m.insert(1u32, 2u32);
     /// };
     ///
-    /// let stdout = std::io::stdout();
-    /// let mut w = fmt::IoWriter::new(stdout.lock());
+    /// assert_eq!(
+    ///     "use std::collections::HashMap;\n\nlet mut m = HashMap::new();\nm.insert(1u32, 2u32);\n",
+    ///     tokens.to_file_string()?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn to_file_string(&self) -> fmt::Result<String> {
+        let fmt = fmt::Config::from_lang::<L>();
+        let config = L::Config::default();
+        self.to_file_string_with(&config, &fmt)
+    }
+
+    /// Format the token stream as a file for the given target language,
+    /// returning it as a string using the provided configuration.
     ///
-    /// let fmt = fmt::Config::from_lang::<Rust>()
-    ///     .with_indentation(fmt::Indentation::Space(2));
-    /// let mut formatter = w.as_formatter(&fmt);
-    /// let config = rust::Config::default();
+    /// See [to_file_string][Self::to_file_string] for details.
     ///
-    /// // Default format state for Rust.
-    /// let format = rust::Format::default();
+    /// # Examples
     ///
-    /// tokens.format(&mut formatter, &config, &format)?;
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let tokens: java::Tokens = quote! {
+    ///     class Foo {
+    ///         void bar() {}
+    ///     }
+    /// };
+    ///
+    /// let config = java::Config::default().with_package("se.tedro");
+    /// let fmt = fmt::Config::from_lang::<Java>()
+    ///     .with_indentation(fmt::Indentation::Tab);
+    ///
+    /// assert_eq!(
+    ///     "package se.tedro;\n\nclass Foo {\n\tvoid bar() {}\n}\n",
+    ///     tokens.to_file_string_with(&config, &fmt)?
+    /// );
     /// # Ok::<_, genco::fmt::Error>(())
     /// ```
+    pub fn to_file_string_with(
+        &self,
+        config: &L::Config,
+        fmt: &fmt::Config,
+    ) -> fmt::Result<String> {
+        let mut w = fmt::FmtWriter::new(String::new());
+        self.format_file(&mut w.as_formatter(fmt), config)?;
+        Ok(w.into_inner())
+    }
+
+    /// Format the token stream as a file and collect its lines into a vector
+    /// using the given configuration and [VecWriter][fmt::VecWriter].
     ///
-    /// [LangItem::format()]: crate::lang::LangItem::format()
-    pub fn format(
+    /// The writer determines how lines are collected, like whether the
+    /// trailing line ending of the file results in a final empty line. See
+    /// [VecWriter::with_keep_trailing][fmt::VecWriter::with_keep_trailing].
+    ///
+    /// This function will render imports.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn foo() {
+    ///         bar();
+    ///     }
+    /// };
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>();
+    /// let config = rust::Config::default();
+    ///
+    /// assert_eq!(
+    ///     vec!["fn foo() {", "    bar();", "}", ""],
+    ///     tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new().with_keep_trailing(true))?
+    /// );
+    ///
+    /// assert_eq!(
+    ///     vec!["fn foo() {", "    bar();", "}"],
+    ///     tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+    /// );
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn to_file_vec_with(
         &self,
-        out: &mut fmt::Formatter<'_>,
         config: &L::Config,
-        format: &L::Format,
-    ) -> fmt::Result {
-        out.format_items(&self.items, config, format)
+        fmt: &fmt::Config,
+        mut w: fmt::VecWriter,
+    ) -> fmt::Result<Vec<String>> {
+        self.format_file(&mut w.as_formatter(fmt), config)?;
+        Ok(w.into_vec())
     }
 
-    /// Push a single item to the stream while checking for structural
-    /// guarantees.
+    /// Format the token stream as a file using the given configuration and
+    /// iterate over its lines.
+    ///
+    /// The whole file is formatted into a single buffer when this is called,
+    /// and lines are then copied out of it one at a time as they're
+    /// requested. So unlike [to_file_vec][Self::to_file_vec] only one line is
+    /// allocated at a time, but the complete output is still held in memory.
+    /// To process output which doesn't fit in memory, write it through an
+    /// [IoWriter][fmt::IoWriter] instead. If formatting fails, the error is
+    /// produced as the only item.
+    ///
+    /// Like [str::lines], the trailing line ending doesn't result in a final
+    /// empty line.
     ///
     /// # Examples
     ///
     /// ```
     /// use genco::prelude::*;
-    /// use genco::tokens::{Item, ItemStr};
+    /// use genco::fmt;
     ///
-    /// let mut tokens = Tokens::<()>::new();
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn foo() {
+    ///         bar();
+    ///     }
+    /// };
     ///
-    /// tokens.append(ItemStr::Static("foo"));
-    /// tokens.space();
-    /// tokens.space(); // Note: second space ignored
-    /// tokens.append(ItemStr::Static("bar"));
+    /// let fmt = fmt::Config::from_lang::<Rust>().with_newline("\r\n");
+    /// let config = rust::Config::default();
     ///
-    /// assert_eq!(tokens, quote!(foo bar));
+    /// let mut lines = tokens.lines(&config, &fmt);
+    /// assert_eq!(Some("fn foo() {"), lines.next().transpose()?.as_deref());
+    ///
+    /// let rest = lines.collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(vec!["    bar();", "}"], rest);
+    /// # Ok::<_, genco::fmt::Error>(())
     /// ```
-    pub(crate) fn item(&mut self, item: Item<L>) {
-        match item {
-            Item::Push => self.push(),
-            Item::Line => self.line(),
-            Item::Space => self.space(),
-            Item::Indentation(n) => self.indentation(n),
-            Item::Lang(_, item) => self.lang_item(item),
-            Item::Register(_, item) => self.lang_item_register(item),
-            other => self.items.push(other),
-        }
+    pub fn lines(
+        &self,
+        config: &L::Config,
+        fmt: &fmt::Config,
+    ) -> impl Iterator<Item = fmt::Result<String>> {
+        let (output, mut error) = match self.to_file_string_with(config, fmt) {
+            Ok(output) => (output, None),
+            Err(error) => (String::new(), Some(error)),
+        };
+
+        let mut offset = 0;
+
+        iter::from_fn(move || {
+            if let Some(error) = error.take() {
+                return Some(Err(error));
+            }
+
+            let rest = &output[offset..];
+
+            if rest.is_empty() {
+                return None;
+            }
+
+            let end = rest.find('\n').map_or(rest.len(), |n| n + 1);
+            offset += end;
+
+            let line = &rest[..end];
+            let line = line.strip_suffix('\n').unwrap_or(line);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            Some(Ok(String::from(line)))
+        })
     }
 
-    /// Add a language item directly.
-    pub(crate) fn lang_item(&mut self, item: Box<L::Item>) {
-        // NB: recorded position needs to be adjusted.
-        self.items
-            .push(crate::tokens::Item::Lang(self.last_lang_item, item));
-        self.last_lang_item = self.items.len();
+    /// Format the token stream as a file for the given target language and
+    /// write it to the given path using the default configuration.
+    ///
+    /// Any missing parent directories of the path are created, and the file
+    /// is written with a trailing line ending.
+    ///
+    /// This function will render imports.
+    ///
+    /// # Errors
+    ///
+    /// Errors are reported through [WriteFileError][fmt::WriteFileError],
+    /// which distinguishes I/O failures from formatting errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     let mut m = $map::new();
+    /// };
+    ///
+    /// let dir = std::env::temp_dir().join("genco-to-file-path");
+    /// let path = dir.join("src").join("lib.rs");
+    /// tokens.to_file_path(&path)?;
+    ///
+    /// assert_eq!(
+    ///     "use std::collections::HashMap;\n\nlet mut m = HashMap::new();\n",
+    ///     std::fs::read_to_string(&path)?
+    /// );
+    ///
+    /// std::fs::remove_dir_all(&dir)?;
+    /// # Ok::<_, anyhow::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_file_path<P>(&self, path: P) -> Result<(), fmt::WriteFileError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let fmt = fmt::Config::from_lang::<L>();
+        let config = L::Config::default();
+        self.to_file_path_with(path, &config, &fmt)
     }
 
-    /// Register a language item directly.
-    pub(crate) fn lang_item_register(&mut self, item: Box<L::Item>) {
-        // NB: recorded position needs to be adjusted.
-        self.items
-            .push(crate::tokens::Item::Register(self.last_lang_item, item));
-        self.last_lang_item = self.items.len();
+    /// Format the token stream as a file for the given target language and
+    /// write it to the given path using the provided configuration.
+    ///
+    /// See [to_file_path][Self::to_file_path] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    /// use genco::fmt;
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn foo() {
+    ///         bar();
+    ///     }
+    /// };
+    ///
+    /// let dir = std::env::temp_dir().join("genco-to-file-path-with");
+    /// let path = dir.join("lib.rs");
+    ///
+    /// let fmt = fmt::Config::from_lang::<Rust>()
+    ///     .with_indentation(fmt::Indentation::Space(2));
+    /// tokens.to_file_path_with(&path, &rust::Config::default(), &fmt)?;
+    ///
+    /// assert_eq!(
+    ///     "fn foo() {\n  bar();\n}\n",
+    ///     std::fs::read_to_string(&path)?
+    /// );
+    ///
+    /// std::fs::remove_dir_all(&dir)?;
+    /// # Ok::<_, anyhow::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_file_path_with<P>(
+        &self,
+        path: P,
+        config: &L::Config,
+        fmt: &fmt::Config,
+    ) -> Result<(), fmt::WriteFileError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref();
+
+        let mut w = fmt::FmtWriter::new(String::new());
+        self.format_file(&mut w.as_formatter(fmt), config)?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(path, w.into_inner())?;
+        Ok(())
     }
 
-    /// File formatting function for token streams that gives full control over the
-    /// formatting environment.
+    /// Test if formatting the token stream as a file using the default
+    /// configuration would produce something other than `existing`.
+    ///
+    /// This uses a [ComparingWriter][fmt::ComparingWriter], so the output is
+    /// never stored and comparison stops at the first difference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn foo() {}
+    /// };
+    ///
+    /// assert!(!tokens.differs_from("fn foo() {}\n")?);
+    /// assert!(tokens.differs_from("fn foo() {}")?);
+    /// assert!(tokens.differs_from("fn bar() {}\n")?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn differs_from(&self, existing: &str) -> fmt::Result<bool> {
+        let fmt = fmt::Config::from_lang::<L>();
+        let config = L::Config::default();
+        self.differs_from_with(existing, &config, &fmt)
+    }
+
+    /// Test if writing the token stream to the given path with
+    /// [to_file_path][Self::to_file_path] would change the file, which is
+    /// the case if it doesn't exist.
     ///
-    /// File formatting will render preambles like namespace declarations and
-    /// imports.
+    /// This can be used to avoid rewriting files which are up to date, which
+    /// keeps their modification times stable for incremental builds.
     ///
-    /// Available formatters:
+    /// # Errors
     ///
-    /// * [fmt::VecWriter] - To write result into a vector.
-    /// * [fmt::FmtWriter] - To write the result into something implementing
-    ///   [fmt::Write][std::fmt::Write].
-    /// * [fmt::IoWriter]- To write the result into something implementing
-    ///   [io::Write][std::io::Write].
+    /// Errors are reported through [WriteFileError][fmt::WriteFileError],
+    /// which distinguishes I/O failures from formatting errors.
     ///
     /// # Examples
     ///
-    /// ```,no_run
+    /// ```
     /// use genco::prelude::*;
-    /// use genco::fmt;
-    ///
-    /// let map = rust::import("std::collections", "HashMap");
     ///
     /// let tokens: rust::Tokens = quote! {
-    ///     let mut m = $map::new();
-    ///     m.insert(1u32, 2u32);
+    ///     fn foo() {}
     /// };
     ///
-    /// let stdout = std::io::stdout();
-    /// let mut w = fmt::IoWriter::new(stdout.lock());
+    /// let dir = std::env::temp_dir().join("genco-differs-from-path");
+    /// let path = dir.join("lib.rs");
     ///
-    /// let fmt = fmt::Config::from_lang::<Rust>()
-    ///     .with_indentation(fmt::Indentation::Space(2));
-    /// let mut formatter = w.as_formatter(&fmt);
-    /// let config = rust::Config::default();
+    /// assert!(tokens.differs_from_path(&path)?);
+    /// tokens.to_file_path(&path)?;
+    /// assert!(!tokens.differs_from_path(&path)?);
     ///
-    /// tokens.format_file(&mut formatter, &config)?;
-    /// # Ok::<_, genco::fmt::Error>(())
+    /// std::fs::remove_dir_all(&dir)?;
+    /// # Ok::<_, anyhow::Error>(())
     /// ```
-    pub fn format_file(&self, out: &mut fmt::Formatter<'_>, config: &L::Config) -> fmt::Result {
-        L::format_file(self, out, config)?;
-        out.write_trailing_line()?;
-        Ok(())
-    }
-
-    /// Internal function to modify the indentation of the token stream.
-    fn indentation(&mut self, mut n: i16) {
-        let item = loop {
-            // flush all whitespace preceeding the indentation change.
-            match self.items.pop() {
-                Some(Item::Push) => continue,
-                Some(Item::Space) => continue,
-                Some(Item::Line) => continue,
-                Some(Item::Indentation(u)) => n += u,
-                item => break item,
-            }
+    #[cfg(feature = "std")]
+    pub fn differs_from_path<P>(&self, path: P) -> Result<bool, fmt::WriteFileError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let existing = match std::fs::read(path) {
+            Ok(existing) => existing,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(true),
+            Err(e) => return Err(e.into()),
         };
 
-        self.items.extend(item);
-
-        if n != 0 {
-            self.items.push(Item::Indentation(n));
-        }
-    }
-}
+        // NB: output is always valid UTF-8.
+        let Ok(existing) = core::str::from_utf8(&existing) else {
+            return Ok(true);
+        };
 
-impl<L> Default for Tokens<L>
-where
-    L: Lang,
-{
-    fn default() -> Self {
-        Self::new()
+        Ok(self.differs_from(existing)?)
     }
-}
-
-impl<L> Tokens<L>
-where
-    L: LangSupportsEval,
-{
-    /// Helper function to determine if the token stream supports evaluation at compile time.
-    #[doc(hidden)]
-    #[inline]
-    pub fn lang_supports_eval(&self) {}
-}
 
-impl<L> Tokens<L>
-where
-    L: Lang,
-    L::Config: Default,
-{
-    /// Format the token stream as a file for the given target language to a
-    /// string using the default configuration.
+    /// Format the token stream as a file and write it to the given
+    /// asynchronous writer, using the given configuration.
     ///
-    /// This is a shorthand to using [FmtWriter][fmt::FmtWriter] directly in
-    /// combination with [format][Self::format_file].
+    /// The file is formatted once into the internal buffer of the writer,
+    /// which splits it into chunks of about its
+    /// [threshold][fmt::AsyncIoWriter::with_threshold] at line boundaries.
+    /// The chunks are then [flushed][fmt::AsyncIoWriter::flush], waiting for
+    /// the underlying writer to accept each of them. Output the writer
+    /// doesn't accept while formatting is buffered in memory until then.
     ///
-    /// This function will render imports.
+    /// This requires the `tokio` feature to be enabled.
     ///
     /// # Examples
     ///
@@ -696,26 +2943,41 @@ where
     /// use genco::prelude::*;
     /// use genco::fmt;
     ///
-    /// let map = rust::import("std::collections", "HashMap");
+    /// async fn run() -> Result<(), genco::fmt::WriteFileError> {
+    ///     let map = rust::import("std::collections", "HashMap");
     ///
-    /// let tokens: rust::Tokens = quote! {
-    ///     let mut m = $map::new();
-    ///     m.insert(1u32, 2u32);
-    /// };
+    ///     let tokens: rust::Tokens = quote! {
+    ///         let mut m = $map::new();
+    ///     };
     ///
-    /// assert_eq!(
-    ///     "use std::collections::HashMap;\n\nlet mut m = HashMap::new();\nm.insert(1u32, 2u32);\n",
-    ///     tokens.to_file_string()?
-    /// );
-    /// # Ok::<_, genco::fmt::Error>(())
+    ///     let mut w = fmt::AsyncIoWriter::new(Vec::<u8>::new());
+    ///
+    ///     let fmt = fmt::Config::from_lang::<Rust>();
+    ///     tokens.format_file_async(&mut w, &rust::Config::default(), &fmt).await?;
+    ///
+    ///     assert_eq!(
+    ///         b"use std::collections::HashMap;\n\nlet mut m = HashMap::new();\n",
+    ///         &w.into_inner()[..]
+    ///     );
+    ///     Ok(())
+    /// }
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build()?.block_on(run())?;
+    /// # Ok::<_, genco::fmt::WriteFileError>(())
     /// ```
-    pub fn to_file_string(&self) -> fmt::Result<String> {
-        let mut w = fmt::FmtWriter::new(String::new());
-        let fmt = fmt::Config::from_lang::<L>();
-        let mut formatter = w.as_formatter(&fmt);
-        let config = L::Config::default();
-        self.format_file(&mut formatter, &config)?;
-        Ok(w.into_inner())
+    #[cfg(feature = "tokio")]
+    pub async fn format_file_async<W>(
+        &self,
+        w: &mut fmt::AsyncIoWriter<W>,
+        config: &L::Config,
+        fmt: &fmt::Config,
+    ) -> Result<(), fmt::WriteFileError>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        self.format_file(&mut w.as_formatter(fmt), config)?;
+        w.flush().await?;
+        Ok(())
     }
 
     /// Format only the current token stream as a string using the default
@@ -724,7 +2986,8 @@ where
     /// This is a shorthand to using [FmtWriter][fmt::FmtWriter] directly in
     /// combination with [format][Self::format].
     ///
-    /// This function _will not_ render imports.
+    /// This function _will not_ render imports. Since it borrows the token
+    /// stream, it can be used to inspect a stream while it's being built.
     ///
     /// # Examples
     ///
@@ -820,6 +3083,39 @@ where
         Ok(w.into_vec())
     }
 
+    /// Count the number of lines the token stream produces when formatted as
+    /// a file using the default configuration.
+    ///
+    /// This matches the number of lines produced by
+    /// [to_file_vec][Self::to_file_vec], but the output is only measured
+    /// through [fmt::Metrics] instead of being stored. See
+    /// [measure][Self::measure] to use a custom configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use genco::prelude::*;
+    ///
+    /// let map = rust::import("std::collections", "HashMap");
+    ///
+    /// let tokens: rust::Tokens = quote! {
+    ///     fn foo() {
+    ///         let mut m = $map::new();
+    ///
+    ///         m.insert(1u32, 2u32);
+    ///     }
+    /// };
+    ///
+    /// assert_eq!(7, tokens.count_lines()?);
+    /// assert_eq!(tokens.to_file_vec()?.len(), tokens.count_lines()?);
+    /// # Ok::<_, genco::fmt::Error>(())
+    /// ```
+    pub fn count_lines(&self) -> fmt::Result<usize> {
+        let fmt = fmt::Config::from_lang::<L>();
+        let config = L::Config::default();
+        Ok(self.measure(&config, &fmt)?.lines())
+    }
+
     /// Helper function to format tokens into a vector, where each entry equals
     /// a line using the default configuration.
     ///
@@ -978,6 +3274,70 @@ where
     }
 }
 
+/// Mutable iterator over [Tokens].
+///
+/// This is created using [Tokens::iter_mut()].
+pub struct IterMut<'a, L>
+where
+    L: Lang,
+{
+    iter: slice::IterMut<'a, Item<L>>,
+}
+
+impl<'a, L> Iterator for IterMut<'a, L>
+where
+    L: Lang,
+{
+    type Item = ItemMut<'a, L>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(ItemMut {
+            item: self.iter.next()?,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// A mutable reference to an item in [Tokens], which only permits rewriting
+/// literals.
+///
+/// This is created using [Tokens::iter_mut()].
+pub struct ItemMut<'a, L>
+where
+    L: Lang,
+{
+    item: &'a mut Item<L>,
+}
+
+impl<'a, L> ItemMut<'a, L>
+where
+    L: Lang,
+{
+    /// Access the string of a literal item, which can be rewritten.
+    ///
+    /// Returns `None` if the item is not a [literal][Item::Literal].
+    pub fn literal_mut(&mut self) -> Option<&mut ItemStr> {
+        match self.item {
+            Item::Literal(literal) => Some(literal),
+            _ => None,
+        }
+    }
+}
+
+impl<L> ops::Deref for ItemMut<'_, L>
+where
+    L: Lang,
+{
+    type Target = Item<L>;
+
+    fn deref(&self) -> &Self::Target {
+        self.item
+    }
+}
+
 impl<'a, L> IntoIterator for &'a Tokens<L>
 where
     L: Lang,
@@ -1016,6 +3376,149 @@ where
     }
 }
 
+/// Concatenate token streams, one after another.
+///
+/// Each stream is appended like with [Tokens::append], so the structural
+/// guarantees of the resulting stream are upheld.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let fragments: Vec<rust::Tokens> = vec![
+///     quote!(fn foo() {}),
+///     quote!($['\n']),
+///     quote!(fn bar() {}),
+/// ];
+///
+/// let tokens = fragments.into_iter().collect::<rust::Tokens>();
+///
+/// assert_eq!(vec!["fn foo() {}", "", "fn bar() {}"], tokens.to_vec()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+impl<L> FromIterator<Tokens<L>> for Tokens<L>
+where
+    L: Lang,
+{
+    fn from_iter<I: IntoIterator<Item = Tokens<L>>>(iter: I) -> Self {
+        let mut tokens = Self::new();
+
+        for t in iter {
+            tokens.append(t);
+        }
+
+        tokens
+    }
+}
+
+/// Extend the token stream with items, respecting the structural requirements
+/// of the stream like [Tokens::extend].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+///
+/// let a: Tokens<()> = quote!(foo bar);
+/// let b: Tokens<()> = quote!(baz);
+///
+/// let mut tokens = Tokens::new();
+/// Extend::extend(&mut tokens, &a);
+/// tokens.space();
+/// Extend::extend(&mut tokens, b);
+///
+/// assert_eq!(tokens, quote!(foo bar baz));
+/// ```
+impl<L> Extend<Item<L>> for Tokens<L>
+where
+    L: Lang,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Item<L>>,
+    {
+        Tokens::extend(self, iter)
+    }
+}
+
+impl<'a, L> Extend<&'a Item<L>> for Tokens<L>
+where
+    L: Lang,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a Item<L>>,
+    {
+        Tokens::extend(self, iter.into_iter().cloned())
+    }
+}
+
+/// Append anything implementing [FormatInto] to a token stream, like
+/// [Tokens::append].
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::{block, Item};
+///
+/// fn function(name: &str, body: rust::Tokens) -> rust::Tokens {
+///     let signature: rust::Tokens = quote!(fn $name());
+///     signature + Item::Space + block(body)
+/// }
+///
+/// fn file(functions: Vec<rust::Tokens>) -> rust::Tokens {
+///     let mut tokens = rust::Tokens::new();
+///
+///     for function in functions {
+///         tokens += Item::Line;
+///         tokens += function;
+///     }
+///
+///     tokens
+/// }
+///
+/// let tokens = file(vec![
+///     function("foo", quote!(bar();)),
+///     function("bar", quote!()),
+/// ]);
+///
+/// assert_eq!(
+///     vec![
+///         "fn foo() {",
+///         "    bar();",
+///         "}",
+///         "",
+///         "fn bar() {}",
+///     ],
+///     tokens.to_file_vec()?
+/// );
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+impl<L, T> ops::Add<T> for Tokens<L>
+where
+    L: Lang,
+    T: FormatInto<L>,
+{
+    type Output = Tokens<L>;
+
+    fn add(mut self, rhs: T) -> Self::Output {
+        self.append(rhs);
+        self
+    }
+}
+
+impl<L, T> ops::AddAssign<T> for Tokens<L>
+where
+    L: Lang,
+    T: FormatInto<L>,
+{
+    fn add_assign(&mut self, rhs: T) {
+        self.append(rhs);
+    }
+}
+
 /// An iterator over language-specific imported items.
 ///
 /// Constructed using the [Tokens::walk_imports] method.
@@ -1025,6 +3528,11 @@ where
 {
     items: &'a [Item<L>],
     pos: usize,
+    /// Streams to resume walking once a shared stream has been walked.
+    stack: Vec<(&'a [Item<L>], usize)>,
+    /// Registered imports which have been walked, if repeated registrations
+    /// should be skipped.
+    registered: Option<BTreeSet<&'a L::Item>>,
 }
 
 impl<'a, L> Iterator for WalkImports<'a, L>
@@ -1034,31 +3542,55 @@ where
     type Item = &'a L::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let pos = mem::take(&mut self.pos);
+        loop {
+            let pos = mem::take(&mut self.pos);
+
+            if pos == 0 {
+                let (items, pos) = self.stack.pop()?;
+                self.items = items;
+                self.pos = pos;
+                continue;
+            }
 
-        if pos == 0 {
-            return None;
-        }
+            // NB: recorded position needs to be adjusted.
+            let item = self.items.get(pos - 1)?;
 
-        // NB: recorded position needs to be adjusted.
-        let item = self.items.get(pos - 1)?;
+            let (prev, item) = match item {
+                Item::Lang(prev, item) => (prev, item),
+                Item::Register(prev, item) => {
+                    if let Some(registered) = &mut self.registered {
+                        if !registered.insert(&**item) {
+                            self.pos = *prev;
+                            continue;
+                        }
+                    }
 
-        let (prev, item) = match item {
-            Item::Lang(prev, item) => (prev, item),
-            Item::Register(prev, item) => (prev, item),
-            _ => return None,
-        };
+                    (prev, item)
+                }
+                Item::Shared(prev, tokens) => {
+                    self.stack.push((self.items, *prev));
+                    self.items = &tokens.items;
+                    self.pos = tokens.last_lang_item;
+                    continue;
+                }
+                _ => return None,
+            };
 
-        self.pos = *prev;
-        Some(item)
+            self.pos = *prev;
+            return Some(item);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
     use crate as genco;
     use crate::fmt;
-    use crate::{quote, Tokens};
+    use crate::{quote, quote_in, Tokens};
 
     /// Own little custom language for this test.
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -1068,12 +3600,12 @@ mod tests {
         Lang {
             type Config = ();
             type Format = ();
-            type Item = Import;
+            type Item = Any;
         }
 
         Import {
             fn format(&self, out: &mut fmt::Formatter<'_>, _: &(), _: &()) -> fmt::Result {
-                use std::fmt::Write as _;
+                use core::fmt::Write as _;
                 write!(out, "{}", self.0)
             }
         }
@@ -1091,7 +3623,33 @@ mod tests {
         let mut output: Vec<_> = toks.walk_imports().cloned().collect();
         output.sort();
 
-        let expected = vec![Import(1), Import(2)];
+        let expected = vec![Any::Import(Import(1)), Any::Import(Import(2))];
+
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn test_walk_imports_dedup() {
+        let mut toks: Tokens<Lang> = Tokens::new();
+
+        for _ in 0..3 {
+            toks.register(Import(1));
+            toks.register(Import(2));
+        }
+
+        quote_in!(toks => $(Import(1)) $(Import(1)));
+
+        assert_eq!(8, toks.walk_imports().count());
+
+        let mut output: Vec<_> = toks.walk_imports_dedup().cloned().collect();
+        output.sort();
+
+        let expected = vec![
+            Any::Import(Import(1)),
+            Any::Import(Import(1)),
+            Any::Import(Import(1)),
+            Any::Import(Import(2)),
+        ];
 
         assert_eq!(expected, output);
     }
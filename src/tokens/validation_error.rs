@@ -0,0 +1,163 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Error raised by [Tokens::validate][crate::Tokens::validate] when a token
+/// stream wouldn't produce valid code.
+///
+/// Variants which concern an item carry the index of the offending item, as
+/// yielded by [Tokens::iter][crate::Tokens::iter].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// An imported name collides with a reserved word in the language.
+    ReservedName {
+        /// The index of the language item which imports the name.
+        index: usize,
+        /// The reserved name.
+        name: String,
+    },
+    /// A whitespace item which violates the structural guarantees of token
+    /// streams, like two spaces in a row or a push directly after a line.
+    RedundantWhitespace {
+        /// The index of the redundant whitespace item.
+        index: usize,
+    },
+    /// A [CloseQuote][crate::tokens::Item::CloseQuote] without a matching
+    /// open quote.
+    UnexpectedCloseQuote {
+        /// The index of the close quote.
+        index: usize,
+    },
+    /// An [OpenQuote][crate::tokens::Item::OpenQuote] which is never closed.
+    UnclosedQuote {
+        /// The index of the open quote.
+        index: usize,
+    },
+    /// An [OpenEval][crate::tokens::Item::OpenEval] outside of a quote.
+    UnexpectedOpenEval {
+        /// The index of the open evaluation.
+        index: usize,
+    },
+    /// A [CloseEval][crate::tokens::Item::CloseEval] without a matching open
+    /// evaluation.
+    UnexpectedCloseEval {
+        /// The index of the close evaluation.
+        index: usize,
+    },
+    /// An [OpenEval][crate::tokens::Item::OpenEval] which is never closed.
+    UnclosedEval {
+        /// The index of the open evaluation.
+        index: usize,
+    },
+    /// A token stream is nested deeper than the limit configured through
+    /// [Config::with_max_depth][crate::fmt::Config::with_max_depth].
+    ///
+    /// This is only reported by [Formatter::error][crate::fmt::Formatter::error].
+    MaxDepthExceeded {
+        /// The index of the item holding the nested token stream.
+        index: usize,
+        /// The configured maximum depth.
+        max_depth: usize,
+    },
+    /// A file was formatted with both a shebang and a
+    /// [byte order mark][crate::fmt::Config::with_bom], which can't both be
+    /// the first thing in the file.
+    ///
+    /// This is only reported by [Formatter::error][crate::fmt::Formatter::error].
+    ShebangWithBom,
+    /// Formatting was configured through
+    /// [Config::with_newline][crate::fmt::Config::with_newline] to use a line
+    /// ending which is neither `"\n"` nor `"\r\n"`.
+    ///
+    /// This is only reported by [Formatter::error][crate::fmt::Formatter::error].
+    InvalidNewline {
+        /// The configured line ending.
+        newline: &'static str,
+    },
+}
+
+impl ValidationError {
+    /// The index of the item which failed validation, if the error concerns
+    /// an item.
+    pub fn index(&self) -> Option<usize> {
+        let index = match self {
+            Self::ReservedName { index, .. }
+            | Self::RedundantWhitespace { index }
+            | Self::UnexpectedCloseQuote { index }
+            | Self::UnclosedQuote { index }
+            | Self::UnexpectedOpenEval { index }
+            | Self::UnexpectedCloseEval { index }
+            | Self::UnclosedEval { index }
+            | Self::MaxDepthExceeded { index, .. } => *index,
+            Self::ShebangWithBom | Self::InvalidNewline { .. } => return None,
+        };
+
+        Some(index)
+    }
+
+    /// Replace the index of the item which failed validation.
+    pub(crate) fn with_index(mut self, index: usize) -> Self {
+        match &mut self {
+            Self::ReservedName { index: i, .. }
+            | Self::RedundantWhitespace { index: i }
+            | Self::UnexpectedCloseQuote { index: i }
+            | Self::UnclosedQuote { index: i }
+            | Self::UnexpectedOpenEval { index: i }
+            | Self::UnexpectedCloseEval { index: i }
+            | Self::UnclosedEval { index: i }
+            | Self::MaxDepthExceeded { index: i, .. } => *i = index,
+            Self::ShebangWithBom | Self::InvalidNewline { .. } => {}
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReservedName { index, name } => write!(
+                f,
+                "imported name `{}` at index {} is a reserved word",
+                name, index
+            ),
+            Self::RedundantWhitespace { index } => {
+                write!(f, "redundant whitespace at index {}", index)
+            }
+            Self::UnexpectedCloseQuote { index } => {
+                write!(f, "close quote at index {} without an open quote", index)
+            }
+            Self::UnclosedQuote { index } => {
+                write!(f, "quote opened at index {} is never closed", index)
+            }
+            Self::UnexpectedOpenEval { index } => {
+                write!(f, "evaluation at index {} is outside of a quote", index)
+            }
+            Self::UnexpectedCloseEval { index } => write!(
+                f,
+                "close evaluation at index {} without an open evaluation",
+                index
+            ),
+            Self::UnclosedEval { index } => {
+                write!(f, "evaluation opened at index {} is never closed", index)
+            }
+            Self::MaxDepthExceeded { index, max_depth } => write!(
+                f,
+                "token stream at index {} is nested deeper than {}",
+                index, max_depth
+            ),
+            Self::ShebangWithBom => write!(
+                f,
+                "a byte order mark can't be written in a file with a shebang"
+            ),
+            Self::InvalidNewline { newline } => write!(
+                f,
+                "line ending {:?} is neither \"\\n\" nor \"\\r\\n\"",
+                newline
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
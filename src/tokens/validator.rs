@@ -0,0 +1,122 @@
+use crate::lang::Lang;
+use crate::tokens::{Item, ValidationError};
+use alloc::vec::Vec;
+
+/// A region of the token stream which has been opened but not yet closed.
+#[derive(Debug, Clone, Copy)]
+enum Open {
+    /// A quote opened at the given index.
+    Quote(usize),
+    /// An evaluation opened at the given index.
+    Eval(usize),
+}
+
+/// Incremental checker for the structural guarantees of a token stream and
+/// the nesting rules of quotes and evaluations.
+///
+/// This is used both by [Tokens::validate][crate::Tokens::validate] and to
+/// diagnose why formatting failed.
+#[derive(Debug, Default)]
+pub(crate) struct Validator {
+    stack: Vec<Open>,
+    index: usize,
+    last_whitespace: Option<Whitespace>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Whitespace {
+    Space,
+    Push,
+    Line,
+    Indentation,
+}
+
+impl Validator {
+    /// Check the next item in the stream.
+    pub(crate) fn check<L>(&mut self, item: &Item<L>) -> Result<(), ValidationError>
+    where
+        L: Lang,
+    {
+        let index = self.index;
+        self.index += 1;
+
+        let last = self.last_whitespace.take();
+
+        let whitespace = match item {
+            Item::Space | Item::SoftBreak => Some(Whitespace::Space),
+            Item::Push => Some(Whitespace::Push),
+            Item::Line => Some(Whitespace::Line),
+            Item::Indentation(_) => Some(Whitespace::Indentation),
+            _ => None,
+        };
+
+        let redundant = matches!(
+            (last, item),
+            (Some(Whitespace::Space), Item::Space | Item::SoftBreak)
+                | (
+                    Some(Whitespace::Space | Whitespace::Push | Whitespace::Line),
+                    Item::Push
+                )
+                | (Some(Whitespace::Push | Whitespace::Line), Item::Line)
+                | (Some(_), Item::Indentation(_))
+                | (_, Item::Indentation(0))
+        );
+
+        if redundant {
+            return Err(ValidationError::RedundantWhitespace { index });
+        }
+
+        self.last_whitespace = whitespace;
+
+        match item {
+            Item::OpenQuote(_) => {
+                self.stack.push(Open::Quote(index));
+            }
+            Item::CloseQuote => match self.stack.last() {
+                Some(Open::Quote(..)) => {
+                    self.stack.pop();
+                }
+                _ => return Err(ValidationError::UnexpectedCloseQuote { index }),
+            },
+            Item::OpenEval => match self.stack.last() {
+                Some(Open::Quote(..)) => {
+                    self.stack.push(Open::Eval(index));
+                }
+                _ => return Err(ValidationError::UnexpectedOpenEval { index }),
+            },
+            Item::CloseEval => match self.stack.last() {
+                Some(Open::Eval(..)) => {
+                    self.stack.pop();
+                }
+                _ => return Err(ValidationError::UnexpectedCloseEval { index }),
+            },
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Finish checking, making sure that all quotes and evaluations have been
+    /// closed.
+    pub(crate) fn finish(self) -> Result<(), ValidationError> {
+        match self.stack.last() {
+            Some(Open::Quote(index)) => Err(ValidationError::UnclosedQuote { index: *index }),
+            Some(Open::Eval(index)) => Err(ValidationError::UnclosedEval { index: *index }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Check the structure of the given items, returning the first violation.
+pub(crate) fn validate_structure<L>(items: &[Item<L>]) -> Result<(), ValidationError>
+where
+    L: Lang,
+{
+    let mut validator = Validator::default();
+
+    for item in items {
+        validator.check(item)?;
+    }
+
+    validator.finish()
+}
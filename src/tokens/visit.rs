@@ -0,0 +1,209 @@
+//! Visiting and rewriting the items of a token stream.
+//!
+//! A [Visitor] inspects the items of a stream through
+//! [Tokens::visit][crate::Tokens::visit], and a [Fold] rewrites them into a
+//! new stream through [Tokens::fold][crate::Tokens::fold].
+//!
+//! # Examples
+//!
+//! ```
+//! use genco::prelude::*;
+//! use genco::tokens::visit::{self, Visitor};
+//!
+//! #[derive(Default)]
+//! struct Stats {
+//!     literals: usize,
+//!     imports: usize,
+//! }
+//!
+//! impl Visitor<Rust> for Stats {
+//!     fn visit_literal(&mut self, _: &str) {
+//!         self.literals += 1;
+//!     }
+//!
+//!     fn visit_lang(&mut self, _: &rust::Any) {
+//!         self.imports += 1;
+//!     }
+//! }
+//!
+//! let map = rust::import("std::collections", "HashMap");
+//!
+//! let tokens: rust::Tokens = quote! {
+//!     let mut old = $map::new();
+//! };
+//!
+//! let mut stats = Stats::default();
+//! tokens.visit(&mut stats);
+//! assert_eq!(stats.literals, 5);
+//! assert_eq!(stats.imports, 1);
+//!
+//! let tokens = tokens.fold(&mut visit::rename("old", "new"));
+//!
+//! assert_eq!(
+//!     vec![
+//!         "use std::collections::HashMap;",
+//!         "",
+//!         "let mut new = HashMap::new();",
+//!     ],
+//!     tokens.to_file_vec()?
+//! );
+//! # Ok::<_, genco::fmt::Error>(())
+//! ```
+
+use crate::lang::Lang;
+use crate::tokens::{Item, ItemStr, Tokens};
+use alloc::boxed::Box;
+
+/// Callbacks for inspecting the items of a token stream.
+///
+/// Every callback does nothing by default, so implementors only need to
+/// override the ones they're interested in. The dispatch to each callback is
+/// performed by [visit_item][Visitor::visit_item].
+pub trait Visitor<L>
+where
+    L: Lang,
+{
+    /// Visit a single item, dispatching to the other callbacks.
+    fn visit_item(&mut self, item: &Item<L>) {
+        match item {
+            Item::Literal(literal) => self.visit_literal(literal),
+            Item::Escaped(literal) => self.visit_escaped(literal),
+            Item::Lang(_, item) => self.visit_lang(item),
+            Item::Register(_, item) => self.visit_register(item),
+            Item::Shared(_, tokens) => self.visit_shared(tokens),
+            Item::Push
+            | Item::Line
+            | Item::Space
+            | Item::Indentation(..)
+            | Item::OperatorSpace
+            | Item::SoftBreak => self.visit_whitespace(item),
+            Item::OpenQuote(has_eval) => self.visit_open_quote(*has_eval),
+            Item::CloseQuote => self.visit_close_quote(),
+            Item::OpenEval => self.visit_open_eval(),
+            Item::CloseEval => self.visit_close_eval(),
+            Item::TrailingComma => self.visit_trailing_comma(),
+        }
+    }
+
+    /// Visit a literal.
+    fn visit_literal(&mut self, _: &str) {}
+
+    /// Visit a literal which is written without escaping inside of quotes.
+    fn visit_escaped(&mut self, _: &str) {}
+
+    /// Visit a language item.
+    fn visit_lang(&mut self, _: &L::Item) {}
+
+    /// Visit a registered language item.
+    fn visit_register(&mut self, _: &L::Item) {}
+
+    /// Visit a shared token stream.
+    ///
+    /// By default this visits every item in the shared stream.
+    fn visit_shared(&mut self, tokens: &Tokens<L>) {
+        for item in tokens.iter() {
+            self.visit_item(item);
+        }
+    }
+
+    /// Visit a whitespace item, which is one of [Item::Push], [Item::Line],
+    /// [Item::Space], [Item::Indentation], [Item::OperatorSpace], or
+    /// [Item::SoftBreak].
+    fn visit_whitespace(&mut self, _: &Item<L>) {}
+
+    /// Visit the opening of a quote, and whether it contains evaluations.
+    fn visit_open_quote(&mut self, _: bool) {}
+
+    /// Visit the closing of a quote.
+    fn visit_close_quote(&mut self) {}
+
+    /// Visit the opening of an evaluation inside of a quote.
+    fn visit_open_eval(&mut self) {}
+
+    /// Visit the closing of an evaluation.
+    fn visit_close_eval(&mut self) {}
+
+    /// Visit a trailing comma.
+    fn visit_trailing_comma(&mut self) {}
+}
+
+/// Rewrite the items of a token stream into a new stream.
+///
+/// By default every item is appended to the output unchanged. Items are
+/// appended through [Tokens::append], so the structural guarantees of the
+/// output stream are upheld regardless of what a fold produces.
+pub trait Fold<L>
+where
+    L: Lang,
+{
+    /// Fold a single item into the output, dispatching to the other
+    /// callbacks.
+    fn fold_item(&mut self, item: Item<L>, out: &mut Tokens<L>) {
+        match item {
+            Item::Literal(literal) => self.fold_literal(literal, out),
+            Item::Lang(_, item) => self.fold_lang(item, out),
+            item => out.append(item),
+        }
+    }
+
+    /// Fold a literal.
+    fn fold_literal(&mut self, literal: ItemStr, out: &mut Tokens<L>) {
+        out.append(Item::Literal(literal));
+    }
+
+    /// Fold a language item.
+    fn fold_lang(&mut self, item: Box<L::Item>, out: &mut Tokens<L>) {
+        out.append(Item::Lang(0, item));
+    }
+}
+
+/// A fold which renames every literal which is exactly equal to `from` into
+/// `to`.
+///
+/// This struct is created by the [rename()] function.
+pub struct Rename {
+    from: ItemStr,
+    to: ItemStr,
+}
+
+impl<L> Fold<L> for Rename
+where
+    L: Lang,
+{
+    fn fold_literal(&mut self, literal: ItemStr, out: &mut Tokens<L>) {
+        if *literal == *self.from {
+            out.append(Item::Literal(self.to.clone()));
+        } else {
+            out.append(Item::Literal(literal));
+        }
+    }
+}
+
+/// Construct a fold which renames every literal equal to `from` into `to`.
+///
+/// Only whole literals are matched. Note that [quote!][crate::quote]
+/// produces one literal for each sequence of tokens which isn't separated by
+/// whitespace, so `foo` is matched in `foo = 1` but not in `foo.bar()`.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::visit;
+///
+/// let tokens: java::Tokens = quote!(int foo = foo + 1; foo.bar(););
+/// let tokens = tokens.fold(&mut visit::rename("foo", "bar"));
+///
+/// assert_eq!("int bar = bar + 1; foo.bar();", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn rename<F, T>(from: F, to: T) -> Rename
+where
+    F: Into<ItemStr>,
+    T: Into<ItemStr>,
+{
+    Rename {
+        from: from.into(),
+        to: to.into(),
+    }
+}
@@ -0,0 +1,108 @@
+use crate::lang::Lang;
+use crate::tokens::FormatInto;
+use crate::Tokens;
+
+/// Conditionally format the given value, which is only formatted if `cond`
+/// is `true`.
+///
+/// This is the programmatic equivalent of `$(if cond { ... })` in
+/// [quote!][crate::quote]. Nothing is formatted until the returned value is
+/// interpolated, so passing in something like [from_fn()] only runs the
+/// function when the condition holds.
+///
+/// [from_fn()]: crate::tokens::from_fn()
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::when;
+///
+/// let debug = true;
+/// let tokens: rust::Tokens = quote!(let y = x; $(when(debug, quote!(dbg!(y);))));
+/// assert_eq!("let y = x; dbg!(y);", tokens.to_string()?);
+///
+/// let debug = false;
+/// let tokens: rust::Tokens = quote!(let y = x; $(when(debug, quote!(dbg!(y);))));
+/// assert_eq!("let y = x;", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn when<T>(cond: bool, value: T) -> When<T> {
+    When { cond, value }
+}
+
+/// A value which is only formatted if a condition holds.
+///
+/// This is constructed with the [when()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct When<T> {
+    cond: bool,
+    value: T,
+}
+
+impl<L, T> FormatInto<L> for When<T>
+where
+    L: Lang,
+    T: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        if self.cond {
+            self.value.format_into(tokens);
+        }
+    }
+}
+
+/// Format `then` if `cond` is `true`, and `otherwise` if it isn't.
+///
+/// This is the programmatic equivalent of `$(if cond { ... } else { ... })`
+/// in [quote!][crate::quote]. Only the selected branch is formatted.
+///
+/// # Examples
+///
+/// ```
+/// use genco::prelude::*;
+/// use genco::tokens::or_else;
+///
+/// let tokens: java::Tokens = quote! {
+///     $(or_else(true, quote!(public), quote!(private))) int x;
+/// };
+/// assert_eq!("public int x;", tokens.to_string()?);
+///
+/// let tokens: java::Tokens = quote! {
+///     $(or_else(false, quote!(public), quote!(private))) int x;
+/// };
+/// assert_eq!("private int x;", tokens.to_string()?);
+/// # Ok::<_, genco::fmt::Error>(())
+/// ```
+pub fn or_else<A, B>(cond: bool, then: A, otherwise: B) -> OrElse<A, B> {
+    OrElse {
+        cond,
+        then,
+        otherwise,
+    }
+}
+
+/// One of two values, selected by a condition.
+///
+/// This is constructed with the [or_else()] function.
+#[derive(Debug, Clone, Copy)]
+pub struct OrElse<A, B> {
+    cond: bool,
+    then: A,
+    otherwise: B,
+}
+
+impl<L, A, B> FormatInto<L> for OrElse<A, B>
+where
+    L: Lang,
+    A: FormatInto<L>,
+    B: FormatInto<L>,
+{
+    fn format_into(self, tokens: &mut Tokens<L>) {
+        if self.cond {
+            self.then.format_into(tokens);
+        } else {
+            self.otherwise.format_into(tokens);
+        }
+    }
+}
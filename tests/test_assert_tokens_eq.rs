@@ -0,0 +1,76 @@
+use genco::assert_tokens_eq;
+use genco::prelude::*;
+use std::panic;
+
+fn panic_message<F>(f: F) -> String
+where
+    F: FnOnce() + panic::UnwindSafe,
+{
+    let error = panic::catch_unwind(f).expect_err("assertion should fail");
+
+    match error.downcast::<String>() {
+        Ok(message) => *message,
+        Err(..) => panic!("panic message is not a string"),
+    }
+}
+
+#[test]
+fn test_assert_tokens_eq() {
+    let tokens: rust::Tokens = quote! {
+        fn foo() {
+            bar();
+        }
+    };
+
+    assert_tokens_eq!("fn foo() {\n    bar();\n}", tokens);
+    assert_tokens_eq!(String::from("fn foo() {\n    bar();\n}\n"), &tokens);
+    assert_tokens_eq!(tokens.clone(), tokens);
+}
+
+#[test]
+fn test_assert_tokens_eq_diff() {
+    let message = panic_message(|| {
+        let tokens: rust::Tokens = quote! {
+            fn foo() {
+                bar();
+            }
+        };
+
+        assert_tokens_eq!("fn foo() {\n    baz(); \n}", tokens);
+    });
+
+    assert_eq!(
+        concat!(
+            "tokens are not equal, first difference at line 2\n",
+            "--- expected\n",
+            "+++ actual\n",
+            "   fn foo() {\n",
+            ">-     baz();·\n",
+            " +     bar();\n",
+            "   }\n",
+        ),
+        message
+    );
+}
+
+#[test]
+fn test_assert_tokens_eq_large_diff() {
+    let message = panic_message(|| {
+        let expected = (0..20_000)
+            .map(|n| format!("let a{} = {};\n", n, n))
+            .collect::<String>();
+
+        let mut tokens = rust::Tokens::new();
+
+        for n in 0..20_000 {
+            let n = if n == 10_000 { 0 } else { n };
+            quote_in!(tokens => let $(format!("a{}", n)) = $n;);
+            tokens.push();
+        }
+
+        assert_tokens_eq!(expected, tokens);
+    });
+
+    assert!(message.starts_with("tokens are not equal, first difference at line 10001\n"));
+    assert!(message.contains("\n>- let a10000 = 10000;\n + let a0 = 0;\n"));
+}
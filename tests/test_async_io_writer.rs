@@ -0,0 +1,267 @@
+#![cfg(feature = "tokio")]
+
+use std::task::{Context, Poll};
+
+use genco::fmt;
+use genco::prelude::*;
+use tokio::io::AsyncReadExt;
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+}
+
+fn function(n: u32) -> rust::Tokens {
+    quote!(fn function_$n() -> u32 { $n })
+}
+
+#[test]
+fn test_duplex_small_pipe() {
+    let tokens: rust::Tokens = quote! {
+        $(for n in 0..1000 join ($['\n']) => $(function(n)))
+    };
+
+    let expected = tokens.to_file_string().unwrap();
+
+    // NB: the pipe is much smaller than the output, so flushing has to wait
+    // for the reader to make progress.
+    let (writer, mut reader) = tokio::io::duplex(64);
+
+    let output = runtime().block_on(async move {
+        let read = tokio::spawn(async move {
+            let mut output = String::new();
+            reader.read_to_string(&mut output).await.map(|_| output)
+        });
+
+        let mut w = fmt::AsyncIoWriter::new(writer);
+        let fmt = fmt::Config::from_lang::<Rust>();
+
+        tokens
+            .format_file_async(&mut w, &rust::Config::default(), &fmt)
+            .await
+            .unwrap();
+
+        // NB: dropping the writer closes the pipe.
+        drop(w);
+        read.await.unwrap().unwrap()
+    });
+
+    assert_eq!(expected, output);
+}
+
+#[test]
+fn test_duplex_flush_between_streams() {
+    let expected = (0..1000)
+        .map(|n| format!("fn function_{}() -> u32 {{ {} }}\n", n, n))
+        .collect::<String>();
+
+    let (writer, mut reader) = tokio::io::duplex(64);
+
+    let output = runtime().block_on(async move {
+        let read = tokio::spawn(async move {
+            let mut output = String::new();
+            reader.read_to_string(&mut output).await.map(|_| output)
+        });
+
+        let mut w = fmt::AsyncIoWriter::new(writer);
+        let fmt = fmt::Config::from_lang::<Rust>();
+
+        // NB: only one function is buffered at a time, since flushing waits
+        // for the reader.
+        for n in 0..1000 {
+            function(n)
+                .format_file(&mut w.as_formatter(&fmt), &rust::Config::default())
+                .unwrap();
+            w.flush().await.unwrap();
+        }
+
+        drop(w);
+        read.await.unwrap().unwrap()
+    });
+
+    assert_eq!(expected, output);
+}
+
+#[test]
+fn test_write_error_during_formatting() {
+    use std::io;
+    use std::pin::Pin;
+
+    struct Broken;
+
+    impl tokio::io::AsyncWrite for Broken {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            _: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    let tokens: rust::Tokens = quote!(
+        fn foo() {}
+    );
+
+    let mut w = fmt::AsyncIoWriter::new(Broken).with_threshold(0);
+    let fmt = fmt::Config::from_lang::<Rust>();
+
+    // The error is recorded while formatting and reported when flushing.
+    tokens
+        .format_file(&mut w.as_formatter(&fmt), &rust::Config::default())
+        .unwrap();
+
+    let error = runtime().block_on(w.flush()).unwrap_err();
+    assert_eq!(io::ErrorKind::BrokenPipe, error.kind());
+}
+
+#[test]
+fn test_large_file_slow_writer() {
+    use std::io;
+    use std::pin::Pin;
+
+    /// A writer which only accepts a few bytes at a time, and has to be
+    /// waited for every other write.
+    #[derive(Default)]
+    struct Slow {
+        output: Vec<u8>,
+        ready: bool,
+        max_write: usize,
+    }
+
+    impl tokio::io::AsyncWrite for Slow {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            self.max_write = self.max_write.max(buf.len());
+
+            self.ready = !self.ready;
+
+            if !self.ready {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+
+            let n = buf.len().min(16);
+            self.output.extend_from_slice(&buf[..n]);
+            Poll::Ready(Ok(n))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    let tokens: rust::Tokens = quote! {
+        $(for n in 0..1000 join ($['\n']) => $(function(n)))
+    };
+
+    let expected = tokens.to_file_string().unwrap();
+
+    let mut w = fmt::AsyncIoWriter::new(Slow::default()).with_threshold(256);
+    let fmt = fmt::Config::from_lang::<Rust>();
+
+    runtime()
+        .block_on(tokens.format_file_async(&mut w, &rust::Config::default(), &fmt))
+        .unwrap();
+
+    let slow = w.into_inner();
+    assert_eq!(expected.as_bytes(), &slow.output[..]);
+
+    // NB: output is split into chunks at the first line boundary past the
+    // threshold, and written one chunk at a time.
+    assert!(expected.len() > 10 * 1024);
+    assert!(slow.max_write < 256 + 32, "{}", slow.max_write);
+}
+
+#[test]
+fn test_format_file_async_is_send() {
+    fn assert_send<T: Send>(_: T) {}
+
+    let tokens: rust::Tokens = quote!(
+        fn foo() {}
+    );
+    let mut w = fmt::AsyncIoWriter::new(Vec::<u8>::new());
+    let fmt = fmt::Config::from_lang::<Rust>();
+    let config = rust::Config::default();
+
+    assert_send(tokens.format_file_async(&mut w, &config, &fmt));
+}
+
+#[test]
+fn test_format_file_async_chunks() {
+    async fn format<L>(tokens: &Tokens<L>, config: &L::Config) -> (String, String)
+    where
+        L: Lang,
+        L::Config: Default,
+    {
+        let fmt = fmt::Config::from_lang::<L>().with_header(["Generated"]);
+        let expected = tokens.to_file_string_with(config, &fmt).unwrap();
+
+        // NB: every line is written as a separate chunk.
+        let mut w = fmt::AsyncIoWriter::new(Vec::<u8>::new()).with_threshold(0);
+        tokens
+            .format_file_async(&mut w, config, &fmt)
+            .await
+            .unwrap();
+        let output = String::from_utf8(w.into_inner()).unwrap();
+
+        (expected, output)
+    }
+
+    let list = csharp::import("System.Collections.Generic", "List");
+
+    let cs: csharp::Tokens = quote! {
+        class Foo {
+            void Bar() {
+                var names = new $list<string>();
+                Console.Write($(quoted("a $(1 + 2)")));
+            }
+        }
+    };
+
+    let cs_config = csharp::Config::default().with_namespace("Example");
+
+    let py: python::Tokens = quote! {
+        def foo():
+            return $(python::import("collections", "OrderedDict"))()
+    };
+
+    let py_config = python::Config::default().with_shebang("#!/usr/bin/env python3");
+
+    let go: go::Tokens = quote! {
+        func main() {
+            $(go::import("fmt", "Println"))("hello")
+        }
+    };
+
+    let go_config = go::Config::default()
+        .with_package("main")
+        .with_build_constraint("linux");
+
+    runtime().block_on(async {
+        let (expected, output) = format(&go, &go_config).await;
+        assert_eq!(expected, output);
+
+        let (expected, output) = format(&cs, &cs_config).await;
+        assert_eq!(expected, output);
+
+        let (expected, output) = format(&py, &py_config).await;
+        assert_eq!(expected, output);
+    });
+}
@@ -0,0 +1,28 @@
+#![cfg(feature = "std")]
+
+use genco::fmt;
+use genco::prelude::*;
+use genco::tokens::binop;
+
+#[test]
+fn test_operator_spacing() -> fmt::Result {
+    let tokens: js::Tokens = quote! {
+        const total = $(binop(binop("a", "+", "b"), "*", quote!(($(binop("c", "-", 1))))));
+    };
+
+    let config = js::Config::default();
+
+    let spaced = fmt::Config::from_lang::<JavaScript>();
+    assert_eq!(
+        "const total = a + b * (c - 1);\n",
+        tokens.to_file_string_with(&config, &spaced)?
+    );
+
+    let compact = fmt::Config::from_lang::<JavaScript>().with_operator_spacing(false);
+    assert_eq!(
+        "const total = a+b*(c-1);\n",
+        tokens.to_file_string_with(&config, &compact)?
+    );
+
+    Ok(())
+}
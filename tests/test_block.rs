@@ -0,0 +1,87 @@
+use genco::prelude::*;
+use genco::tokens::{block, BraceStyle};
+
+fn method(style: BraceStyle) -> csharp::Tokens {
+    let body: csharp::Tokens = quote! {
+        if (a) $(block(quote!(return 1;)).with_brace_style(style))
+        return 0;
+    };
+
+    quote! {
+        public int Foo(bool a) $(block(body).with_brace_style(style))
+    }
+}
+
+#[test]
+fn test_block_k_and_r() -> genco::fmt::Result {
+    assert_eq!(
+        vec![
+            "public int Foo(bool a) {",
+            "    if (a) {",
+            "        return 1;",
+            "    }",
+            "    return 0;",
+            "}",
+        ],
+        method(BraceStyle::KAndR).to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_block_allman() -> genco::fmt::Result {
+    assert_eq!(
+        vec![
+            "public int Foo(bool a)",
+            "{",
+            "    if (a)",
+            "    {",
+            "        return 1;",
+            "    }",
+            "    return 0;",
+            "}",
+        ],
+        method(BraceStyle::Allman).to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_block_empty() -> genco::fmt::Result {
+    let tokens: js::Tokens = quote!(function foo() $(block(js::Tokens::new())));
+    assert_eq!(vec!["function foo() {}"], tokens.to_file_vec()?);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_nested_combinators() -> genco::fmt::Result {
+    use genco::tokens::{delimited, parens};
+
+    let inner: js::Tokens = quote!(return $(parens(quote!(a + b))););
+    let middle: js::Tokens = quote!(if (a) $(block(inner)));
+    let outer: js::Tokens = quote!(const f = $(delimited("(", middle, ")")););
+
+    let mut tokens = js::Tokens::new();
+    tokens.append(quote!(function foo()));
+    tokens.space();
+    tokens.append(block(outer));
+
+    assert_eq!(
+        vec![
+            "function foo() {",
+            "    const f = (",
+            "        if (a) {",
+            "            return (a + b);",
+            "        }",
+            "    );",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
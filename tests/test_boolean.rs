@@ -0,0 +1,22 @@
+use genco::lang::Lang;
+use genco::prelude::*;
+use genco::tokens::boolean;
+
+fn template<L>() -> Tokens<L>
+where
+    L: Lang,
+{
+    quote!(values = [$(boolean(true)), $(boolean(false))])
+}
+
+#[test]
+fn test_boolean() -> genco::fmt::Result {
+    assert_eq!("values = [True, False]", template::<Python>().to_string()?);
+    assert_eq!("values = [true, false]", template::<Rust>().to_string()?);
+    #[cfg(feature = "std")]
+    assert_eq!(
+        "values = [true, false]",
+        template::<JavaScript>().to_string()?
+    );
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use genco::fmt;
+use genco::prelude::*;
+
+fn tokens() -> rust::Tokens {
+    let map = rust::import("std::collections", "HashMap");
+
+    quote! {
+        fn foo() {
+            let mut m = $map::new();
+            m.insert(1, 2);
+        }
+    }
+}
+
+fn compare(existing: &str, fmt: &fmt::Config) -> Result<Option<usize>, fmt::Error> {
+    let mut w = fmt::ComparingWriter::new(existing);
+    tokens().format_file(&mut w.as_formatter(fmt), &rust::Config::default())?;
+    Ok(w.first_difference())
+}
+
+#[test]
+fn test_first_difference() -> fmt::Result {
+    let fmt = fmt::Config::from_lang::<Rust>();
+    let expected = tokens().to_file_string()?;
+
+    assert_eq!(None, compare(&expected, &fmt)?);
+    assert_eq!(Some(0), compare("", &fmt)?);
+    assert_eq!(Some(0), compare("use std::collections::BTreeMap;\n", &fmt)?);
+    assert_eq!(
+        Some(3),
+        compare(&expected.replace("HashMap::new", "HashMap::new_in"), &fmt)?
+    );
+    assert_eq!(
+        Some(4),
+        compare(&expected.replace("insert(1, 2)", "insert(1, 3)"), &fmt)?
+    );
+
+    // Existing content is longer or shorter than the output.
+    assert_eq!(Some(6), compare(&format!("{}\n", expected), &fmt)?);
+    assert_eq!(Some(5), compare(expected.trim_end(), &fmt)?);
+    Ok(())
+}
+
+#[test]
+fn test_line_endings() -> fmt::Result {
+    let expected = tokens().to_file_string()?;
+    let crlf = expected.replace('\n', "\r\n");
+
+    let fmt = fmt::Config::from_lang::<Rust>();
+    assert_eq!(Some(0), compare(&crlf, &fmt)?);
+
+    let fmt = fmt.with_newline("\r\n");
+    assert_eq!(None, compare(&crlf, &fmt)?);
+    assert_eq!(Some(0), compare(&expected, &fmt)?);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_differs_from_path() -> Result<(), fmt::WriteFileError> {
+    let dir = std::env::temp_dir().join("genco-test-differs-from-path");
+    let path = dir.join("lib.rs");
+
+    assert!(tokens().differs_from_path(&path)?);
+
+    tokens().to_file_path(&path)?;
+    assert!(!tokens().differs_from_path(&path)?);
+
+    std::fs::write(&path, b"\xff\xfe")?;
+    assert!(tokens().differs_from_path(&path)?);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "std")]
+
+use genco::fmt::{self, ParseConfigError, Profile};
+use genco::prelude::*;
+
+fn tokens() -> js::Tokens {
+    quote! {
+        function foo() {
+            return 42;
+        }
+    }
+}
+
+#[test]
+fn test_profile_options() -> Result<(), Box<dyn std::error::Error>> {
+    let config = js::Config::default();
+
+    let fmt = "profile=gofmt, newline=crlf ,".parse::<fmt::Config>()?;
+    assert_eq!(
+        "function foo() {\r\n\treturn 42;\r\n}\r\n",
+        tokens().to_file_string_with(&config, &fmt)?
+    );
+
+    let fmt = "profile=compact,indent=2,trailing_newline=true".parse::<fmt::Config>()?;
+    assert_eq!(
+        "function foo() {\n  return 42;\n}\n",
+        tokens().to_file_string_with(&config, &fmt)?
+    );
+
+    // A profile overrides the options which come before it.
+    let fmt = "indent=tab,profile=google".parse::<fmt::Config>()?;
+    assert_eq!(
+        tokens().to_file_string_with(&config, &fmt::Config::profile(Profile::Google))?,
+        tokens().to_file_string_with(&config, &fmt)?
+    );
+
+    assert_eq!(
+        tokens().to_file_string_with(&config, &fmt::Config::profile(Profile::Rustfmt))?,
+        tokens().to_file_string_with(&config, &"".parse::<fmt::Config>()?)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_errors() {
+    fn parse(s: &str) -> Result<(), ParseConfigError> {
+        s.parse::<fmt::Config>().map(|_| ())
+    }
+
+    assert_eq!(
+        Err(ParseConfigError::MissingValue {
+            option: "indent".into()
+        }),
+        parse("newline=lf,indent")
+    );
+
+    assert_eq!(
+        Err(ParseConfigError::UnknownKey { key: "tabs".into() }),
+        parse("tabs=true")
+    );
+
+    assert_eq!(
+        Err(ParseConfigError::InvalidValue {
+            key: "newline".into(),
+            value: "cr".into()
+        }),
+        parse("newline=cr")
+    );
+
+    assert_eq!(
+        Err(ParseConfigError::InvalidValue {
+            key: "indent".into(),
+            value: "wide".into()
+        }),
+        parse("indent=wide")
+    );
+
+    assert_eq!(
+        Err(ParseConfigError::InvalidValue {
+            key: "profile".into(),
+            value: "Google".into()
+        }),
+        parse("profile=Google")
+    );
+
+    assert_eq!(
+        Err(ParseConfigError::InvalidValue {
+            key: "bom".into(),
+            value: "yes".into()
+        }),
+        parse("bom=yes")
+    );
+
+    assert_eq!(
+        "invalid value `yes` for option `bom`",
+        parse("bom=yes").unwrap_err().to_string()
+    );
+}
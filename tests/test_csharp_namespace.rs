@@ -0,0 +1,101 @@
+use genco::prelude::*;
+
+fn tokens() -> csharp::Tokens {
+    let list = csharp::import("System.Collections.Generic", "List");
+    let local = csharp::import("Example", "Bar");
+
+    quote! {
+        class Foo {
+            $list<$local> bars;
+
+            void Run() {
+                bars.Clear();
+            }
+        }
+    }
+}
+
+#[test]
+fn test_block_namespace() -> genco::fmt::Result {
+    let config = csharp::Config::default().with_namespace("Example");
+
+    assert_eq!(
+        vec![
+            "using System.Collections.Generic;",
+            "",
+            "namespace Example {",
+            "    class Foo {",
+            "        List<Bar> bars;",
+            "",
+            "        void Run() {",
+            "            bars.Clear();",
+            "        }",
+            "    }",
+            "}",
+        ],
+        tokens().to_file_vec_with(
+            &config,
+            &genco::fmt::Config::from_lang::<Csharp>(),
+            genco::fmt::VecWriter::new()
+        )?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_file_scoped_namespace() -> genco::fmt::Result {
+    let config = csharp::Config::default()
+        .with_file_scoped_namespace(true)
+        .with_namespace("Example");
+
+    assert_eq!(
+        vec![
+            "using System.Collections.Generic;",
+            "",
+            "namespace Example;",
+            "",
+            "class Foo {",
+            "    List<Bar> bars;",
+            "",
+            "    void Run() {",
+            "        bars.Clear();",
+            "    }",
+            "}",
+        ],
+        tokens().to_file_vec_with(
+            &config,
+            &genco::fmt::Config::from_lang::<Csharp>(),
+            genco::fmt::VecWriter::new()
+        )?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_file_scoped_without_namespace() -> genco::fmt::Result {
+    let config = csharp::Config::default().with_file_scoped_namespace(true);
+
+    assert_eq!(
+        vec![
+            "using Example;",
+            "using System.Collections.Generic;",
+            "",
+            "class Foo {",
+            "    List<Bar> bars;",
+            "",
+            "    void Run() {",
+            "        bars.Clear();",
+            "    }",
+            "}",
+        ],
+        tokens().to_file_vec_with(
+            &config,
+            &genco::fmt::Config::from_lang::<Csharp>(),
+            genco::fmt::VecWriter::new()
+        )?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "std")]
+
+use genco::fmt;
+use genco::prelude::*;
+
+fn render(current_file: &str, path: &str) -> Result<String, fmt::Error> {
+    let toks: dart::Tokens = quote!($(dart::import(path, "A")));
+
+    let fmt = fmt::Config::from_lang::<Dart>();
+    let config = dart::Config::default()
+        .with_package_name("my_pkg")
+        .with_current_file(current_file);
+
+    let mut w = fmt::VecWriter::new();
+    toks.format_file(&mut w.as_formatter(&fmt), &config)?;
+    Ok(w.into_vec().swap_remove(0))
+}
+
+#[test]
+fn test_import_normalization() -> fmt::Result {
+    let cases = [
+        // Files in lib/src import relatively.
+        ("lib/src/a.dart", "lib/src/b.dart", "import \"b.dart\";"),
+        (
+            "lib/src/a.dart",
+            "package:my_pkg/b.dart",
+            "import \"../b.dart\";",
+        ),
+        (
+            "lib/src/x/a.dart",
+            "lib/src/y/b.dart",
+            "import \"../y/b.dart\";",
+        ),
+        // Public libraries import through the package.
+        (
+            "lib/a.dart",
+            "lib/src/b.dart",
+            "import \"package:my_pkg/src/b.dart\";",
+        ),
+        (
+            "lib/a.dart",
+            "package:my_pkg/b.dart",
+            "import \"package:my_pkg/b.dart\";",
+        ),
+        // Files outside of lib.
+        (
+            "bin/main.dart",
+            "lib/a.dart",
+            "import \"package:my_pkg/a.dart\";",
+        ),
+        (
+            "test/a_test.dart",
+            "test/helpers/h.dart",
+            "import \"helpers/h.dart\";",
+        ),
+        (
+            "test/unit/a_test.dart",
+            "bin/util.dart",
+            "import \"../../bin/util.dart\";",
+        ),
+        // Untouched imports.
+        ("lib/src/a.dart", "dart:math", "import \"dart:math\";"),
+        (
+            "lib/src/a.dart",
+            "package:http/http.dart",
+            "import \"package:http/http.dart\";",
+        ),
+        ("lib/src/a.dart", "../b.dart", "import \"../b.dart\";"),
+    ];
+
+    for (current_file, path, expected) in cases {
+        assert_eq!(
+            expected,
+            render(current_file, path)?,
+            "importing {} from {}",
+            path,
+            current_file
+        );
+    }
+
+    Ok(())
+}
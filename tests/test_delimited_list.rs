@@ -0,0 +1,45 @@
+use genco::fmt;
+use genco::prelude::*;
+use genco::tokens::delimited_list;
+
+fn call() -> go::Tokens {
+    let args: Vec<go::Tokens> = vec![quote!(a), quote!(b + c), quote!(f(d, e))];
+
+    quote! {
+        foo$(delimited_list("(", args, ")"))
+        bar$(delimited_list("(", Vec::<&str>::new(), ")"))
+    }
+}
+
+fn format(trailing_comma: bool) -> fmt::Result<Vec<String>> {
+    let mut w = fmt::VecWriter::new();
+    let fmt = fmt::Config::from_lang::<Go>()
+        .with_indentation(fmt::Indentation::Space(2))
+        .with_trailing_comma(trailing_comma);
+    call().format(
+        &mut w.as_formatter(&fmt),
+        &go::Config::default(),
+        &go::Format::default(),
+    )?;
+    Ok(w.into_vec())
+}
+
+#[test]
+fn test_trailing_comma_on() -> fmt::Result {
+    assert_eq!(
+        vec!["foo(", "  a,", "  b + c,", "  f(d, e),", ")", "bar()"],
+        format(true)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_trailing_comma_off() -> fmt::Result {
+    assert_eq!(
+        vec!["foo(", "  a,", "  b + c,", "  f(d, e)", ")", "bar()"],
+        format(false)?
+    );
+
+    Ok(())
+}
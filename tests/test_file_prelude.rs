@@ -0,0 +1,71 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_license_prelude() -> fmt::Result {
+    let list = java::import("java.util", "List");
+
+    let tokens: java::Tokens = quote! {
+        class Foo {
+            $list<String> names;
+        }
+    };
+
+    let fmt = fmt::Config::from_lang::<Java>()
+        .with_prelude("/*\n * Licensed under the MIT license.\n */");
+    let config = java::Config::default().with_package("se.tedro");
+
+    assert_eq!(
+        "/*\n * Licensed under the MIT license.\n */\n\npackage se.tedro;\n\nimport java.util.List;\n\nclass Foo {\n    List<String> names;\n}\n",
+        tokens.to_file_string_with(&config, &fmt)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_prelude_empty_file() -> fmt::Result {
+    let tokens = python::Tokens::new();
+    let fmt = fmt::Config::from_lang::<Python>().with_prelude("# generated");
+
+    assert_eq!(
+        "# generated\n",
+        tokens.to_file_string_with(&python::Config::default(), &fmt)?
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_bom() -> fmt::Result {
+    let tokens: js::Tokens = quote!(const a = 1;);
+
+    let fmt = fmt::Config::from_lang::<JavaScript>().with_bom(true);
+    let output = tokens.to_file_string_with(&js::Config::default(), &fmt)?;
+
+    assert_eq!(&[0xef, 0xbb, 0xbf], &output.as_bytes()[..3]);
+    assert_eq!("\u{feff}const a = 1;\n", output);
+
+    let fmt = fmt.with_prelude("// generated");
+    let output = tokens.to_file_string_with(&js::Config::default(), &fmt)?;
+    assert_eq!("\u{feff}// generated\n\nconst a = 1;\n", output);
+
+    assert_eq!("const a = 1;\n", tokens.to_file_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_prelude_after_directives() -> fmt::Result {
+    let tokens: python::Tokens = quote!(print("hello"));
+
+    let fmt = fmt::Config::from_lang::<Python>().with_prelude("# Copyright 2026 Example Authors");
+    let config = python::Config::default().with_shebang("#!/usr/bin/env python3");
+
+    assert_eq!(
+        "#!/usr/bin/env python3\n# Copyright 2026 Example Authors\n\nprint(\"hello\")\n",
+        tokens.to_file_string_with(&config, &fmt)?
+    );
+
+    Ok(())
+}
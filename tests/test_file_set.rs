@@ -0,0 +1,176 @@
+#![cfg(feature = "std")]
+
+use std::fs;
+
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_file_set() -> anyhow::Result<()> {
+    let dir = std::env::temp_dir().join("genco-test-file-set");
+    let _ = fs::remove_dir_all(&dir);
+
+    let list = java::import("java.util", "List");
+
+    let mut files = fmt::FileSet::<Java>::new()
+        .with_config(fmt::Config::from_lang::<Java>().with_indentation(fmt::Indentation::Space(2)))
+        .with_skip_unchanged(true)
+        .with_manifest("meta/manifest.txt");
+
+    files.insert_with(
+        "se/tedro/Foo.java",
+        quote! {
+            class Foo {
+                $list<String> names;
+            }
+        },
+        java::Config::default().with_package("se.tedro"),
+    );
+
+    files.insert("Bar.java", quote!(class Bar {}));
+    assert_eq!(2, files.len());
+
+    let summary = files.write_to(&dir)?;
+    assert_eq!(vec!["Bar.java", "se/tedro/Foo.java"], summary.written());
+    assert!(summary.skipped().is_empty());
+    assert!(summary.removed().is_empty());
+
+    assert_eq!(
+        "package se.tedro;\n\nimport java.util.List;\n\nclass Foo {\n  List<String> names;\n}\n",
+        fs::read_to_string(dir.join("se/tedro/Foo.java"))?
+    );
+
+    assert_eq!(
+        "Bar.java\nse/tedro/Foo.java\n",
+        fs::read_to_string(dir.join("meta/manifest.txt"))?
+    );
+
+    let modified = fs::metadata(dir.join("Bar.java"))?.modified()?;
+
+    // Only the changed file is written again.
+    files.insert("se/tedro/Foo.java", quote!(class Foo {}));
+
+    let summary = files.write_to(&dir)?;
+    assert_eq!(vec!["se/tedro/Foo.java"], summary.written());
+    assert_eq!(vec!["Bar.java"], summary.skipped());
+    assert_eq!(modified, fs::metadata(dir.join("Bar.java"))?.modified()?);
+
+    // Files removed from the set are removed from the directory, but files
+    // not listed in the manifest are left alone.
+    fs::write(dir.join("Other.java"), "class Other {}")?;
+
+    let mut files = fmt::FileSet::<Java>::new().with_manifest("meta/manifest.txt");
+    files.insert("Bar.java", quote!(class Bar {}));
+
+    let summary = files.write_to(&dir)?;
+    assert_eq!(vec!["Bar.java"], summary.written());
+    assert_eq!(vec!["se/tedro/Foo.java"], summary.removed());
+    assert!(!dir.join("se/tedro/Foo.java").exists());
+    assert!(dir.join("Other.java").exists());
+    assert_eq!(
+        "Bar.java\n",
+        fs::read_to_string(dir.join("meta/manifest.txt"))?
+    );
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+fn is_invalid_input(error: &fmt::WriteFileError) -> bool {
+    matches!(error, fmt::WriteFileError::Io(e) if e.kind() == std::io::ErrorKind::InvalidInput)
+}
+
+#[test]
+fn test_file_set_rejects_uncontained() {
+    let dir = std::env::temp_dir().join("genco-test-file-set-uncontained");
+    let _ = fs::remove_dir_all(&dir);
+
+    for path in [
+        "",
+        "../Foo.java",
+        "/tmp/Foo.java",
+        "foo/../../Foo.java",
+        "./Foo.java",
+    ] {
+        let mut files = fmt::FileSet::<Java>::new();
+        files.insert("Bar.java", quote!(class Bar {}));
+        files.insert(path, quote!(class Foo {}));
+        let error = files.write_to(&dir).unwrap_err();
+        assert!(is_invalid_input(&error), "{:?}: {}", path, error);
+
+        let mut files = fmt::FileSet::<Java>::new().with_manifest(path);
+        files.insert("Bar.java", quote!(class Bar {}));
+        let error = files.write_to(&dir).unwrap_err();
+        assert!(is_invalid_input(&error), "{:?}: {}", path, error);
+    }
+
+    // Nothing is written if any path is invalid.
+    assert!(!dir.exists());
+}
+
+#[test]
+fn test_file_set_rejects_manifest_collision() {
+    let dir = std::env::temp_dir().join("genco-test-file-set-manifest-collision");
+    let _ = fs::remove_dir_all(&dir);
+
+    let mut files = fmt::FileSet::<Java>::new().with_manifest("foo//Bar.java");
+    files.insert("foo/Bar.java", quote!(class Bar {}));
+
+    let error = files.write_to(&dir).unwrap_err();
+    assert!(is_invalid_input(&error), "{}", error);
+    assert!(!dir.exists());
+}
+
+#[test]
+fn test_file_set_rejects_nested_under_file() {
+    let dir = std::env::temp_dir().join("genco-test-file-set-nested");
+    let _ = fs::remove_dir_all(&dir);
+
+    let mut files = fmt::FileSet::<Java>::new();
+    files.insert("foo", quote!(class Foo {}));
+    files.insert("foo/bar/Bar.java", quote!(class Bar {}));
+
+    let error = files.write_to(&dir).unwrap_err();
+    assert!(is_invalid_input(&error), "{}", error);
+
+    let mut files = fmt::FileSet::<Java>::new().with_manifest("foo");
+    files.insert("foo/Bar.java", quote!(class Bar {}));
+
+    let error = files.write_to(&dir).unwrap_err();
+    assert!(is_invalid_input(&error), "{}", error);
+
+    let mut files = fmt::FileSet::<Java>::new().with_manifest("foo/manifest.txt");
+    files.insert("foo", quote!(class Foo {}));
+
+    let error = files.write_to(&dir).unwrap_err();
+    assert!(is_invalid_input(&error), "{}", error);
+
+    // Nothing is written if any path is nested under another file.
+    assert!(!dir.exists());
+}
+
+#[test]
+fn test_file_set_manifest_outside_dir() -> anyhow::Result<()> {
+    let root = std::env::temp_dir().join("genco-test-file-set-manifest");
+    let _ = fs::remove_dir_all(&root);
+
+    let dir = root.join("out");
+    fs::create_dir_all(&dir)?;
+
+    // A tampered manifest must never cause files outside of the output
+    // directory to be removed.
+    fs::write(root.join("Outside.java"), "class Outside {}")?;
+    fs::write(dir.join("manifest.txt"), "../Outside.java\n")?;
+
+    let mut files = fmt::FileSet::<Java>::new().with_manifest("manifest.txt");
+    files.insert("Bar.java", quote!(class Bar {}));
+
+    let error = files.write_to(&dir).unwrap_err();
+    assert!(
+        matches!(error, fmt::WriteFileError::Io(ref e) if e.kind() == std::io::ErrorKind::InvalidData)
+    );
+    assert!(root.join("Outside.java").exists());
+
+    fs::remove_dir_all(&root)?;
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use genco::prelude::*;
+
+#[test]
+fn test_check_err_custom_return() -> genco::fmt::Result {
+    let wrap = go::import("fmt", "Errorf");
+
+    let toks: go::Tokens = quote! {
+        func load(path string) (*Config, error) {
+            data, readErr := read(path)
+            $(go::check_err(quote!(nil, $wrap($(quoted("reading %s: %w")), path, readErr))).with_var("readErr"))
+
+            return parse(data)
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "import \"fmt\"",
+            "",
+            "func load(path string) (*Config, error) {",
+            "\tdata, readErr := read(path)",
+            "\tif readErr != nil {",
+            "\t\treturn nil, fmt.Errorf(\"reading %s: %w\", path, readErr)",
+            "\t}",
+            "",
+            "\treturn parse(data)",
+            "}",
+        ],
+        toks.to_file_vec()?
+    );
+
+    Ok(())
+}
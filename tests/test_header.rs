@@ -0,0 +1,149 @@
+use genco::fmt;
+use genco::prelude::*;
+
+const HEADER: [&str; 1] = ["Code generated by genco. DO NOT EDIT."];
+
+#[test]
+fn test_header_after_prelude() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+    let tokens: rust::Tokens = quote!(let m = $map::<u32, u32>::new(););
+
+    let fmt = fmt::Config::from_lang::<Rust>()
+        .with_prelude("// SPDX-License-Identifier: MIT")
+        .with_header(HEADER);
+
+    assert_eq!(
+        vec![
+            "// SPDX-License-Identifier: MIT",
+            "",
+            "// Code generated by genco. DO NOT EDIT.",
+            "",
+            "use std::collections::HashMap;",
+            "",
+            "let m = HashMap::<u32, u32>::new();",
+        ],
+        tokens.to_file_vec_with(&rust::Config::default(), &fmt, fmt::VecWriter::new())?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_python_directives() -> fmt::Result {
+    let tokens: python::Tokens = quote!(print("hello"));
+
+    let config = python::Config::default()
+        .with_shebang("#!/usr/bin/env python3")
+        .with_coding(true)
+        .with_module_docstring("Example module.");
+
+    let fmt = fmt::Config::from_lang::<Python>().with_header(HEADER);
+
+    assert_eq!(
+        vec![
+            "#!/usr/bin/env python3",
+            "# -*- coding: utf-8 -*-",
+            "# Code generated by genco. DO NOT EDIT.",
+            "",
+            "\"\"\"Example module.\"\"\"",
+            "",
+            "print(\"hello\")",
+        ],
+        tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_go_before_directives() -> fmt::Result {
+    let tokens: go::Tokens = quote!(func Example() {});
+
+    let config = go::Config::default()
+        .with_package("example")
+        .with_build_constraint("linux");
+
+    let fmt = fmt::Config::from_lang::<Go>()
+        .with_header(HEADER)
+        .with_header_placement(fmt::HeaderPlacement::BeforeDirectives);
+
+    assert_eq!(
+        vec![
+            "// Code generated by genco. DO NOT EDIT.",
+            "",
+            "//go:build linux",
+            "",
+            "package example",
+            "",
+            "func Example() {}",
+        ],
+        tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_minified_header() -> fmt::Result {
+    let tokens: js::Tokens = quote! {
+        function foo() {
+            return 1;
+        }
+    };
+
+    let fmt = fmt::Config::from_lang::<JavaScript>()
+        .with_minify(true)
+        .with_header(HEADER);
+
+    assert_eq!(
+        "// Code generated by genco. DO NOT EDIT.\nfunction foo() {return 1;}\n",
+        tokens.to_file_string_with(&js::Config::default(), &fmt)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_python_prelude_shebang_header() -> fmt::Result {
+    let tokens: python::Tokens = quote!(print("hello"));
+
+    let config = python::Config::default()
+        .with_shebang("#!/usr/bin/env python3")
+        .with_coding(true);
+
+    let fmt = fmt::Config::from_lang::<Python>()
+        .with_prelude("# SPDX-License-Identifier: MIT")
+        .with_header(HEADER);
+
+    assert_eq!(
+        vec![
+            "#!/usr/bin/env python3",
+            "# -*- coding: utf-8 -*-",
+            "# SPDX-License-Identifier: MIT",
+            "",
+            "# Code generated by genco. DO NOT EDIT.",
+            "",
+            "print(\"hello\")",
+        ],
+        tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+    );
+
+    let fmt = fmt.with_header_placement(fmt::HeaderPlacement::BeforeDirectives);
+
+    assert_eq!(
+        vec![
+            "#!/usr/bin/env python3",
+            "# SPDX-License-Identifier: MIT",
+            "",
+            "# Code generated by genco. DO NOT EDIT.",
+            "",
+            "# -*- coding: utf-8 -*-",
+            "",
+            "print(\"hello\")",
+        ],
+        tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use genco::prelude::*;
+
+#[test]
+fn test_repeated_imports() {
+    let list = java::import("java.util", "List");
+    let map = java::import("java.util", "Map");
+
+    let tokens: java::Tokens = quote! {
+        $(&list)<$(&map)<String, $(&list)<String>>> a;
+        $(&map)<String, String> b;
+    };
+
+    assert_eq!(4, tokens.walk_imports().count());
+
+    let expected: Vec<java::Import> = vec![list, map];
+    assert_eq!(expected.iter().collect::<Vec<_>>(), tokens.imports());
+}
+
+#[test]
+fn test_registered_imports() {
+    let os = python::import_module("os");
+    let sys = python::import_module("sys");
+    let path = python::import("os", "path");
+
+    let mut tokens: python::Tokens = quote!($(&path).join("a", "b"));
+    tokens.register(&sys);
+    tokens.register(&path);
+
+    assert!(tokens.has_import(&path));
+    assert!(tokens.has_import(&sys));
+    assert!(!tokens.has_import(&os));
+
+    let expected: Vec<python::Any> = vec![path.into(), sys.into()];
+    assert_eq!(expected.iter().collect::<Vec<_>>(), tokens.imports());
+
+    assert_eq!(
+        vec![
+            "from os import path",
+            "import sys",
+            "",
+            "path.join(\"a\", \"b\")"
+        ],
+        tokens.to_file_vec().unwrap()
+    );
+}
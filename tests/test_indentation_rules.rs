@@ -34,3 +34,74 @@ fn test_indentation_rules() -> genco::fmt::Result {
 
     Ok(())
 }
+
+#[test]
+fn test_custom_indentation() -> genco::fmt::Result {
+    use genco::fmt;
+    use genco::tokens::Item;
+
+    let mut tokens = rust::Tokens::new();
+    tokens.append("a");
+    tokens.extend([Item::Indentation(2)]);
+    tokens.append("b");
+    tokens.indent();
+    tokens.append("c");
+    tokens.extend([Item::Indentation(-2)]);
+    tokens.append("d");
+    // Unindenting below zero writes no indentation, and has to be balanced
+    // before indentation is written again.
+    tokens.extend([Item::Indentation(-3)]);
+    tokens.append("e");
+    tokens.indent();
+    tokens.append("f");
+    tokens.extend([Item::Indentation(2)]);
+    tokens.append("g");
+
+    let fmt = fmt::Config::from_lang::<Rust>().with_indentation(fmt::Indentation::Custom("   "));
+
+    let mut w = fmt::VecWriter::new();
+    tokens.format_file(&mut w.as_formatter(&fmt), &rust::Config::default())?;
+
+    assert_eq!(
+        vec!["a", "      b", "         c", "   d", "e", "f", "   g",],
+        w.into_vec()
+    );
+
+    let fmt = fmt::Config::from_lang::<Rust>().with_indentation(fmt::Indentation::Custom("\t "));
+    assert_eq!(
+        "a\n\t \t b\n\t \t \t c\n\t d\ne\nf\n\t g\n",
+        tokens.to_file_string_with(&rust::Config::default(), &fmt)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_default_indentation() -> genco::fmt::Result {
+    use genco::fmt::Indentation;
+    use genco::lang::Lang;
+
+    assert_eq!(Indentation::Tab, Go::default_indentation());
+    assert_eq!(Indentation::Space(4), Rust::default_indentation());
+
+    let tokens: go::Tokens = quote! {
+        func main() {
+            run()
+        }
+    };
+
+    assert_eq!(vec!["func main() {", "\trun()", "}"], tokens.to_file_vec()?);
+
+    let tokens: rust::Tokens = quote! {
+        fn main() {
+            run();
+        }
+    };
+
+    assert_eq!(
+        vec!["fn main() {", "    run();", "}"],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
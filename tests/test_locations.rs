@@ -0,0 +1,68 @@
+#![cfg(feature = "locations")]
+
+use genco::prelude::*;
+use genco::tokens::{Item, ItemStr};
+
+#[test]
+fn test_locations() {
+    let line = line!() + 1;
+    let tokens: Tokens<()> = quote!(foo bar);
+
+    let locations = tokens.locations();
+    assert_eq!(2, locations.len());
+
+    let (index, location) = locations[0];
+    assert_eq!(
+        Some(&Item::Literal(ItemStr::Static("foo"))),
+        tokens.iter().nth(index)
+    );
+    assert_eq!(file!(), location.file());
+    assert_eq!(line as usize, location.line());
+    assert_eq!(36, location.column());
+
+    let (index, location) = locations[1];
+    assert_eq!(
+        Some(&Item::Literal(ItemStr::Static("bar"))),
+        tokens.iter().nth(index)
+    );
+    assert_eq!(40, location.column());
+}
+
+#[test]
+fn test_locations_nested() {
+    let inner: Tokens<()> = quote!(bar);
+    let tokens: Tokens<()> = quote!(foo $inner);
+
+    let literals = tokens
+        .locations()
+        .iter()
+        .map(|(index, _)| tokens.iter().nth(*index).cloned())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            Some(Item::Literal(ItemStr::Static("foo"))),
+            Some(Item::Literal(ItemStr::Static("bar"))),
+        ],
+        literals
+    );
+}
+
+#[test]
+fn test_locations_dedup_registered() {
+    let debug = rust::import("std::fmt", "Debug");
+
+    let mut tokens: rust::Tokens = quote!($(register(&debug)) foo $(register(&debug)) bar);
+    tokens.dedup_registered();
+
+    let literals = tokens
+        .locations()
+        .iter()
+        .filter_map(|(index, _)| match tokens.iter().nth(*index) {
+            Some(Item::Literal(literal)) => Some(literal.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec!["foo", "bar"], literals);
+}
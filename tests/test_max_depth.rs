@@ -0,0 +1,78 @@
+use genco::fmt;
+use genco::prelude::*;
+use genco::tokens::ValidationError;
+
+/// Build a token stream consisting of `depth` nested modules.
+fn nested(depth: usize) -> rust::Tokens {
+    let mut tokens: rust::Tokens = quote!(
+        fn leaf() {}
+    );
+
+    for n in (0..depth).rev() {
+        tokens = quote!($(rust::module(format!("m{}", n), tokens)));
+    }
+
+    tokens
+}
+
+#[test]
+fn test_max_depth_exceeded() {
+    let config = rust::Config::default();
+    let fmt = fmt::Config::from_lang::<Rust>().with_max_depth(8);
+
+    assert!(nested(6).to_file_string_with(&config, &fmt).is_ok());
+    assert!(nested(7).to_file_string_with(&config, &fmt).is_ok());
+    assert_eq!(
+        Err(std::fmt::Error),
+        nested(8).to_file_string_with(&config, &fmt)
+    );
+    assert_eq!(
+        Err(std::fmt::Error),
+        nested(256).to_file_string_with(&config, &fmt)
+    );
+}
+
+#[test]
+fn test_max_depth_error() {
+    let tokens: rust::Tokens = quote! {
+        fn leaf() {}
+
+        $(nested(8))
+    };
+
+    let fmt = fmt::Config::from_lang::<Rust>().with_max_depth(8);
+
+    let mut w = fmt::FmtWriter::new(String::new());
+    let mut out = w.as_formatter(&fmt);
+
+    assert!(tokens
+        .format_file(&mut out, &rust::Config::default())
+        .is_err());
+
+    // Reported at the index of the outermost module.
+    assert_eq!(
+        Some(&ValidationError::MaxDepthExceeded {
+            index: 6,
+            max_depth: 8,
+        }),
+        out.error()
+    );
+}
+
+#[test]
+fn test_max_depth_quoted() {
+    let tokens: rust::Tokens = quote!($(quoted(quote!(a $(quoted("b"))))));
+    let config = rust::Config::default();
+
+    let fmt = fmt::Config::from_lang::<Rust>().with_max_depth(1);
+    assert_eq!(
+        Err(std::fmt::Error),
+        tokens.to_file_string_with(&config, &fmt)
+    );
+
+    let fmt = fmt::Config::from_lang::<Rust>().with_max_depth(2);
+    assert_eq!(
+        "\"a \\\"b\\\"\"\n",
+        tokens.to_file_string_with(&config, &fmt).unwrap()
+    );
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "std")]
+
+use genco::fmt;
+use genco::prelude::*;
+
+fn minify() -> fmt::Config {
+    fmt::Config::from_lang::<JavaScript>().with_minify(true)
+}
+
+#[test]
+fn test_minify_function() -> fmt::Result {
+    let react = js::import("react", "useState");
+
+    let tokens: js::Tokens = quote! {
+        export function Counter() {
+            const [count, setCount] = $react(0);
+
+            if (count > 10) {
+                return null;
+            }
+
+            return $[str](Count: $[const](1) and "more");
+        }
+    };
+
+    assert_eq!(
+        concat!(
+            "import {useState} from \"react\";",
+            "export function Counter() {",
+            "const [count, setCount] = useState(0);",
+            "if (count > 10) {return null;}",
+            "return \"Count: 1 and \\\"more\\\"\";",
+            "}\n",
+        ),
+        tokens.to_file_string_with(&js::Config::default(), &minify())?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_minify_separators() -> fmt::Result {
+    let tokens: js::Tokens = quote! {
+        function f(b, c, d) {
+            let a = b +
+            +c;
+            a = a -
+            -d;
+            a = a /
+            /i/.source.length;
+            ++a;
+            --a;
+            return a;
+        }
+    };
+
+    assert_eq!(
+        concat!(
+            "function f(b, c, d) {",
+            "let a = b + +c;",
+            "a = a - -d;",
+            "a = a / /i/.source.length;",
+            "++a;",
+            "--a;",
+            "return a;",
+            "}",
+        ),
+        tokens.to_file_string_with(
+            &js::Config::default(),
+            &minify().with_trailing_newline(false)
+        )?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_minify_quoted_untouched() -> fmt::Result {
+    let tokens: js::Tokens = quote! {
+        const text = $(quoted("first\n    second"));
+    };
+
+    assert_eq!(
+        "const text = \"first\\n    second\";",
+        tokens.to_file_string_with(
+            &js::Config::default(),
+            &minify().with_trailing_newline(false)
+        )?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_minify_prelude() -> fmt::Result {
+    let tokens: js::Tokens = quote! {
+        function foo() {
+            return 1;
+        }
+    };
+
+    let fmt = minify()
+        .with_shebang("#!/usr/bin/env node")
+        .with_prelude("// generated");
+
+    assert_eq!(
+        "#!/usr/bin/env node\n// generated\nfunction foo() {return 1;}\n",
+        tokens.to_file_string_with(&js::Config::default(), &fmt)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_minify_json() -> fmt::Result {
+    let tokens: js::Tokens = quote! {
+        {
+            "name": "genco",
+            "keywords": [
+                "code generation",
+                "quote"
+            ]
+        }
+    };
+
+    assert_eq!(
+        "{\"name\": \"genco\",\"keywords\": [\"code generation\",\"quote\"]}",
+        tokens.to_file_string_with(
+            &js::Config::default(),
+            &minify().with_trailing_newline(false)
+        )?
+    );
+
+    Ok(())
+}
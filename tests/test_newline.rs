@@ -0,0 +1,104 @@
+use genco::fmt;
+use genco::prelude::*;
+use genco::tokens::ValidationError;
+
+#[test]
+fn test_crlf_preamble_and_nested_blocks() -> fmt::Result {
+    let list = csharp::import("System.Collections.Generic", "List");
+
+    let tokens: csharp::Tokens = quote! {
+        class Foo {
+            void Bar() {
+                var names = new $list<string>();
+
+                if (names.Count == 0) {
+                    Console.Write($(quoted("a\nb")));
+                }
+            }
+        }
+    };
+
+    let config = csharp::Config::default().with_namespace("Example");
+    let fmt = fmt::Config::from_lang::<Csharp>()
+        .with_newline("\r\n")
+        .with_prelude("// Generated\n// Do not edit");
+
+    let expected = [
+        "// Generated",
+        "// Do not edit",
+        "",
+        "using System.Collections.Generic;",
+        "",
+        "namespace Example {",
+        "    class Foo {",
+        "        void Bar() {",
+        "            var names = new List<string>();",
+        "",
+        "            if (names.Count == 0) {",
+        "                Console.Write(\"a\\nb\");",
+        "            }",
+        "        }",
+        "    }",
+        "}",
+        "",
+    ];
+
+    let output = tokens.to_file_string_with(&config, &fmt)?;
+    assert_eq!(expected.join("\r\n"), output);
+
+    let lf = tokens.to_file_string_with(&config, &fmt.with_newline("\n"))?;
+    assert_eq!(expected.join("\n"), lf);
+    Ok(())
+}
+
+#[test]
+fn test_crlf_preserves_literal_newlines() -> fmt::Result {
+    let mut tokens = rust::Tokens::new();
+    tokens.append("a\nb");
+    tokens.push();
+    tokens.append("c");
+
+    let fmt = fmt::Config::from_lang::<Rust>().with_newline("\r\n");
+
+    assert_eq!(
+        "a\nb\r\nc\r\n",
+        tokens.to_file_string_with(&rust::Config::default(), &fmt)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_newline() {
+    let tokens: rust::Tokens = quote! {
+        fn foo() {
+            bar();
+        }
+    };
+
+    let fmt = fmt::Config::from_lang::<Rust>().with_newline("\r");
+
+    let mut w = fmt::VecWriter::new();
+    let mut formatter = w.as_formatter(&fmt);
+    assert!(tokens
+        .format(
+            &mut formatter,
+            &rust::Config::default(),
+            &rust::Format::default()
+        )
+        .is_err());
+    assert_eq!(
+        Some(&ValidationError::InvalidNewline { newline: "\r" }),
+        formatter.error()
+    );
+
+    let mut w = fmt::VecWriter::new();
+    let mut formatter = w.as_formatter(&fmt);
+    assert!(tokens
+        .format_file(&mut formatter, &rust::Config::default())
+        .is_err());
+    assert_eq!(
+        Some(&ValidationError::InvalidNewline { newline: "\r" }),
+        formatter.error()
+    );
+}
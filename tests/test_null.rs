@@ -0,0 +1,20 @@
+use genco::lang::Lang;
+use genco::prelude::*;
+use genco::tokens::null;
+
+fn template<L>() -> Tokens<L>
+where
+    L: Lang,
+{
+    quote!(value = $(null()))
+}
+
+#[test]
+fn test_null() -> genco::fmt::Result {
+    assert_eq!("value = None", template::<Python>().to_string()?);
+    #[cfg(feature = "std")]
+    assert_eq!("value = null", template::<JavaScript>().to_string()?);
+    assert_eq!("value = nil", template::<Swift>().to_string()?);
+    assert_eq!("value = NULL", template::<C>().to_string()?);
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_prefix_writer_file() -> fmt::Result {
+    let list = java::import("java.util", "List");
+    let array_list = java::import("java.util", "ArrayList");
+
+    let tokens: java::Tokens = quote! {
+        class Example {
+            $list<String> items = new $array_list<>();
+
+            void add(String item) {
+                items.add(item);
+            }
+        }
+    };
+
+    let fmt = fmt::Config::from_lang::<Java>();
+    let config = java::Config::default().with_package("com.example");
+
+    let mut w = fmt::PrefixWriter::new(String::new(), "    ");
+    tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+
+    assert_eq!(
+        vec![
+            "    package com.example;",
+            "",
+            "    import java.util.ArrayList;",
+            "    import java.util.List;",
+            "",
+            "    class Example {",
+            "        List<String> items = new ArrayList<>();",
+            "",
+            "        void add(String item) {",
+            "            items.add(item);",
+            "        }",
+            "    }",
+        ],
+        w.into_inner().lines().collect::<Vec<_>>()
+    );
+
+    let mut w = fmt::PrefixWriter::new(String::new(), "/// ").with_empty_lines(true);
+    tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+
+    assert_eq!(
+        vec![
+            "/// package com.example;",
+            "///",
+            "/// import java.util.ArrayList;",
+            "/// import java.util.List;",
+            "///",
+            "/// class Example {",
+            "///     List<String> items = new ArrayList<>();",
+            "///",
+            "///     void add(String item) {",
+            "///         items.add(item);",
+            "///     }",
+            "/// }",
+        ],
+        w.into_inner().lines().collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use genco::prelude::*;
+
+#[test]
+fn test_stacked_decorators() -> genco::fmt::Result {
+    let flask = python::import("flask", "Flask");
+    let wraps = python::import("functools", "wraps").qualified();
+
+    let login_required = python::decorator(quote!(auth.login_required));
+    let route = python::decorator(quote!($flask.route))
+        .with_argument(quoted("/x"))
+        .with_keyword("methods", quote!([$(quoted("GET")), $(quoted("POST"))]));
+
+    let tokens: python::Tokens = quote! {
+        class App:
+            $(python::decorator(quote!($wraps(handler))))
+            $route
+            $login_required
+            def index(self):
+                pass
+    };
+
+    assert_eq!(
+        vec![
+            "from flask import Flask",
+            "import functools",
+            "",
+            "class App:",
+            "    @functools.wraps(handler)",
+            "    @Flask.route(\"/x\", methods=[\"GET\", \"POST\"])",
+            "    @auth.login_required",
+            "    def index(self):",
+            "        pass",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
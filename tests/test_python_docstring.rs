@@ -0,0 +1,49 @@
+use genco::fmt;
+use genco::prelude::*;
+
+fn docstring(docstring: &str) -> fmt::Result<Vec<String>> {
+    let tokens: python::Tokens = quote!(pass);
+    let config = python::Config::default().with_module_docstring(docstring.to_owned());
+    let fmt = fmt::Config::from_lang::<Python>();
+    tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())
+}
+
+#[test]
+fn test_docstring_trailing_quote() -> fmt::Result {
+    assert_eq!(
+        vec!["\"\"\"Say \\\"hi\\\"\"\"\"", "", "pass"],
+        docstring("Say \"hi\"")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_docstring_quote_runs() -> fmt::Result {
+    assert_eq!(
+        vec!["\"\"\"a \\\"\\\"\\\"\\\" b\"\"\"", "", "pass"],
+        docstring("a \"\"\"\" b")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_docstring_backslash() -> fmt::Result {
+    assert_eq!(
+        vec!["\"\"\"C:\\\\path\"\"\"", "", "pass"],
+        docstring("C:\\path")?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_docstring_leading_newline() -> fmt::Result {
+    assert_eq!(
+        vec!["\"\"\"", "Summary.", "", "Details.", "\"\"\"", "", "pass"],
+        docstring("\nSummary.\n\nDetails.\n")?
+    );
+
+    Ok(())
+}
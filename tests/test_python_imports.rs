@@ -0,0 +1,102 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_module_binding_conflict() -> fmt::Result {
+    let module = python::import_module("b");
+    let name = python::import("a", "b");
+
+    let toks: python::Tokens = quote! {
+        $(&module)
+        $(&name)
+    };
+
+    assert_eq!(
+        vec!["import a", "import b", "", "b", "a.b"],
+        toks.to_file_vec()?
+    );
+
+    let config = python::Config::default().with_conflict_mode(python::ConflictMode::Aliased);
+    let fmt = fmt::Config::from_lang::<Python>();
+
+    assert_eq!(
+        vec!["from a import b as a_b", "import b", "", "b", "a_b"],
+        toks.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_module_alias_conflict() -> fmt::Result {
+    let module = python::import_module("numpy").with_alias("np");
+    let name = python::import("a", "np");
+
+    let toks: python::Tokens = quote! {
+        $module
+        $name
+    };
+
+    assert_eq!(
+        vec!["import a", "import numpy as np", "", "np", "a.np"],
+        toks.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_qualified_module_conflict() -> fmt::Result {
+    let c = python::import("a", "c");
+    let b = python::import("b", "Client");
+    let conflict = python::import("c", "Client");
+
+    let toks: python::Tokens = quote! {
+        $c
+        $b()
+        $conflict()
+    };
+
+    assert_eq!(
+        vec![
+            "from a import c",
+            "from b import Client",
+            "from c import Client as c_Client",
+            "",
+            "c",
+            "Client()",
+            "c_Client()",
+        ],
+        toks.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_qualified_shares_package() -> fmt::Result {
+    let path = python::import_module("os.path");
+    let a = python::import("a", "sep");
+    let b = python::import("os", "sep");
+
+    let toks: python::Tokens = quote! {
+        $path
+        $a
+        $b
+    };
+
+    assert_eq!(
+        vec![
+            "from a import sep",
+            "import os",
+            "import os.path",
+            "",
+            "os.path",
+            "sep",
+            "os.sep"
+        ],
+        toks.to_file_vec()?
+    );
+
+    Ok(())
+}
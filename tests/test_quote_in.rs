@@ -17,3 +17,27 @@ fn test_quote_into_unit() -> genco::fmt::Result {
     assert_eq!("uint32", tokens.to_string()?);
     Ok(())
 }
+
+/// quote_in! can target a raw vector of items.
+#[test]
+fn test_quote_in_vec() {
+    use genco::tokens::{Item, ItemStr};
+
+    let mut items = vec![Item::<()>::Literal(ItemStr::Static("start"))];
+
+    quote_in! { items =>
+        foo
+            $(ref t => quote_in!(*t => bar))
+    };
+
+    assert_eq!(
+        vec![
+            Item::Literal(ItemStr::Static("start")),
+            Item::Literal(ItemStr::Static("foo")),
+            Item::Indentation(1),
+            Item::Literal(ItemStr::Static("bar")),
+            Item::Indentation(-1),
+        ],
+        items
+    );
+}
@@ -28,3 +28,38 @@ fn test_register() -> genco::fmt::Result {
 
     Ok(())
 }
+
+#[test]
+fn test_register_collections() -> genco::fmt::Result {
+    let traits = vec![
+        rust::import("std::io", "Read").with_alias("_"),
+        rust::import("std::io", "Write").with_alias("_"),
+    ];
+
+    let debug = rust::import("std::fmt", "Debug").with_alias("_");
+
+    let tokens: Tokens<Rust> = quote! {
+        $(register(&traits))
+        $(register([debug]))
+        $(register(None::<rust::Import>))
+
+        fn test() {}
+    };
+
+    let mut manual: Tokens<Rust> = quote!(
+        fn test() {}
+    );
+    manual.register_all(traits.iter().chain(traits.iter()));
+    manual.register(Some(rust::import("std::fmt", "Debug").with_alias("_")));
+
+    let expected = vec![
+        "use std::fmt::Debug as _;",
+        "use std::io::{Read as _, Write as _};",
+        "",
+        "fn test() {}",
+    ];
+
+    assert_eq!(expected, tokens.to_file_vec()?);
+    assert_eq!(expected, manual.to_file_vec()?);
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use genco::prelude::*;
+
+#[test]
+fn test_lifetime_and_bound() -> genco::fmt::Result {
+    let debug = rust::import("std::fmt", "Debug");
+
+    let generics = rust::generics().with_lifetime("a").with_bound("T", debug);
+
+    let tokens: rust::Tokens = quote! {
+        struct Wrapper$(&generics) {
+            value: &'a T,
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "use std::fmt::Debug;",
+            "",
+            "struct Wrapper<'a, T: Debug> {",
+            "    value: &'a T,",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_where_clause() -> genco::fmt::Result {
+    let display = rust::import("std::fmt", "Display");
+    let hash = rust::import("std::hash", "Hash");
+
+    let generics = rust::generics()
+        .with_lifetime("'a")
+        .with_type("U")
+        .with_bound("T", display)
+        .with_bound("T", hash)
+        .with_bound("T", "'a")
+        .with_const("N", "usize");
+
+    let tokens: rust::Tokens = quote! {
+        fn show$(&generics)(values: [&'a T; N], other: U) $(generics.where_clause()) {}
+    };
+
+    assert_eq!(
+        vec![
+            "use std::fmt::Display;",
+            "use std::hash::Hash;",
+            "",
+            "fn show<'a, U, T, const N: usize>(values: [&'a T; N], other: U) where T: Display + Hash + 'a {}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_empty() -> genco::fmt::Result {
+    let generics = rust::generics();
+    assert!(generics.is_empty());
+
+    let tokens: rust::Tokens = quote! {
+        fn empty$(&generics)() $(generics.where_clause()) {}
+    };
+
+    assert_eq!(vec!["fn empty() {}"], tokens.to_file_vec()?);
+    Ok(())
+}
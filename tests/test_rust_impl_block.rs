@@ -0,0 +1,65 @@
+use genco::prelude::*;
+
+#[test]
+fn test_trait_impl() -> genco::fmt::Result {
+    let debug = rust::import("std::fmt", "Debug");
+    let formatter = rust::import("std::fmt", "Formatter");
+    let result = rust::import("std::fmt", "Result");
+    let wrapper = rust::import("crate::types", "Wrapper");
+
+    let tokens: rust::Tokens = quote! {
+        $(rust::impl_block(Some(quote!($(&debug))), quote!($wrapper<T>), quote! {
+            fn fmt(&self, f: &mut $formatter<'_>) -> $result {
+                self.0.fmt(f)
+            }
+        }).with_generics(quote!(T: $(&debug))))
+    };
+
+    assert_eq!(
+        vec![
+            "use crate::types::Wrapper;",
+            "use std::fmt::{Debug, Formatter, Result};",
+            "",
+            "impl<T: Debug> Debug for Wrapper<T> {",
+            "    fn fmt(&self, f: &mut Formatter<'_>) -> Result {",
+            "        self.0.fmt(f)",
+            "    }",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_inherent_impl() -> genco::fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+
+    let tokens: rust::Tokens = quote! {
+        $(rust::impl_block(None, "Registry", quote! {
+            const LIMIT: usize = 16;
+
+            fn new() -> Self {
+                Self($map::new())
+            }
+        }))
+    };
+
+    assert_eq!(
+        vec![
+            "use std::collections::HashMap;",
+            "",
+            "impl Registry {",
+            "    const LIMIT: usize = 16;",
+            "",
+            "    fn new() -> Self {",
+            "        Self(HashMap::new())",
+            "    }",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_import_hook_reverse() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+    let set = rust::import("std::collections", "HashSet");
+    let debug = rust::import("std::fmt", "Debug");
+    let serialize = rust::import("serde", "Serialize");
+
+    let tokens: rust::Tokens = quote! {
+        #[derive($debug, $serialize)]
+        struct Foo($map<u32, u32>, $set<u32>);
+    };
+
+    assert_eq!(
+        vec![
+            "use serde::Serialize;",
+            "use std::collections::{HashMap, HashSet};",
+            "use std::fmt::Debug;",
+            "",
+            "#[derive(Debug, Serialize)]",
+            "struct Foo(HashMap<u32, u32>, HashSet<u32>);",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    let config = rust::Config::default().with_import_hook(|imports| imports.reverse());
+    let fmt = fmt::Config::from_lang::<Rust>();
+
+    let mut w = fmt::VecWriter::new();
+    tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+
+    assert_eq!(
+        vec![
+            "use std::fmt::Debug;",
+            "use std::collections::{HashMap, HashSet};",
+            "use serde::Serialize;",
+            "",
+            "#[derive(Debug, Serialize)]",
+            "struct Foo(HashMap<u32, u32>, HashSet<u32>);",
+        ],
+        w.into_vec()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_import_hook_filter() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+    let prelude = rust::import("std::option", "Option");
+
+    let tokens: rust::Tokens = quote!(let a: $prelude<$map<u32, u32>> = None;);
+
+    let config = rust::Config::default()
+        .with_import_hook(|imports| imports.retain(|import| import.module() != "std::option"));
+
+    assert_eq!(
+        "use std::collections::HashMap;\n\nlet a: Option<HashMap<u32, u32>> = None;\n",
+        tokens.to_file_string_with(&config, &fmt::Config::from_lang::<Rust>())?
+    );
+
+    Ok(())
+}
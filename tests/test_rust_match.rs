@@ -0,0 +1,53 @@
+use genco::prelude::*;
+
+#[test]
+fn test_two_arm_match() -> genco::fmt::Result {
+    let io_error = rust::import("std::io", "Error");
+    let error_kind = rust::import("std::io", "ErrorKind");
+
+    let arms = [
+        (quote!(Some(value)), quote!(value)),
+        (
+            quote!(None),
+            quote! {
+                {
+                    return Err($(&io_error)::from($error_kind::NotFound));
+                }
+            },
+        ),
+    ];
+
+    let tokens: rust::Tokens = quote! {
+        fn get(value: Option<u32>) -> Result<u32, $io_error> {
+            let value = $(rust::match_expr("value", arms));
+            Ok(value)
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "use std::io::{Error, ErrorKind};",
+            "",
+            "fn get(value: Option<u32>) -> Result<u32, Error> {",
+            "    let value = match value {",
+            "        Some(value) => value,",
+            "        None => {",
+            "            return Err(Error::from(ErrorKind::NotFound));",
+            "        },",
+            "    };",
+            "    Ok(value)",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_match() -> genco::fmt::Result {
+    let arms: [(rust::Tokens, rust::Tokens); 0] = [];
+    let tokens: rust::Tokens = quote!(let x = $(rust::match_expr("never", arms)););
+    assert_eq!(vec!["let x = match never {};"], tokens.to_file_vec()?);
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use genco::prelude::*;
+
+#[test]
+fn test_import_only_inside_module() -> genco::fmt::Result {
+    let debug = rust::import("std::fmt", "Debug");
+
+    let toks: rust::Tokens = quote! {
+        $(rust::module("inner", quote!(impl $debug for Foo {})))
+    };
+
+    assert_eq!(
+        vec![
+            "mod inner {",
+            "    use std::fmt::Debug;",
+            "",
+            "    impl Debug for Foo {}",
+            "}"
+        ],
+        toks.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_import_inside_and_outside_module() -> genco::fmt::Result {
+    let debug = rust::import("std::fmt", "Debug");
+
+    let toks: rust::Tokens = quote! {
+        impl $(&debug) for Foo {}
+
+        $(rust::module("inner", quote!(impl $(&debug) for Bar {})))
+    };
+
+    assert_eq!(
+        vec![
+            "use std::fmt::Debug;",
+            "",
+            "impl Debug for Foo {}",
+            "",
+            "mod inner {",
+            "    use std::fmt::Debug;",
+            "",
+            "    impl Debug for Bar {}",
+            "}",
+        ],
+        toks.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_nested_modules() -> genco::fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+    let set = rust::import("std::collections", "HashSet");
+
+    let inner = rust::module("b", quote!(type M = $map<u32, u32>;));
+
+    let toks: rust::Tokens = quote! {
+        $(rust::module("a", quote! {
+            type S = $set<u32>;
+
+            $inner
+        }))
+
+        $(rust::module("empty", rust::Tokens::new()))
+    };
+
+    assert_eq!(
+        vec![
+            "mod a {",
+            "    use std::collections::HashSet;",
+            "",
+            "    type S = HashSet<u32>;",
+            "",
+            "    mod b {",
+            "        use std::collections::HashMap;",
+            "",
+            "        type M = HashMap<u32, u32>;",
+            "    }",
+            "}",
+            "",
+            "mod empty {}",
+        ],
+        toks.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_imports_inside_module() {
+    use genco::tokens::ValidationError;
+
+    let map = rust::import("std::collections", "HashMap");
+    let debug = rust::import("std::fmt", "Debug");
+    let set = rust::import("std::collections", "HashSet");
+
+    let module = rust::module("inner", quote!(impl $(&debug) for Foo {}));
+
+    let toks: rust::Tokens = quote! {
+        let a = $(&map)::<u32, u32>::new();
+        $(&module)
+    };
+
+    assert!(toks.has_import(&map));
+    assert!(toks.has_import(&debug));
+    assert!(!toks.has_import(&set));
+
+    let expected: Vec<rust::Any> = vec![map.into(), debug.into(), module.into()];
+    assert_eq!(expected.iter().collect::<Vec<_>>(), toks.imports());
+
+    let reserved = rust::import("foo", "super");
+
+    let toks: rust::Tokens = quote! {
+        $(rust::module("inner", quote!(let a = $reserved;)))
+    };
+
+    assert_eq!(
+        toks.validate(),
+        Err(ValidationError::ReservedName {
+            index: 0,
+            name: String::from("super"),
+        })
+    );
+}
@@ -0,0 +1,45 @@
+use genco::prelude::*;
+
+#[test]
+fn test_raw_ident_import() -> genco::fmt::Result {
+    let a = rust::import("futures", "async");
+    let b = rust::import("foo::type", "Bar").qualified();
+    let c = rust::import("foo", "Baz").with_alias("match");
+
+    let tokens: rust::Tokens = quote! {
+        $a();
+        $b::new();
+        $c::default();
+    };
+
+    assert_eq!(
+        vec![
+            "use foo::Baz as r#match;",
+            "use foo::r#type;",
+            "use futures::r#async;",
+            "",
+            "r#async();",
+            "r#type::Bar::new();",
+            "r#match::default();",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    assert_eq!(tokens.validate(), Ok(()));
+    Ok(())
+}
+
+#[test]
+fn test_raw_ident_grouped_import() -> genco::fmt::Result {
+    let a = rust::import("foo", "async");
+    let b = rust::import("foo", "bar");
+
+    let tokens: rust::Tokens = quote!($a($b));
+
+    assert_eq!(
+        vec!["use foo::{r#async, bar};", "", "r#async(bar)"],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use genco::prelude::*;
+
+#[test]
+fn test_shared_imports() -> genco::fmt::Result {
+    let list = java::import("java.util", "List");
+    let map = java::import("java.util", "Map");
+
+    let inner: Arc<java::Tokens> = Arc::new(quote!($map<String, String> m;));
+    let outer: Arc<java::Tokens> = Arc::new(quote!($list<String> l; $(&inner)));
+
+    let tokens: java::Tokens = quote! {
+        class Foo {
+            $(&outer)
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "import java.util.List;",
+            "import java.util.Map;",
+            "",
+            "class Foo {",
+            "    List<String> l; Map<String, String> m;",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_shared_js_imports() -> genco::fmt::Result {
+    let react = js::import("react", "useState");
+
+    let shared: Arc<js::Tokens> = Arc::new(quote!(const [a, setA] = $react(0);));
+
+    let tokens: js::Tokens = quote! {
+        $(&shared)
+        $(&shared)
+    };
+
+    assert_eq!(
+        vec![
+            "import {useState} from \"react\";",
+            "",
+            "const [a, setA] = useState(0);",
+            "const [a, setA] = useState(0);",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_shared_reuse() -> genco::fmt::Result {
+    let shared: Arc<rust::Tokens> = Arc::new(quote! {
+        fn helper() {}
+    });
+
+    let files = (0..10)
+        .map(|n| {
+            let tokens: rust::Tokens = quote! {
+                $(&shared)
+
+                fn main$n() {}
+            };
+
+            tokens
+        })
+        .collect::<Vec<_>>();
+
+    // Every file refers to the same underlying stream.
+    assert_eq!(Arc::strong_count(&shared), 11);
+
+    assert_eq!(
+        vec!["fn helper() {}", "", "fn main3() {}"],
+        files[3].to_file_vec()?
+    );
+
+    drop(files);
+    assert_eq!(Arc::strong_count(&shared), 1);
+    Ok(())
+}
+
+#[test]
+fn test_shared_validate() {
+    use genco::tokens::ValidationError;
+
+    let reserved = rust::import("foo", "super");
+    let shared: Arc<rust::Tokens> = Arc::new(quote!(let a = $reserved;));
+
+    let tokens: rust::Tokens = quote!(fn foo() { $(&shared) });
+
+    assert_eq!(
+        tokens.validate(),
+        Err(ValidationError::ReservedName {
+            index: 6,
+            name: String::from("super"),
+        })
+    );
+}
+
+#[test]
+fn test_shared_dedup_registered() -> genco::fmt::Result {
+    let debug = rust::import("std::fmt", "Debug");
+
+    let mut shared = rust::Tokens::new();
+    shared.register(&debug);
+    let shared = Arc::new(shared);
+
+    let mut tokens = rust::Tokens::new();
+    tokens.append_shared(shared.clone());
+    tokens.register(&debug);
+    tokens.register(&debug);
+
+    tokens.dedup_registered();
+
+    // The registration in the shared stream is kept.
+    assert_eq!(1, tokens.walk_imports().count());
+    assert_eq!(1, shared.walk_imports().count());
+    assert_eq!(vec!["use std::fmt::Debug;"], tokens.to_file_vec()?);
+    Ok(())
+}
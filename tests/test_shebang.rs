@@ -0,0 +1,74 @@
+use genco::fmt;
+use genco::prelude::*;
+use genco::tokens::ValidationError;
+
+#[test]
+fn test_shebang_first_line() -> fmt::Result {
+    let tokens: rust::Tokens = quote! {
+        fn main() {
+            println!("Hello");
+        }
+    };
+
+    let fmt = fmt::Config::from_lang::<Rust>()
+        .with_shebang("#!/usr/bin/env -S cargo +nightly -Zscript")
+        .with_prelude("// Generated file");
+
+    let output = tokens.to_file_string_with(&rust::Config::default(), &fmt)?;
+
+    assert_eq!(
+        "#!/usr/bin/env -S cargo +nightly -Zscript\n// Generated file\n\nfn main() {\n    println!(\"Hello\");\n}\n",
+        output
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_shebang_empty_file() -> fmt::Result {
+    let tokens = js::Tokens::new();
+
+    let fmt = fmt::Config::from_lang::<JavaScript>().with_shebang("#!/usr/bin/env node");
+    let output = tokens.to_file_string_with(&js::Config::default(), &fmt)?;
+
+    assert_eq!("#!/usr/bin/env node\n", output);
+    Ok(())
+}
+
+#[test]
+fn test_shebang_precedence() -> fmt::Result {
+    let tokens: python::Tokens = quote!(print("hello"));
+
+    let fmt = fmt::Config::from_lang::<Python>().with_shebang("#!/usr/bin/python3");
+    let config = python::Config::default()
+        .with_shebang("#!/usr/bin/env python3")
+        .with_coding(true);
+
+    assert_eq!(
+        "#!/usr/bin/python3\n# -*- coding: utf-8 -*-\n\nprint(\"hello\")\n",
+        tokens.to_file_string_with(&config, &fmt)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_shebang_with_bom() {
+    let tokens = rust::Tokens::new();
+
+    let fmt = fmt::Config::from_lang::<Rust>()
+        .with_shebang("#!/usr/bin/env -S cargo +nightly -Zscript")
+        .with_bom(true);
+
+    assert!(tokens
+        .to_file_string_with(&rust::Config::default(), &fmt)
+        .is_err());
+
+    let mut w = fmt::VecWriter::new();
+    let mut formatter = w.as_formatter(&fmt);
+    assert!(tokens
+        .format_file(&mut formatter, &rust::Config::default())
+        .is_err());
+    assert_eq!(Some(&ValidationError::ShebangWithBom), formatter.error());
+}
@@ -0,0 +1,171 @@
+use std::sync::{Arc, Mutex};
+
+use genco::fmt;
+use genco::prelude::*;
+use genco::tokens::Item;
+
+fn format_with(tokens: &java::Tokens, fmt: &fmt::Config) -> genco::fmt::Result<Vec<String>> {
+    let output = tokens.to_file_string_with(&java::Config::default(), fmt)?;
+    Ok(output.lines().map(String::from).collect())
+}
+
+#[test]
+fn test_soft_break_fits() -> genco::fmt::Result {
+    let tokens: java::Tokens = quote!(call(a,$[soft]b,$[soft]c););
+
+    assert_eq!(vec!["call(a, b, c);"], tokens.to_file_vec()?);
+
+    let fmt = fmt::Config::from_lang::<Java>().with_max_width(14);
+    assert_eq!(vec!["call(a, b, c);"], format_with(&tokens, &fmt)?);
+    Ok(())
+}
+
+#[test]
+fn test_soft_break_wraps() -> genco::fmt::Result {
+    let args = ["alpha", "beta", "gamma", "delta", "epsilon"];
+
+    let tokens: java::Tokens = quote! {
+        class Foo {
+            void bar() {
+                call($(for a in args join (,$[soft]) => $a));
+                done();
+            }
+        }
+    };
+
+    let fmt = fmt::Config::from_lang::<Java>().with_max_width(30);
+
+    assert_eq!(
+        vec![
+            "class Foo {",
+            "    void bar() {",
+            "        call(alpha, beta,",
+            "            gamma, delta,",
+            "            epsilon);",
+            "        done();",
+            "    }",
+            "}",
+        ],
+        format_with(&tokens, &fmt)?
+    );
+
+    let fmt = fmt::Config::from_lang::<Java>().with_max_width(20);
+
+    assert_eq!(
+        vec![
+            "class Foo {",
+            "    void bar() {",
+            "        call(alpha,",
+            "            beta,",
+            "            gamma,",
+            "            delta,",
+            "            epsilon);",
+            "        done();",
+            "    }",
+            "}",
+        ],
+        format_with(&tokens, &fmt)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_soft_break_never_in_quotes() -> genco::fmt::Result {
+    let mut string = java::Tokens::new();
+    string.append(Item::OpenQuote(false));
+    string.append("a very long");
+    string.soft_break();
+    string.append("string which does not fit");
+    string.append(Item::CloseQuote);
+
+    let tokens: java::Tokens = quote!(call($string,$[soft]b););
+
+    let fmt = fmt::Config::from_lang::<Java>().with_max_width(10);
+
+    assert_eq!(
+        vec!["call(\"a very long string which does not fit\",", "    b);"],
+        format_with(&tokens, &fmt)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_soft_break_no_trailing_space() -> genco::fmt::Result {
+    let mut tokens = java::Tokens::new();
+    tokens.append("first");
+    tokens.soft_break();
+    tokens.push();
+    tokens.append("second");
+
+    let fmt = fmt::Config::from_lang::<Java>().with_max_width(10);
+    assert_eq!(vec!["first", "second"], format_with(&tokens, &fmt)?);
+    Ok(())
+}
+
+#[test]
+fn test_soft_break_spans() -> genco::fmt::Result {
+    let tokens: java::Tokens = quote!(call(first,$[soft]second););
+
+    let spans = Arc::new(Mutex::new(Vec::new()));
+    let listener = spans.clone();
+
+    let fmt = fmt::Config::from_lang::<Java>()
+        .with_max_width(16)
+        .with_span_listener(move |span| {
+            listener.lock().unwrap().push((span.start(), span.end()));
+        });
+
+    assert_eq!(
+        vec!["call(first,", "    second);"],
+        format_with(&tokens, &fmt)?
+    );
+
+    assert_eq!(
+        vec![((0, 0), (0, 11)), ((1, 4), (1, 12))],
+        *spans.lock().unwrap()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_soft_break_collapses_like_space() {
+    let a = || Item::<()>::Literal("a".into());
+    let b = || Item::<()>::Literal("b".into());
+
+    let mut t = Tokens::<()>::new();
+    t.append("a");
+    t.soft_break();
+    t.push();
+    t.append("b");
+    assert_eq!(vec![a(), Item::Push, b()], t);
+
+    let mut t = Tokens::<()>::new();
+    t.append("a");
+    t.soft_break();
+    t.indent();
+    t.append("b");
+    assert_eq!(vec![a(), Item::Indentation(1), b()], t);
+
+    let mut t = Tokens::<()>::new();
+    t.soft_break();
+    t.append("a");
+    t.soft_break();
+    t.normalize();
+    assert_eq!(vec![a()], t);
+
+    let mut t = Tokens::<()>::new();
+    t.append("a");
+    t.soft_break();
+    t.append("b");
+    let tail = t.split_off(1);
+    assert_eq!(vec![a()], t);
+    assert_eq!(vec![b()], tail);
+
+    let mut t = Tokens::<()>::new();
+    t.soft_break();
+    assert!(t.is_whitespace_only());
+    assert!(t.validate().is_ok());
+}
@@ -0,0 +1,181 @@
+#![cfg(feature = "locations")]
+
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_source_map() -> fmt::Result {
+    let react = js::import("react", "React").into_default();
+
+    let line = line!() + 2;
+    let toks: js::Tokens = quote! {
+        const a = $react;
+        let b = 1;
+    };
+
+    let mut w = fmt::VecWriter::new();
+
+    let config = js::Config::default().with_source_map(true);
+    let fmt = fmt::Config::from_lang::<JavaScript>();
+    let mut formatter = w.as_formatter(&fmt);
+
+    toks.format_file(&mut formatter, &config)?;
+
+    let source_map = formatter.source_map().expect("source map enabled").clone();
+
+    assert_eq!(
+        vec![
+            "import React from \"react\";",
+            "",
+            "const a = React;",
+            "let b = 1;",
+        ],
+        w.into_vec()
+    );
+
+    assert_eq!(&[file!()], source_map.sources());
+
+    let mapped = source_map
+        .mappings()
+        .iter()
+        .map(|m| {
+            let location = m.location();
+            (
+                m.line(),
+                m.column(),
+                location.line() - line as usize,
+                location.column(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            (2, 0, 0, 8),
+            (2, 6, 0, 14),
+            (2, 8, 0, 16),
+            (2, 15, 0, 24),
+            (3, 0, 1, 8),
+            (3, 4, 1, 12),
+            (3, 6, 1, 14),
+            (3, 8, 1, 16),
+        ],
+        mapped
+    );
+
+    assert_eq!(
+        format!(
+            ";;AA{l}Q,MAAM,EAAE,OAAQ;AAChB,IAAI,EAAE,EAAE",
+            l = vlq(line as usize - 1)
+        ),
+        source_map.to_mappings_string()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_source_map_soft_break() -> fmt::Result {
+    let toks: js::Tokens = quote!(call(first,$[soft]second););
+
+    let mut w = fmt::VecWriter::new();
+
+    let config = js::Config::default().with_source_map(true);
+    let fmt = fmt::Config::from_lang::<JavaScript>().with_max_width(16);
+    let mut formatter = w.as_formatter(&fmt);
+
+    toks.format_file(&mut formatter, &config)?;
+
+    let source_map = formatter.source_map().expect("source map enabled").clone();
+
+    assert_eq!(vec!["call(first,", "    second);"], w.into_vec());
+
+    let mapped = source_map
+        .mappings()
+        .iter()
+        .map(|m| (m.line(), m.column()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec![(0, 0), (1, 4)], mapped);
+    Ok(())
+}
+
+#[test]
+fn test_source_map_utf16_columns() -> fmt::Result {
+    let toks: js::Tokens = quote!(let a = $(quoted("😀")); let b = 1;);
+
+    let mut w = fmt::VecWriter::new();
+
+    let config = js::Config::default().with_source_map(true);
+    let fmt = fmt::Config::from_lang::<JavaScript>();
+    let mut formatter = w.as_formatter(&fmt);
+
+    toks.format_file(&mut formatter, &config)?;
+
+    let source_map = formatter.source_map().expect("source map enabled").clone();
+
+    assert_eq!(vec!["let a = \"😀\"; let b = 1;"], w.into_vec());
+
+    let mapped = source_map
+        .mappings()
+        .iter()
+        .map(|m| (m.line(), m.column()))
+        .collect::<Vec<_>>();
+
+    // NB: the emoji is two UTF-16 code units wide, so the `;` following the
+    // string starts at column 12 rather than 11.
+    assert_eq!(
+        vec![
+            (0, 0),
+            (0, 4),
+            (0, 6),
+            (0, 12),
+            (0, 14),
+            (0, 18),
+            (0, 20),
+            (0, 22)
+        ],
+        mapped
+    );
+    Ok(())
+}
+
+#[test]
+fn test_no_source_map() -> fmt::Result {
+    let toks: js::Tokens = quote!(let a = 1;);
+
+    let mut w = fmt::VecWriter::new();
+
+    let config = js::Config::default();
+    let fmt = fmt::Config::from_lang::<JavaScript>();
+    let mut formatter = w.as_formatter(&fmt);
+
+    toks.format_file(&mut formatter, &config)?;
+    assert!(formatter.source_map().is_none());
+    Ok(())
+}
+
+/// Reference encoding of a single non-negative value.
+fn vlq(mut value: usize) -> String {
+    const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::new();
+    value <<= 1;
+
+    loop {
+        let mut digit = value & 0b11111;
+        value >>= 5;
+
+        if value > 0 {
+            digit |= 0b100000;
+        }
+
+        out.push(BASE64[digit] as char);
+
+        if value == 0 {
+            break;
+        }
+    }
+
+    out
+}
@@ -0,0 +1,154 @@
+use std::sync::{Arc, Mutex};
+
+use genco::fmt;
+use genco::prelude::*;
+
+#[test]
+fn test_span_listener_imports() -> genco::fmt::Result {
+    let list = java::import("java.util", "List");
+
+    let tokens: java::Tokens = quote! {
+        $list<String> l;
+    };
+
+    let spans = Arc::new(Mutex::new(Vec::new()));
+    let listener = spans.clone();
+
+    let fmt = fmt::Config::from_lang::<Java>().with_span_listener(move |span| {
+        listener
+            .lock()
+            .unwrap()
+            .push((span.in_preamble(), span.start(), span.end()));
+    });
+
+    let mut w = fmt::FmtWriter::new(String::new());
+
+    {
+        let mut out = w.as_formatter(&fmt);
+        tokens.format_file(&mut out, &java::Config::default())?;
+        assert_eq!((3, 0), (out.line(), out.column()));
+    }
+
+    assert_eq!(
+        "import java.util.List;\n\nList<String> l;\n",
+        w.into_inner()
+    );
+
+    assert_eq!(
+        vec![
+            // The import statement in the preamble.
+            (true, (0, 0), (0, 6)),
+            (true, (0, 7), (0, 16)),
+            (true, (0, 16), (0, 17)),
+            (true, (0, 17), (0, 21)),
+            (true, (0, 21), (0, 22)),
+            // The body.
+            (false, (2, 0), (2, 4)),
+            (false, (2, 4), (2, 12)),
+            (false, (2, 13), (2, 15)),
+        ],
+        *spans.lock().unwrap()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_span_listener_in_preamble() -> genco::fmt::Result {
+    let tokens: csharp::Tokens = quote! {
+        class Foo {}
+    };
+
+    let spans = Arc::new(Mutex::new(Vec::new()));
+    let listener = spans.clone();
+
+    let fmt = fmt::Config::from_lang::<Csharp>().with_span_listener(move |span| {
+        listener
+            .lock()
+            .unwrap()
+            .push((span.in_preamble(), span.index(), span.start()));
+    });
+
+    let config = csharp::Config::default().with_namespace("Foo.Bar");
+
+    assert_eq!(
+        vec!["namespace Foo.Bar {", "    class Foo {}", "}"],
+        tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+    );
+
+    // Indexes of the namespace declaration collide with the indexes of the
+    // body, which is told apart by not being in the preamble.
+    assert_eq!(
+        vec![
+            (true, 0, (0, 0)),
+            (true, 2, (0, 10)),
+            (true, 4, (0, 18)),
+            (false, 0, (1, 4)),
+            (false, 2, (1, 10)),
+            (false, 4, (1, 14)),
+            (true, 1, (2, 0)),
+        ],
+        *spans.lock().unwrap()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_span_listener_directives() -> genco::fmt::Result {
+    let tokens: go::Tokens = quote!(func Foo() {});
+
+    let spans = Arc::new(Mutex::new(Vec::new()));
+    let listener = spans.clone();
+
+    let fmt = fmt::Config::from_lang::<Go>().with_span_listener(move |span| {
+        listener
+            .lock()
+            .unwrap()
+            .push((span.in_preamble(), span.start()));
+    });
+
+    let config = go::Config::default()
+        .with_package("foo")
+        .with_build_constraint("linux");
+
+    assert_eq!(
+        vec!["//go:build linux", "", "package foo", "", "func Foo() {}"],
+        tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+    );
+
+    let spans = spans.lock().unwrap();
+    let (preamble, body): (Vec<_>, Vec<_>) = spans.iter().partition(|(p, _)| *p);
+
+    assert!(preamble.iter().all(|(_, (line, _))| *line < 4));
+    assert_eq!(
+        vec![(false, (4, 0)), (false, (4, 5)), (false, (4, 11))],
+        body
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_span_listener_empty_body() -> genco::fmt::Result {
+    let tokens = python::Tokens::new();
+
+    let spans = Arc::new(Mutex::new(Vec::new()));
+    let listener = spans.clone();
+
+    let fmt = fmt::Config::from_lang::<Python>().with_span_listener(move |span| {
+        listener.lock().unwrap().push(span.in_preamble());
+    });
+
+    let config = python::Config::default().with_module_docstring("Empty.");
+
+    assert_eq!(
+        vec!["\"\"\"Empty.\"\"\""],
+        tokens.to_file_vec_with(&config, &fmt, fmt::VecWriter::new())?
+    );
+
+    let spans = spans.lock().unwrap();
+    assert!(!spans.is_empty());
+    assert!(spans.iter().all(|in_preamble| *in_preamble));
+    Ok(())
+}
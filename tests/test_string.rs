@@ -1,6 +1,7 @@
 use genco::prelude::*;
 
 #[test]
+#[cfg(feature = "std")]
 fn test_quoted() -> genco::fmt::Result {
     let t: dart::Tokens = quote!($[str](Hello $($(quoted("World")))));
     assert_eq!("\"Hello ${\"World\"}\"", t.to_string()?);
@@ -17,6 +18,7 @@ fn test_quoted() -> genco::fmt::Result {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn test_string_in_string_in() -> genco::fmt::Result {
     let t: dart::Tokens = quote!($[str](Hello $($[str]($($[str](World))))));
     assert_eq!("\"Hello ${\"${\"World\"}\"}\"", t.to_string()?);
@@ -25,3 +27,40 @@ fn test_string_in_string_in() -> genco::fmt::Result {
     assert_eq!("`Hello ${`${\"World\"}`}`", t.to_string()?);
     Ok(())
 }
+
+#[test]
+fn test_swift_multiline_string() -> genco::fmt::Result {
+    let t: swift::Tokens = quote!($(swift::multiline_string("")));
+    assert_eq!(vec!["\"\"\"", "\"\"\""], t.to_file_vec()?);
+
+    let t: swift::Tokens = quote!($(swift::multiline_string("\nfirst\n\n\nsecond\n")));
+    assert_eq!(
+        vec!["\"\"\"", "\\nfirst\\n\\n", "second\\n", "\"\"\""],
+        t.to_file_vec()?
+    );
+
+    let t: swift::Tokens = quote!($(swift::multiline_string("a \"\"\"\" b \\(c)")));
+    assert_eq!(
+        vec!["\"\"\"", "a \"\"\\\"\" b \\\\(c)", "\"\"\""],
+        t.to_file_vec()?
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_quoted_with() -> genco::fmt::Result {
+    use genco::tokens::quoted_with;
+
+    let escape_newlines = |c, out: &mut String| match c {
+        '\n' => out.push_str("\\n"),
+        c => out.push(c),
+    };
+
+    let tokens: rust::Tokens = quote!(let a = $(quoted_with("\"a\"\nb\\", escape_newlines)););
+    assert_eq!("let a = \"\"a\"\\nb\\\";", tokens.to_string()?);
+
+    let tokens: js::Tokens = quote!(const a = $(quoted_with("'a'\n", escape_newlines)););
+    assert_eq!("const a = \"'a'\\n\";", tokens.to_string()?);
+    Ok(())
+}
@@ -2,7 +2,7 @@
 
 use genco::fmt;
 use genco::prelude::*;
-use genco::tokens::{Item::*, ItemStr::*};
+use genco::tokens::{neutral, Item, Item::*, ItemStr::*};
 
 #[test]
 fn test_token_gen() {
@@ -195,6 +195,53 @@ fn test_repeat() {
     };
 }
 
+#[test]
+fn test_while() -> fmt::Result {
+    let mut it = 0..3;
+
+    let tokens: rust::Tokens = quote! {
+        foo $(while let Some(x) = it.next() => $x)
+    };
+
+    assert_eq! {
+        vec![
+            Literal(Static("foo")),
+            Space,
+            Literal("0".into()),
+            Literal("1".into()),
+            Literal("2".into()),
+        ],
+        tokens,
+    };
+
+    let mut stack = vec!["c", "stop", "b", "a"];
+    let mut total = 0;
+
+    let tokens: rust::Tokens = quote! {
+        fn main() {
+            $(while let Some(name) = stack.pop().filter(|name| *name != "stop") {
+                $(let n = { total += 1; total })
+                let $name = $n;
+            })
+            println!("{}", $(total));
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "fn main() {",
+            "    let a = 1;",
+            "    let b = 2;",
+            "    println!(\"{}\", 2);",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    assert_eq!(vec!["c"], stack);
+    Ok(())
+}
+
 #[test]
 fn test_tight_quote() {
     let output: rust::Tokens = quote! {
@@ -632,3 +679,759 @@ fn test_lines() -> fmt::Result {
 
     Ok(())
 }
+
+#[test]
+fn test_push_str_block() {
+    let mut tokens = rust::Tokens::new();
+    tokens.append("start");
+    tokens.push_str_block("first\n\n  second\nthird");
+
+    assert_eq! {
+        vec![
+            Literal("start".into()),
+            Push,
+            Literal("first".into()),
+            Line,
+            Literal("  second".into()),
+            Push,
+            Literal("third".into()),
+        ],
+        tokens,
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_retain() -> fmt::Result {
+    let tokens: js::Tokens = quote! {
+        $(quoted("leading")) foo "bar" baz
+        $("\"") $[str](quoted $(a)) end
+    };
+
+    let mut in_quote = false;
+
+    let mut retained = tokens.clone();
+
+    retained.retain(|item| match item {
+        OpenQuote(..) => {
+            in_quote = true;
+            false
+        }
+        CloseQuote => {
+            in_quote = false;
+            false
+        }
+        _ => !in_quote,
+    });
+
+    assert_eq! {
+        vec![
+            Literal(Static("foo")),
+            Space,
+            Literal(Static("baz")),
+            Push,
+            Literal(Static("\"")),
+            Space,
+            Literal(Static("end")),
+        ],
+        retained,
+    };
+
+    for (a, b) in retained.iter().zip(retained.iter().skip(1)) {
+        assert!(
+            !matches!((a, b), (Space | Push | Line, Space | Push | Line)),
+            "whitespace in sequence: {:?}",
+            retained
+        );
+    }
+
+    assert_eq!(vec!["foo baz", "\" end"], retained.to_file_vec()?);
+    Ok(())
+}
+
+#[test]
+fn test_append_all_spaced() -> fmt::Result {
+    let mut empty = rust::Tokens::new();
+    empty.append_all_spaced(["a", "b"]);
+
+    assert_eq! {
+        vec![Literal("a".into()), Space, Literal("b".into())],
+        empty,
+    };
+
+    assert_eq!("a b", empty.to_string()?);
+
+    let mut tokens: rust::Tokens = quote!(let x =);
+    tokens.append_all_spaced(["1", "+", "2"]);
+
+    assert_eq!("let x = 1 + 2", tokens.to_string()?);
+
+    let mut untouched: rust::Tokens = quote!(x);
+    untouched.append_all_spaced(Vec::<&str>::new());
+
+    assert_eq!("x", untouched.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_append_lines() -> fmt::Result {
+    let mut empty = rust::Tokens::new();
+    empty.append_lines(["a();", "b();", "c();"]);
+
+    assert_eq! {
+        vec![
+            Literal("a();".into()),
+            Push,
+            Literal("b();".into()),
+            Push,
+            Literal("c();".into()),
+        ],
+        empty,
+    };
+
+    let tokens: rust::Tokens = quote! {
+        fn main() {
+            $(ref t => t.append_lines([quote!(a();), quote!(b();), quote!(c();)]))
+        }
+    };
+
+    assert_eq!(
+        vec!["fn main() {", "    a();", "    b();", "    c();", "}"],
+        tokens.to_file_vec()?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_prepend() -> fmt::Result {
+    let mut tokens: rust::Tokens = quote!($['\r']body$[' ']);
+    tokens.prepend(quote!(head$[' ']));
+
+    assert_eq! {
+        vec![
+            Literal(Static("head")),
+            Push,
+            Literal(Static("body")),
+            Space,
+        ],
+        tokens,
+    };
+
+    let mut tokens: rust::Tokens = quote!($['\n']body);
+    tokens.prepend(quote!(head$['\r']));
+
+    assert_eq! {
+        vec![Literal(Static("head")), Line, Literal(Static("body"))],
+        tokens,
+    };
+
+    let mut tokens: rust::Tokens = quote!(body);
+    tokens.prepend(quote!());
+
+    assert_eq!(vec![Literal(Static("body"))], tokens);
+    Ok(())
+}
+
+#[test]
+fn test_insert() -> fmt::Result {
+    let mut tokens: rust::Tokens = quote!(a$[' ']$['\r']c);
+    tokens.insert(0, Literal(Static("z")));
+    tokens.insert(2, Space);
+
+    assert_eq! {
+        vec![
+            Literal(Static("z")),
+            Literal(Static("a")),
+            Push,
+            Literal(Static("c")),
+        ],
+        tokens,
+    };
+
+    let len = tokens.iter().count();
+    tokens.insert(len, Push);
+
+    assert_eq!(vec!["za", "c"], tokens.to_file_vec()?);
+    Ok(())
+}
+
+#[test]
+fn test_map_literals() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+
+    let mut tokens: rust::Tokens = quote! {
+        let m = $map::new(); $("/* TODO */")
+    };
+
+    tokens.map_literals(|s| match &*s {
+        "/* TODO */" => Static(""),
+        "m" => Static("map"),
+        _ => s,
+    });
+
+    tokens.retain(|item| !matches!(item, Literal(s) if s.is_empty()));
+
+    assert_eq! {
+        vec!["use std::collections::HashMap;", "", "let map = HashMap::new();"],
+        tokens.to_file_vec()?,
+    };
+
+    Ok(())
+}
+
+#[test]
+fn test_is_whitespace_only() {
+    let tokens: rust::Tokens = quote!($['\r']$[' ']);
+    assert!(tokens.is_whitespace_only());
+
+    let mut tokens = rust::Tokens::new();
+    assert!(tokens.is_whitespace_only());
+    tokens.indent();
+    tokens.line();
+    tokens.unindent();
+    assert!(tokens.is_whitespace_only());
+
+    let tokens: rust::Tokens = quote!(a);
+    assert!(!tokens.is_whitespace_only());
+
+    let tokens: rust::Tokens = quote!($(rust::import("std::fmt", "Debug")));
+    assert!(!tokens.is_whitespace_only());
+}
+
+#[test]
+fn test_lang_cast() -> fmt::Result {
+    let neutral: Tokens<()> = quote!(a $(quoted("b")) c);
+    let cast = neutral
+        .lang_cast::<Rust>()
+        .expect("no language items to cast");
+
+    assert_eq! {
+        vec![
+            Literal(Static("a")),
+            Space,
+            OpenQuote(false),
+            Literal("b".into()),
+            CloseQuote,
+            Space,
+            Literal(Static("c")),
+        ],
+        cast,
+    };
+
+    assert_eq!("a \"b\" c", cast.to_string()?);
+
+    let mut with_lang: Tokens<()> = quote!(a b);
+    with_lang.append(Lang(0, std::boxed::Box::new(())));
+
+    let error = with_lang.lang_cast::<Rust>().unwrap_err();
+    assert_eq!(3, error.index());
+    Ok(())
+}
+
+#[test]
+fn test_display() -> fmt::Result {
+    let answer = 42;
+
+    let tokens: rust::Tokens = quote!(let a = $(display(answer)););
+    assert_eq!("let a = 42;", tokens.to_string()?);
+
+    let tokens: rust::Tokens = quote!($(display(answer)) + $(display(1.5)));
+    assert_eq!("42 + 1.5", tokens.to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_scope() -> fmt::Result {
+    fn with_macro(args: &[&str]) -> rust::Tokens {
+        quote! {
+            call($(ref t {
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        t.append(",");
+                        t.space();
+                    }
+
+                    t.append(*arg);
+                }
+            }));
+        }
+    }
+
+    fn with_method(args: &[&str]) -> rust::Tokens {
+        let mut t = rust::Tokens::new();
+        t.append("call(");
+
+        t.scope(|t| {
+            for (i, arg) in args.iter().enumerate() {
+                if i > 0 {
+                    t.append(",");
+                    t.space();
+                }
+
+                t.append(*arg);
+            }
+        });
+
+        t.append(");");
+        t
+    }
+
+    for args in [&[][..], &["a"][..], &["a", "b"][..]] {
+        assert_eq!(
+            with_macro(args).to_string()?,
+            with_method(args).to_string()?
+        );
+    }
+
+    assert_eq!("call(a, b);", with_method(&["a", "b"]).to_string()?);
+    Ok(())
+}
+
+#[test]
+fn test_normalize() -> fmt::Result {
+    fn modifiers(public: bool, is_static: bool) -> java::Tokens {
+        quote!($(if public => public)$(if is_static => $[' ']static))
+    }
+
+    fn fields(fields: &[(bool, bool, &str)]) -> java::Tokens {
+        let mut tokens = java::Tokens::new();
+
+        for (public, is_static, name) in fields {
+            tokens.push();
+            tokens.extend(modifiers(*public, *is_static));
+            tokens.space();
+            tokens.append(quote!(String $(*name);));
+            tokens.space();
+        }
+
+        tokens.line();
+        tokens
+    }
+
+    let mut tokens = fields(&[(true, false, "a"), (false, false, "b"), (false, true, "c")]);
+
+    assert_eq!(
+        "public String a;\n String b;\n static String c;",
+        tokens.to_string()?
+    );
+
+    tokens.normalize();
+
+    assert_eq!(
+        "public String a;\nString b;\nstatic String c;",
+        tokens.to_string()?
+    );
+
+    assert_eq! {
+        vec![
+            Literal(Static("public")),
+            Space,
+            Literal(Static("String")),
+            Space,
+            Literal("a".into()),
+            Literal(Static(";")),
+            Push,
+            Literal(Static("String")),
+            Space,
+            Literal("b".into()),
+            Literal(Static(";")),
+            Push,
+            Literal(Static("static")),
+            Space,
+            Literal(Static("String")),
+            Space,
+            Literal("c".into()),
+            Literal(Static(";")),
+        ] as Vec<Item<Java>>,
+        tokens,
+    };
+
+    let header: Option<java::Tokens> = None;
+
+    let mut tokens: java::Tokens = quote! {
+        $(if let Some(header) = &header {
+            $header
+        })
+
+        class Foo {
+            $(if header.is_some() {
+                int a;
+            })
+            int b;
+        }
+    };
+
+    tokens.normalize();
+
+    let expected: java::Tokens = quote! {
+        class Foo {
+            int b;
+        }
+    };
+
+    assert_eq!(expected, tokens);
+    Ok(())
+}
+
+#[test]
+fn test_split_off() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+    let set = rust::import("std::collections", "HashSet");
+
+    let mut tokens: rust::Tokens = quote!(let a = $(&map)::<u32, u32>::new(););
+    tokens.space();
+    let index = tokens.len();
+    tokens.space();
+    tokens.append(quote!(let b = $(&set)::<u32>::new();));
+
+    let rest = tokens.split_off(index);
+
+    assert_eq!(
+        vec![
+            "use std::collections::HashMap;",
+            "",
+            "let a = HashMap::<u32, u32>::new();",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    assert_eq!(
+        vec![
+            "use std::collections::HashSet;",
+            "",
+            "let b = HashSet::<u32>::new();",
+        ],
+        rest.to_file_vec()?
+    );
+
+    assert!(matches!(tokens.get(tokens.len() - 1), Some(Literal(..))));
+    assert!(matches!(rest.get(0), Some(Literal(..))));
+
+    let mut tokens: rust::Tokens = quote!(a $(&map));
+    assert!(matches!(tokens.pop(), Some(Lang(..))));
+    assert_eq!(0, tokens.walk_imports().count());
+    tokens.append(&set);
+    assert_eq!(1, tokens.walk_imports().count());
+    Ok(())
+}
+
+#[test]
+fn test_edit_balances() -> fmt::Result {
+    let mut tokens = rust::Tokens::new();
+    tokens.append("{");
+    tokens.indent();
+    tokens.append("a();");
+
+    tokens.edit(|t| {
+        t.indent();
+        t.append("b(");
+        t.append(OpenQuote(false));
+        t.append("c");
+    });
+
+    tokens.append(");");
+
+    // Indentation opened before the edit can be closed by it.
+    tokens.edit(|t| {
+        t.unindent();
+        t.append("}");
+    });
+
+    assert_eq!(
+        vec![
+            Literal("{".into()),
+            Indentation(1),
+            Literal("a();".into()),
+            Indentation(1),
+            Literal("b(".into()),
+            OpenQuote(false),
+            Literal("c".into()),
+            CloseQuote,
+            Indentation(-1),
+            Literal(");".into()),
+            Indentation(-1),
+            Literal("}".into()),
+        ],
+        tokens
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_scoped_editing() -> fmt::Result {
+    fn body(t: &mut rust::Tokens, fail: bool) -> Result<(), &'static str> {
+        t.append("a();");
+
+        if fail {
+            return Err("failed");
+        }
+
+        t.push();
+        t.append("b();");
+        Ok(())
+    }
+
+    let mut tokens = rust::Tokens::new();
+    tokens.append("{");
+    assert!(tokens.indented(|t| body(t, true)).is_err());
+    tokens.push();
+    tokens.append("}");
+    tokens.push();
+    tokens.append("c(");
+    tokens.quoted_scope(|t| t.append("\"d\""));
+    tokens.append(");");
+
+    assert_eq!(
+        vec![
+            Literal("{".into()),
+            Indentation(1),
+            Literal("a();".into()),
+            Indentation(-1),
+            Push,
+            Literal("}".into()),
+            Push,
+            Literal("c(".into()),
+            OpenQuote(false),
+            Literal("\"d\"".into()),
+            CloseQuote,
+            Literal(");".into()),
+        ],
+        tokens.iter().cloned().collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        vec!["{", "    a();", "}", "c(\"\\\"d\\\"\");"],
+        tokens.to_vec()?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_count_lines() -> fmt::Result {
+    let list = java::import("java.util", "List");
+
+    let mut trailing: java::Tokens = quote!(a);
+    trailing.line();
+
+    let streams: Vec<java::Tokens> = vec![
+        quote!(),
+        quote!(a),
+        trailing,
+        quote! {
+            class Foo {
+                $list<String> a;
+
+                void bar() {
+                    $(quoted("b"));
+                }
+            }
+        },
+    ];
+
+    for tokens in streams {
+        assert_eq!(tokens.to_file_vec()?.len(), tokens.count_lines()?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_collect_tokens() -> fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+
+    let fragments: Vec<rust::Tokens> = vec![
+        quote!(let a = $(&map)::new();),
+        quote!($['\r']),
+        quote!(let b = 1;),
+    ];
+
+    let mut expected = rust::Tokens::new();
+
+    for fragment in &fragments {
+        expected.append(fragment);
+    }
+
+    let tokens = fragments.into_iter().collect::<rust::Tokens>();
+
+    assert_eq!(expected, tokens);
+    assert_eq!(1, tokens.walk_imports().count());
+    assert_eq!(
+        vec![
+            "use std::collections::HashMap;",
+            "",
+            "let a = HashMap::new();",
+            "let b = 1;",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_split_once() -> genco::fmt::Result {
+    let tokens: Tokens<()> = quote!(a __M__ b);
+
+    let (head, tail) = tokens.split_once("__M__").expect("marker");
+    assert_eq!(quote!(a), head);
+    assert_eq!(quote!(b), tail);
+
+    let tokens: Tokens<()> = quote!(__M__ b __M__);
+    let (head, tail) = tokens.split_once("__M__").expect("marker");
+    assert!(head.is_empty());
+    assert_eq!("b __M__", tail.to_string()?);
+
+    assert!(tokens.split_once("__N__").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_file_lines() -> genco::fmt::Result {
+    use genco::fmt;
+    use genco::tokens::Item;
+
+    let map = rust::import("std::collections", "HashMap");
+
+    let tokens: rust::Tokens = quote! {
+        fn foo() {
+            let m = $map::<u32, u32>::new();
+        }
+
+        fn bar() {}
+    };
+
+    let fmt = fmt::Config::from_lang::<Rust>();
+    let config = rust::Config::default();
+
+    let golden = tokens.to_file_string()?;
+
+    assert_eq!(
+        golden.split('\n').collect::<Vec<_>>(),
+        tokens.to_file_vec_with(
+            &config,
+            &fmt,
+            fmt::VecWriter::new().with_keep_trailing(true)
+        )?
+    );
+
+    assert_eq!(
+        tokens.to_file_vec()?,
+        tokens
+            .lines(&config, &fmt)
+            .collect::<fmt::Result<Vec<_>>>()?
+    );
+
+    let mut broken = tokens.clone();
+    broken.extend([Item::CloseQuote]);
+
+    let mut lines = broken.lines(&config, &fmt);
+    assert!(matches!(lines.next(), Some(Err(..))));
+    assert!(lines.next().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_embed_neutral_fragment() -> fmt::Result {
+    use std::sync::Arc;
+
+    let body: Arc<Tokens<()>> = Arc::new(quote!(a + b));
+
+    let fragment: Tokens<()> = quote! {
+        let sum = $(&body);
+        sum
+    };
+
+    let tokens: rust::Tokens = quote! {
+        fn add(a: u32, b: u32) -> u32 {
+            $(neutral(fragment))
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "fn add(a: u32, b: u32) -> u32 {",
+            "    let sum = a + b;",
+            "    sum",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_debug_alternate() {
+    let tokens: rust::Tokens = quote! {
+        fn foo() {
+            if bar {
+                baz();
+            }
+        }
+    };
+
+    let compact = format!("{:?}", tokens);
+    assert!(!compact.contains('\n'));
+
+    let pretty = format!("{:#?}", tokens);
+    assert!(pretty.contains('\n'));
+    assert!(pretty.contains("\n            Literal(Static(\"if\")),\n"));
+    assert!(pretty.contains("\n                Literal(Static(\"baz();\")),\n"));
+    assert!(pretty.contains("\n        Literal(Static(\"}\")),\n    ],\n"));
+    assert!(pretty.contains("\n    last_lang_item: 0,\n"));
+}
+
+#[test]
+fn test_debug_alternate_shared() {
+    let shared = std::sync::Arc::new(quote! {
+        if bar {
+            baz();
+        }
+    });
+
+    let tokens: rust::Tokens = quote! {
+        fn foo() {
+            $(&shared)
+        }
+    };
+
+    let pretty = format!("{:#?}", tokens);
+
+    assert!(pretty.contains(concat!(
+        "\n            Shared(0, [\n",
+        "                Literal(Static(\"if\")),\n",
+    )));
+    assert!(pretty.contains("\n                    Literal(Static(\"baz();\")),\n"));
+    assert!(pretty.contains("\n            ]),\n"));
+}
+
+#[test]
+fn test_as_slice() {
+    let tokens: rust::Tokens = quote!(fn $(rust::import("std::fmt", "Debug"))() {});
+    let items = tokens.as_slice();
+
+    assert_eq!(tokens.len(), items.len());
+    assert_eq!(Literal(Static("fn")), items[0]);
+    assert!(matches!(items[2], Lang(..)));
+    assert_eq!(Literal(Static("()")), items[3]);
+}
+
+#[test]
+fn test_neutral_skips_lang_items() -> fmt::Result {
+    let mut fragment: Tokens<()> = quote!(a);
+    fragment.append(genco::tokens::__lang_item::<()>(()));
+    fragment.append(quote!($[' ']b));
+
+    assert!(fragment.clone().lang_cast::<Rust>().is_err());
+
+    let tokens: rust::Tokens = quote!($(neutral(fragment)));
+
+    assert_eq! {
+        vec![Literal(Static("a")), Space, Literal(Static("b"))],
+        tokens,
+    };
+
+    assert_eq!("a b", tokens.to_string()?);
+    Ok(())
+}
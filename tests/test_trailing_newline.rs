@@ -0,0 +1,68 @@
+use genco::fmt;
+use genco::prelude::*;
+
+fn ends_in_literal() -> rust::Tokens {
+    quote!(
+        fn foo() {}
+    )
+}
+
+fn ends_in_line() -> rust::Tokens {
+    let mut tokens = ends_in_literal();
+    tokens.line();
+    tokens
+}
+
+fn ends_in_push() -> rust::Tokens {
+    let mut tokens = ends_in_literal();
+    tokens.push();
+    tokens
+}
+
+#[test]
+fn test_trailing_newline() -> fmt::Result {
+    let config = rust::Config::default();
+    let fmt = fmt::Config::from_lang::<Rust>();
+
+    for tokens in [ends_in_literal(), ends_in_line(), ends_in_push()] {
+        assert_eq!("fn foo() {}\n", tokens.to_file_string()?);
+        assert_eq!("fn foo() {}\n", tokens.to_file_string_with(&config, &fmt)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_trailing_newline_crlf() -> fmt::Result {
+    let config = rust::Config::default();
+    let fmt = fmt::Config::from_lang::<Rust>().with_newline("\r\n");
+
+    for tokens in [ends_in_literal(), ends_in_line(), ends_in_push()] {
+        assert_eq!(
+            "fn foo() {}\r\n",
+            tokens.to_file_string_with(&config, &fmt)?
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_no_trailing_newline() -> fmt::Result {
+    let config = rust::Config::default();
+    let fmt = fmt::Config::from_lang::<Rust>().with_trailing_newline(false);
+
+    for tokens in [ends_in_literal(), ends_in_line(), ends_in_push()] {
+        assert_eq!("fn foo() {}", tokens.to_file_string_with(&config, &fmt)?);
+    }
+
+    let map = rust::import("std::collections", "HashMap");
+    let tokens: rust::Tokens = quote!(let m = $map::<u32, u32>::new(););
+
+    assert_eq!(
+        "use std::collections::HashMap;\n\nlet m = HashMap::<u32, u32>::new();",
+        tokens.to_file_string_with(&config, &fmt)?
+    );
+
+    Ok(())
+}
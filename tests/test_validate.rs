@@ -0,0 +1,206 @@
+use genco::prelude::*;
+use genco::tokens::{Item, ItemStr, ValidationError};
+
+#[test]
+fn test_reserved_rust_import() {
+    let m = rust::import("foo", "super");
+
+    let tokens: rust::Tokens = quote! {
+        fn test() {
+            $m();
+        }
+    };
+
+    assert_eq!(
+        tokens.validate(),
+        Err(ValidationError::ReservedName {
+            index: 6,
+            name: String::from("super"),
+        })
+    );
+}
+
+#[test]
+fn test_reserved_rust_alias() {
+    let m = rust::import("foo", "bar").with_alias("Self");
+
+    let tokens: rust::Tokens = quote!($m);
+
+    assert!(matches!(
+        tokens.validate(),
+        Err(ValidationError::ReservedName { name, .. }) if name == "Self"
+    ));
+}
+
+#[test]
+fn test_raw_rust_import() -> genco::fmt::Result {
+    let m = rust::import("foo", "match");
+
+    let tokens: rust::Tokens = quote! {
+        fn test() {
+            $m();
+        }
+    };
+
+    assert_eq!(tokens.validate(), Ok(()));
+
+    assert_eq!(
+        vec![
+            "use foo::r#match;",
+            "",
+            "fn test() {",
+            "    r#match();",
+            "}"
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_raw_rust_alias() -> genco::fmt::Result {
+    let m = rust::import("foo", "bar").with_alias("type");
+
+    let tokens: rust::Tokens = quote!($m);
+
+    assert_eq!(tokens.validate(), Ok(()));
+    assert_eq!(
+        vec!["use foo::bar as r#type;", "", "r#type"],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_raw_rust_reserved_keyword() -> genco::fmt::Result {
+    let a = rust::import("foo", "gen");
+    let b = rust::import("foo", "bar").with_alias("yield");
+
+    let tokens: rust::Tokens = quote!($a($b));
+
+    assert_eq!(tokens.validate(), Ok(()));
+    assert_eq!(
+        vec!["use foo::{bar as r#yield, r#gen};", "", "r#gen(r#yield)"],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_reserved_registered_import() {
+    let class = java::import("foo", "class");
+
+    let mut tokens = java::Tokens::new();
+    tokens.register(class);
+
+    assert!(tokens.validate().is_err());
+}
+
+#[test]
+fn test_valid_imports() {
+    let map = rust::import("std::collections", "HashMap");
+    let tokens: rust::Tokens = quote!($map::<u32, u32>::new());
+    assert_eq!(tokens.validate(), Ok(()));
+
+    let def = python::import("foo", "define");
+    let tokens: python::Tokens = quote!($def());
+    assert_eq!(tokens.validate(), Ok(()));
+
+    let def = python::import("foo", "def");
+    let tokens: python::Tokens = quote!($def());
+    assert!(tokens.validate().is_err());
+}
+
+#[test]
+fn test_iter_mut_keeps_structure() {
+    let mut tokens: rust::Tokens = quote!(a b c);
+
+    for mut item in tokens.iter_mut() {
+        if let Some(s) = item.literal_mut() {
+            if &**s == "b" {
+                *s = ItemStr::Static("d");
+            }
+        }
+    }
+
+    assert_eq!(tokens.validate(), Ok(()));
+    assert_eq!(tokens, quote!(a d c));
+}
+
+#[test]
+fn test_quote_nesting() {
+    let mut tokens: rust::Tokens = quote!(a);
+    tokens.extend([Item::CloseQuote]);
+    assert_eq!(
+        tokens.validate(),
+        Err(ValidationError::UnexpectedCloseQuote { index: 1 })
+    );
+
+    let mut tokens: rust::Tokens = quote!(a);
+    tokens.extend([Item::OpenQuote(false), Item::Literal("b".into())]);
+    assert_eq!(
+        tokens.validate(),
+        Err(ValidationError::UnclosedQuote { index: 1 })
+    );
+
+    let tokens: rust::Tokens = quote!($(quoted(quote!(a $(quoted("b"))))));
+    assert_eq!(tokens.validate(), Ok(()));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_eval_nesting() {
+    let mut tokens: js::Tokens = quote!(a);
+    tokens.extend([Item::OpenEval]);
+    assert_eq!(
+        tokens.validate(),
+        Err(ValidationError::UnexpectedOpenEval { index: 1 })
+    );
+
+    let mut tokens: js::Tokens = js::Tokens::new();
+    tokens.extend([Item::OpenQuote(true), Item::CloseEval, Item::CloseQuote]);
+    assert_eq!(
+        tokens.validate(),
+        Err(ValidationError::UnexpectedCloseEval { index: 1 })
+    );
+
+    let mut tokens: js::Tokens = js::Tokens::new();
+    tokens.extend([Item::OpenQuote(true), Item::OpenEval]);
+    assert_eq!(
+        tokens.validate(),
+        Err(ValidationError::UnclosedEval { index: 1 })
+    );
+
+    let tokens: js::Tokens = quote!($[str](Hello $(world)));
+    assert_eq!(tokens.validate(), Ok(()));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_format_error_diagnostic() {
+    let mut tokens: js::Tokens = quote!(let a =);
+    tokens.extend([Item::CloseEval]);
+
+    let fmt = genco::fmt::Config::from_lang::<JavaScript>();
+    let mut w = genco::fmt::FmtWriter::new(String::new());
+    let mut formatter = w.as_formatter(&fmt);
+
+    let result = tokens.format(
+        &mut formatter,
+        &js::Config::default(),
+        &js::Format::default(),
+    );
+
+    assert!(result.is_err());
+    assert_eq!(
+        formatter.error(),
+        Some(&ValidationError::UnexpectedCloseEval { index: 5 })
+    );
+    assert_eq!(
+        "close evaluation at index 5 without an open evaluation",
+        formatter.error().unwrap().to_string()
+    );
+}
@@ -0,0 +1,124 @@
+use genco::prelude::*;
+use genco::tokens::visit::{self, Fold, Visitor};
+use genco::tokens::{Item, ItemStr};
+
+#[derive(Default)]
+struct Stats {
+    literals: usize,
+    whitespace: usize,
+    imports: usize,
+    quotes: usize,
+}
+
+impl Visitor<Java> for Stats {
+    fn visit_literal(&mut self, _: &str) {
+        self.literals += 1;
+    }
+
+    fn visit_whitespace(&mut self, _: &Item<Java>) {
+        self.whitespace += 1;
+    }
+
+    fn visit_lang(&mut self, _: &java::Import) {
+        self.imports += 1;
+    }
+
+    fn visit_open_quote(&mut self, _: bool) {
+        self.quotes += 1;
+    }
+}
+
+#[test]
+fn test_visit_stats() {
+    let list = java::import("java.util", "List");
+
+    let tokens: java::Tokens = quote! {
+        class Foo {
+            $list<String> names = $(quoted("a"));
+        }
+    };
+
+    let mut stats = Stats::default();
+    tokens.visit(&mut stats);
+
+    assert_eq!(stats.imports, 1);
+    assert_eq!(stats.quotes, 1);
+    assert_eq!(stats.literals, 9);
+    assert_eq!(stats.whitespace, 7);
+}
+
+/// Qualify every literal outside of quotes which starts with `_` with the
+/// given namespace.
+struct Qualify {
+    namespace: &'static str,
+    in_quote: bool,
+}
+
+impl Fold<Java> for Qualify {
+    fn fold_item(&mut self, item: Item<Java>, out: &mut java::Tokens) {
+        match item {
+            Item::OpenQuote(..) => self.in_quote = true,
+            Item::CloseQuote => self.in_quote = false,
+            Item::Literal(literal) => return self.fold_literal(literal, out),
+            _ => {}
+        }
+
+        out.append(item);
+    }
+
+    fn fold_literal(&mut self, literal: ItemStr, out: &mut java::Tokens) {
+        match literal.strip_prefix('_') {
+            Some(rest) if !self.in_quote => {
+                out.append(self.namespace);
+                out.append(".");
+                out.append(rest.to_owned());
+            }
+            _ => out.append(Item::Literal(literal)),
+        }
+    }
+}
+
+#[test]
+fn test_fold() -> genco::fmt::Result {
+    let list = java::import("java.util", "List");
+
+    let tokens: java::Tokens = quote! {
+        class Foo {
+            $list<String> names = $(quoted("_Name"));
+            _Name name;
+        }
+    };
+
+    let tokens = tokens.fold(&mut Qualify {
+        namespace: "com.example",
+        in_quote: false,
+    });
+
+    assert_eq!(
+        vec![
+            "import java.util.List;",
+            "",
+            "class Foo {",
+            "    List<String> names = \"_Name\";",
+            "    com.example.Name name;",
+            "}",
+        ],
+        tokens.to_file_vec()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_rename() -> genco::fmt::Result {
+    let tokens: java::Tokens = quote! {
+        int count = 0;
+        count += 1;
+    };
+
+    let tokens = tokens.fold(&mut visit::rename("count", "total"));
+
+    assert_eq!(vec!["int total = 0;", "total += 1;"], tokens.to_file_vec()?);
+
+    Ok(())
+}
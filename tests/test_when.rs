@@ -0,0 +1,66 @@
+use std::cell::Cell;
+
+use genco::prelude::*;
+use genco::tokens::{from_fn, or_else, when};
+
+#[test]
+fn test_when() -> genco::fmt::Result {
+    let calls = Cell::new(0);
+
+    let body = |debug: bool| -> rust::Tokens {
+        let trace = from_fn(|t: &mut rust::Tokens| {
+            calls.set(calls.get() + 1);
+            quote_in!(*t => dbg!(x););
+        });
+
+        quote! {
+            fn foo(x: u32) {
+                $(when(debug, trace))
+                bar(x);
+            }
+        }
+    };
+
+    assert_eq!(
+        vec!["fn foo(x: u32) {", "    dbg!(x);", "    bar(x);", "}"],
+        body(true).to_file_vec()?
+    );
+    assert_eq!(1, calls.get());
+
+    assert_eq!(
+        vec!["fn foo(x: u32) {", "    bar(x);", "}"],
+        body(false).to_file_vec()?
+    );
+    assert_eq!(1, calls.get());
+    Ok(())
+}
+
+#[test]
+fn test_or_else() -> genco::fmt::Result {
+    let map = rust::import("std::collections", "HashMap");
+    let btree = rust::import("std::collections", "BTreeMap");
+
+    let field = |ordered: bool| -> rust::Tokens {
+        quote!(values: $(or_else(ordered, quote!($(&btree)<String, u32>), quote!($(&map)<String, u32>))),)
+    };
+
+    assert_eq!(
+        vec![
+            "use std::collections::BTreeMap;",
+            "",
+            "values: BTreeMap<String, u32>,",
+        ],
+        field(true).to_file_vec()?
+    );
+
+    assert_eq!(
+        vec![
+            "use std::collections::HashMap;",
+            "",
+            "values: HashMap<String, u32>,",
+        ],
+        field(false).to_file_vec()?
+    );
+
+    Ok(())
+}
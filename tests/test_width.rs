@@ -0,0 +1,101 @@
+use genco::fmt;
+use genco::prelude::*;
+use std::fmt::Write as _;
+
+#[derive(Default)]
+struct Config {}
+
+#[derive(Default)]
+struct Format {}
+
+genco::impl_lang! {
+    Table {
+        type Config = Config;
+        type Item = Any;
+        type Format = Format;
+    }
+
+    // Pads the current line with spaces up to the given column.
+    Align {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            let pad = self.0.saturating_sub(out.column());
+            write!(out, "{:pad$}", "", pad = pad)?;
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+struct Align(usize);
+
+fn table() -> Tokens<Table> {
+    quote! {
+        $("ab")$(Align(6))| left
+        $("漢字")$(Align(6))| right
+    }
+}
+
+#[test]
+fn test_width_chars() -> fmt::Result {
+    let mut w = fmt::VecWriter::new();
+    let fmt = fmt::Config::from_lang::<Table>().with_width_measure(fmt::WidthMode::Chars);
+    table().format(
+        &mut w.as_formatter(&fmt),
+        &Config::default(),
+        &Format::default(),
+    )?;
+
+    assert_eq!(vec!["ab    | left", "漢字    | right"], w.into_vec());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "unicode-width")]
+fn test_width_unicode() -> fmt::Result {
+    let mut w = fmt::VecWriter::new();
+    let fmt = fmt::Config::from_lang::<Table>().with_width_measure(fmt::WidthMode::Unicode);
+    table().format(
+        &mut w.as_formatter(&fmt),
+        &Config::default(),
+        &Format::default(),
+    )?;
+
+    assert_eq!(vec!["ab    | left", "漢字  | right"], w.into_vec());
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "unicode-width")]
+fn test_width_indentation() -> fmt::Result {
+    let tokens: Tokens<Table> = quote! {
+        {
+            $("日本")$(Align(10))x
+        }
+    };
+
+    let mut w = fmt::VecWriter::new();
+    let fmt = fmt::Config::from_lang::<Table>().with_width_measure(fmt::WidthMode::Unicode);
+    tokens.format(
+        &mut w.as_formatter(&fmt),
+        &Config::default(),
+        &Format::default(),
+    )?;
+
+    assert_eq!(vec!["{", "    日本  x", "}"], w.into_vec());
+    Ok(())
+}
+
+#[test]
+fn test_width_untracked() -> fmt::Result {
+    let mut w = fmt::VecWriter::new();
+    let fmt = fmt::Config::from_lang::<Table>();
+    table().format(
+        &mut w.as_formatter(&fmt),
+        &Config::default(),
+        &Format::default(),
+    )?;
+
+    // Without anything consuming the column, only whitespace is counted.
+    assert_eq!(vec!["ab      | left", "漢字      | right"], w.into_vec());
+    Ok(())
+}
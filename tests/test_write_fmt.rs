@@ -0,0 +1,114 @@
+use genco::fmt;
+use genco::prelude::*;
+use genco::tokens::Item;
+use std::fmt::Write as _;
+
+#[derive(Default)]
+struct Config {}
+
+#[derive(Default)]
+struct Format {}
+
+genco::impl_lang! {
+    Script {
+        type Config = Config;
+        type Item = Any;
+        type Format = Format;
+    }
+
+    // Writes a summary of the given values over multiple lines.
+    Summary {
+        fn format(&self, out: &mut fmt::Formatter<'_>, _: &Config, _: &Format) -> fmt::Result {
+            write!(out, "min = {:.2};\n\nmax = {:.2};\r\nlen = {};", self.0, self.1, self.2)?;
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Summary(f64, f64, usize);
+
+impl core::hash::Hash for Summary {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+        self.1.to_bits().hash(state);
+        self.2.hash(state);
+    }
+}
+
+impl Eq for Summary {}
+
+impl PartialOrd for Summary {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Summary {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0
+            .total_cmp(&other.0)
+            .then(self.1.total_cmp(&other.1))
+            .then(self.2.cmp(&other.2))
+    }
+}
+
+fn format(tokens: &Tokens<Script>) -> Result<Vec<String>, fmt::Error> {
+    let mut w = fmt::VecWriter::new();
+    let fmt = fmt::Config::from_lang::<Script>();
+    tokens.format(
+        &mut w.as_formatter(&fmt),
+        &Config::default(),
+        &Format::default(),
+    )?;
+    Ok(w.into_vec())
+}
+
+#[test]
+fn test_write_fmt_multiline() -> fmt::Result {
+    let tokens: Tokens<Script> = quote! {
+        stats {
+            $(Summary(0.5, 1.23456, 4))
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "stats {",
+            "    min = 0.50;",
+            "",
+            "    max = 1.23;",
+            "    len = 4;",
+            "}",
+        ],
+        format(&tokens)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_write_fmt_quoted_verbatim() -> fmt::Result {
+    let mut string = Tokens::<Script>::new();
+    string.append(Item::OpenQuote(false));
+    string.append("values: ");
+    string.append(Summary(1.0, 2.0, 2));
+    string.append(Item::CloseQuote);
+
+    let tokens: Tokens<Script> = quote! {
+        stats {
+            $string
+        }
+    };
+
+    assert_eq!(
+        vec![
+            "stats {",
+            "    \"values: min = 1.00;\n\nmax = 2.00;\r\nlen = 2;\"",
+            "}",
+        ],
+        format(&tokens)?
+    );
+
+    Ok(())
+}